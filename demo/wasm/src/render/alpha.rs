@@ -2,6 +2,7 @@
 //! alpha_gradient, image_alpha, alpha_mask3, image_transforms, mol_view,
 //! image_resample, alpha_mask2.
 
+use agg_rust::alpha_mask_u8::{AlphaMaskGray8, OneComponentMask};
 use agg_rust::bounding_rect::bounding_rect;
 use agg_rust::basics::{VertexSource, PATH_CMD_LINE_TO, PATH_CMD_MOVE_TO, PATH_CMD_STOP, PATH_FLAGS_CCW};
 use agg_rust::conv_curve::ConvCurve;
@@ -16,6 +17,7 @@ use agg_rust::gsv_text::GsvText;
 use agg_rust::image_accessors::ImageAccessorClone;
 use agg_rust::image_filters::{ImageFilterBilinear, ImageFilterLut};
 use agg_rust::path_storage::PathStorage;
+use agg_rust::pixfmt_amask_adaptor::PixfmtAmaskAdaptor;
 use agg_rust::pixfmt_gray::PixfmtGray8;
 use agg_rust::pixfmt_rgba::PixfmtRgba32;
 use agg_rust::rasterizer_scanline_aa::RasterizerScanlineAa;
@@ -373,13 +375,17 @@ pub fn alpha_mask_demo(width: u32, height: u32, params: &[f64]) -> Vec<u8> {
     mtx.multiply(&TransAffine::new_skewing(skew_x / 1000.0, skew_y / 1000.0));
     mtx.multiply(&TransAffine::new_translation(w / 2.0, h / 2.0));
 
-    let mut lion_buf = vec![0u8; (width * height * 4) as usize];
+    // Render the lion over a white background through the mask.
+    let mut mask_ra = RowAccessor::new();
+    unsafe { mask_ra.attach(mask_buf.as_mut_ptr(), width, height, width as i32) };
+    let mask = AlphaMaskGray8::new(&mask_ra, OneComponentMask);
+
+    let mut buf = vec![255u8; (width * height * 4) as usize];
     {
-        let mut lion_ra = RowAccessor::new();
-        unsafe { lion_ra.attach(lion_buf.as_mut_ptr(), width, height, (width * 4) as i32) };
-        let lion_pf = PixfmtRgba32::new(&mut lion_ra);
-        let mut lion_rb = RendererBase::new(lion_pf);
-        lion_rb.clear(&Rgba8::new(0, 0, 0, 0));
+        let mut ra = RowAccessor::new();
+        unsafe { ra.attach(buf.as_mut_ptr(), width, height, (width * 4) as i32) };
+        let pf = PixfmtAmaskAdaptor::new(PixfmtRgba32::new(&mut ra), &mask);
+        let mut rb = RendererBase::new(pf);
 
         let mut ras = RasterizerScanlineAa::new();
         let mut sl = ScanlineU8::new();
@@ -387,37 +393,7 @@ pub fn alpha_mask_demo(width: u32, height: u32, params: &[f64]) -> Vec<u8> {
         for i in 0..npaths {
             ras.reset();
             ras.add_path(&mut transformed, path_idx[i] as u32);
-            render_scanlines_aa_solid(&mut ras, &mut sl, &mut lion_rb, &colors[i]);
-        }
-    }
-
-    // Composite the masked lion over a white background.
-    let mut buf = vec![255u8; (width * height * 4) as usize];
-    for y in 0..height as usize {
-        for x in 0..width as usize {
-            let i = y * width as usize + x;
-            let pi = i * 4;
-            let sr = lion_buf[pi] as u32;
-            let sg = lion_buf[pi + 1] as u32;
-            let sb = lion_buf[pi + 2] as u32;
-            let sa = lion_buf[pi + 3] as u32;
-            if sa == 0 {
-                continue;
-            }
-            let m = mask_buf[i] as u32;
-            let a = (255 + sa * m) >> 8;
-            if a == 0 {
-                continue;
-            }
-            let inv_a = 255 - a;
-            // lion_buf RGB is already premultiplied by lion alpha; apply only mask.
-            let src_r = (255 + sr * m) >> 8;
-            let src_g = (255 + sg * m) >> 8;
-            let src_b = (255 + sb * m) >> 8;
-            buf[pi] = (src_r + ((255 + buf[pi] as u32 * inv_a) >> 8)).min(255) as u8;
-            buf[pi + 1] = (src_g + ((255 + buf[pi + 1] as u32 * inv_a) >> 8)).min(255) as u8;
-            buf[pi + 2] = (src_b + ((255 + buf[pi + 2] as u32 * inv_a) >> 8)).min(255) as u8;
-            buf[pi + 3] = 255;
+            render_scanlines_aa_solid(&mut ras, &mut sl, &mut rb, &colors[i]);
         }
     }
 
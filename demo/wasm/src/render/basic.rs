@@ -20,7 +20,7 @@ use agg_rust::math::{calc_distance, calc_line_point_distance};
 use agg_rust::math_stroke::{InnerJoin, LineCap, LineJoin};
 use agg_rust::path_storage::PathStorage;
 use agg_rust::pixfmt_rgba::PixfmtRgba32;
-use agg_rust::rasterizer_scanline_aa::{RasterizerScanlineAa, Scanline};
+use agg_rust::rasterizer_scanline_aa::RasterizerScanlineAa;
 use agg_rust::renderer_base::RendererBase;
 use agg_rust::renderer_scanline::{render_scanlines_aa, render_scanlines_aa_solid, SpanGenerator};
 use agg_rust::rendering_buffer::RowAccessor;
@@ -1796,30 +1796,12 @@ pub fn rasterizers(width: u32, height: u32, params: &[f64]) -> Vec<u8> {
     let mut rb = RendererBase::new(pf);
     rb.clear(&Rgba8::new(255, 255, 255, 255));
 
-    let mut render_with_gamma = |path: &mut PathStorage, color: Rgba8, map_cover: &dyn Fn(u8) -> u8| {
+    let mut render_with_gamma = |path: &mut PathStorage, color: Rgba8, gamma: &dyn GammaFunction| {
         let mut ras = RasterizerScanlineAa::new();
         let mut sl = ScanlineU8::new();
+        ras.gamma(gamma);
         ras.add_path(path, 0);
-        if !ras.rewind_scanlines() {
-            return;
-        }
-        sl.reset(ras.min_x(), ras.max_x());
-        while ras.sweep_scanline(&mut sl) {
-            let y = sl.y();
-            let spans = sl.begin();
-            let covers = sl.covers();
-            for span in spans {
-                if span.len <= 0 {
-                    continue;
-                }
-                let len = span.len as usize;
-                let mut mapped = Vec::with_capacity(len);
-                for cov in &covers[span.cover_offset..span.cover_offset + len] {
-                    mapped.push(map_cover(*cov));
-                }
-                rb.blend_solid_hspan(span.x, y, span.len, &color, &mapped);
-            }
-        }
+        render_scanlines_aa_solid(&mut ras, &mut sl, &mut rb, &color);
     };
 
     let a = (alpha.clamp(0.0, 1.0) * 255.0) as u32;
@@ -1831,12 +1813,7 @@ pub fn rasterizers(width: u32, height: u32, params: &[f64]) -> Vec<u8> {
         path.line_to(vx1, vy1);
         path.line_to(vx2, vy2);
         path.close_polygon(0);
-        let gp = GammaPower::new(gamma * 2.0);
-        render_with_gamma(
-            &mut path,
-            Rgba8::new(179, 128, 26, a),
-            &|cov: u8| ((gp.call(cov as f64 / 255.0) * 255.0) + 0.5) as u8,
-        );
+        render_with_gamma(&mut path, Rgba8::new(179, 128, 26, a), &GammaPower::new(gamma * 2.0));
     }
 
     // Left triangle: aliased with gamma_threshold(gamma), shifted by -200.
@@ -1846,12 +1823,7 @@ pub fn rasterizers(width: u32, height: u32, params: &[f64]) -> Vec<u8> {
         path.line_to(vx1 - 200.0, vy1);
         path.line_to(vx2 - 200.0, vy2);
         path.close_polygon(0);
-        let gt = GammaThreshold::new(gamma);
-        render_with_gamma(
-            &mut path,
-            Rgba8::new(26, 128, 179, a),
-            &|cov: u8| if gt.call(cov as f64 / 255.0) >= 0.5 { 255 } else { 0 },
-        );
+        render_with_gamma(&mut path, Rgba8::new(26, 128, 179, a), &GammaThreshold::new(gamma));
     }
 
     let mut ras = RasterizerScanlineAa::new();
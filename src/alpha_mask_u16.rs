@@ -0,0 +1,474 @@
+//! 16-bit alpha masking with clipping support.
+//!
+//! The 16-bit counterpart of `agg_alpha_mask_u8.h`: the mask buffer holds
+//! native-endian `u16` values (e.g. rendered through `PixfmtGray16`), and
+//! coverage values are modulated against the full 16-bit mask value, so only
+//! the final cover is rounded to 8 bits.
+//!
+//! `STEP` and `OFFSET` are counted in `u16` components, as in C++ where
+//! they are in units of the buffer's `value_type`.
+
+use crate::alpha_mask_u8::AlphaMask;
+use crate::rendering_buffer::RowAccessor;
+
+// ============================================================================
+// MaskFunctionU16 trait
+// ============================================================================
+
+/// Trait for computing a 16-bit mask value from pixel data.
+///
+/// `p` is the pixel's component slice, already decoded to `u16`.
+pub trait MaskFunctionU16 {
+    fn calculate(&self, p: &[u16]) -> u16;
+}
+
+/// Single-component mask: returns the first component.
+#[derive(Clone, Copy, Default)]
+pub struct OneComponentMaskU16;
+
+impl MaskFunctionU16 for OneComponentMaskU16 {
+    #[inline]
+    fn calculate(&self, p: &[u16]) -> u16 {
+        p[0]
+    }
+}
+
+/// RGB-to-gray mask: weighted sum of R, G, B components.
+///
+/// Same luminance weights as `RgbToGrayMask`: `R*77 + G*150 + B*29 >> 8`.
+#[derive(Clone, Copy)]
+pub struct RgbToGrayMaskU16 {
+    pub r_offset: usize,
+    pub g_offset: usize,
+    pub b_offset: usize,
+}
+
+impl RgbToGrayMaskU16 {
+    pub const fn new(r: usize, g: usize, b: usize) -> Self {
+        Self {
+            r_offset: r,
+            g_offset: g,
+            b_offset: b,
+        }
+    }
+}
+
+impl MaskFunctionU16 for RgbToGrayMaskU16 {
+    #[inline]
+    fn calculate(&self, p: &[u16]) -> u16 {
+        ((p[self.r_offset] as u32 * 77
+            + p[self.g_offset] as u32 * 150
+            + p[self.b_offset] as u32 * 29)
+            >> 8) as u16
+    }
+}
+
+// ============================================================================
+// Shared helpers
+// ============================================================================
+
+const MASK_SHIFT: u32 = 16;
+const MASK_FULL: u32 = 0xFFFF;
+
+/// Combine an 8-bit cover with a 16-bit mask value.
+///
+/// Same form as the 8-bit `(cover_full + val * mask) >> cover_shift`.
+#[inline]
+fn combine(val: u8, mask: u16) -> u8 {
+    ((MASK_FULL + val as u32 * mask as u32) >> MASK_SHIFT) as u8
+}
+
+/// Reduce a 16-bit mask value to an 8-bit cover.
+#[inline]
+fn to_cover(mask: u16) -> u8 {
+    (mask >> 8) as u8
+}
+
+/// Clip a span of `len` pixels starting at `pos` against `0..limit`.
+///
+/// Returns `(dst_offset, start, count)` of the visible part, or `None` if
+/// nothing is visible. Port of the clipping prologue shared by the
+/// `alpha_mask_u8` span methods.
+#[inline]
+fn clip_span(pos: i32, len: usize, limit: i32) -> Option<(usize, i32, usize)> {
+    let mut count = len as i32;
+    let mut off = 0usize;
+    let mut pos = pos;
+    if pos < 0 {
+        count += pos;
+        if count <= 0 {
+            return None;
+        }
+        off = (-pos) as usize;
+        pos = 0;
+    }
+    if pos + count > limit {
+        count = limit - pos;
+        if count <= 0 {
+            return None;
+        }
+    }
+    Some((off, pos, count as usize))
+}
+
+/// Zero everything in `dst` outside `off..off + count`.
+#[inline]
+fn zero_outside(dst: &mut [u8], off: usize, count: usize) {
+    dst[..off].iter_mut().for_each(|d| *d = 0);
+    dst[off + count..].iter_mut().for_each(|d| *d = 0);
+}
+
+/// Shared buffer access for both 16-bit mask flavours.
+struct MaskBuf<'a, const STEP: usize, const OFFSET: usize, MF: MaskFunctionU16> {
+    rbuf: &'a RowAccessor,
+    mask_function: MF,
+}
+
+impl<const STEP: usize, const OFFSET: usize, MF: MaskFunctionU16> MaskBuf<'_, STEP, OFFSET, MF> {
+    /// Mask value at `(x, y)`, no bounds checking.
+    #[inline]
+    fn value(&self, x: i32, y: i32) -> u16 {
+        let row = self.rbuf.row_slice(y as u32);
+        let mut comps = [0u16; STEP];
+        let base = x as usize * STEP * 2;
+        for (i, c) in comps.iter_mut().enumerate() {
+            *c = u16::from_ne_bytes([row[base + i * 2], row[base + i * 2 + 1]]);
+        }
+        self.mask_function.calculate(&comps[OFFSET..])
+    }
+}
+
+// ============================================================================
+// AlphaMaskU16 — clipped 16-bit alpha mask
+// ============================================================================
+
+/// 16-bit alpha mask with bounds-checked access to a rendering buffer.
+///
+/// `STEP` is the number of `u16` components per pixel, `OFFSET` is the
+/// component index of the mask value within each pixel.
+///
+/// 16-bit counterpart of `AlphaMaskU8` (C++ `alpha_mask_u8<Step, Offset, MaskF>`).
+pub struct AlphaMaskU16<'a, const STEP: usize, const OFFSET: usize, MF: MaskFunctionU16> {
+    buf: MaskBuf<'a, STEP, OFFSET, MF>,
+}
+
+impl<'a, const STEP: usize, const OFFSET: usize, MF: MaskFunctionU16>
+    AlphaMaskU16<'a, STEP, OFFSET, MF>
+{
+    pub fn new(rbuf: &'a RowAccessor, mask_function: MF) -> Self {
+        Self {
+            buf: MaskBuf {
+                rbuf,
+                mask_function,
+            },
+        }
+    }
+
+    pub fn mask_function(&self) -> &MF {
+        &self.buf.mask_function
+    }
+
+    fn inbox(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.buf.rbuf.width() as i32 && y < self.buf.rbuf.height() as i32
+    }
+
+    /// Full-precision mask value at `(x, y)`; 0 outside the buffer.
+    pub fn pixel_u16(&self, x: i32, y: i32) -> u16 {
+        if self.inbox(x, y) {
+            self.buf.value(x, y)
+        } else {
+            0
+        }
+    }
+}
+
+impl<const STEP: usize, const OFFSET: usize, MF: MaskFunctionU16> AlphaMask
+    for AlphaMaskU16<'_, STEP, OFFSET, MF>
+{
+    fn pixel(&self, x: i32, y: i32) -> u8 {
+        to_cover(self.pixel_u16(x, y))
+    }
+
+    fn combine_pixel(&self, x: i32, y: i32, val: u8) -> u8 {
+        if self.inbox(x, y) {
+            combine(val, self.buf.value(x, y))
+        } else {
+            0
+        }
+    }
+
+    fn fill_hspan(&self, x: i32, y: i32, dst: &mut [u8]) {
+        let clipped = if y < 0 || y >= self.buf.rbuf.height() as i32 {
+            None
+        } else {
+            clip_span(x, dst.len(), self.buf.rbuf.width() as i32)
+        };
+        let Some((off, x, count)) = clipped else {
+            dst.iter_mut().for_each(|d| *d = 0);
+            return;
+        };
+        zero_outside(dst, off, count);
+        for (i, d) in dst[off..off + count].iter_mut().enumerate() {
+            *d = to_cover(self.buf.value(x + i as i32, y));
+        }
+    }
+
+    fn combine_hspan(&self, x: i32, y: i32, dst: &mut [u8]) {
+        let clipped = if y < 0 || y >= self.buf.rbuf.height() as i32 {
+            None
+        } else {
+            clip_span(x, dst.len(), self.buf.rbuf.width() as i32)
+        };
+        let Some((off, x, count)) = clipped else {
+            dst.iter_mut().for_each(|d| *d = 0);
+            return;
+        };
+        zero_outside(dst, off, count);
+        for (i, d) in dst[off..off + count].iter_mut().enumerate() {
+            *d = combine(*d, self.buf.value(x + i as i32, y));
+        }
+    }
+
+    fn fill_vspan(&self, x: i32, y: i32, dst: &mut [u8]) {
+        let clipped = if x < 0 || x >= self.buf.rbuf.width() as i32 {
+            None
+        } else {
+            clip_span(y, dst.len(), self.buf.rbuf.height() as i32)
+        };
+        let Some((off, y, count)) = clipped else {
+            dst.iter_mut().for_each(|d| *d = 0);
+            return;
+        };
+        zero_outside(dst, off, count);
+        for (i, d) in dst[off..off + count].iter_mut().enumerate() {
+            *d = to_cover(self.buf.value(x, y + i as i32));
+        }
+    }
+
+    fn combine_vspan(&self, x: i32, y: i32, dst: &mut [u8]) {
+        let clipped = if x < 0 || x >= self.buf.rbuf.width() as i32 {
+            None
+        } else {
+            clip_span(y, dst.len(), self.buf.rbuf.height() as i32)
+        };
+        let Some((off, y, count)) = clipped else {
+            dst.iter_mut().for_each(|d| *d = 0);
+            return;
+        };
+        zero_outside(dst, off, count);
+        for (i, d) in dst[off..off + count].iter_mut().enumerate() {
+            *d = combine(*d, self.buf.value(x, y + i as i32));
+        }
+    }
+}
+
+// ============================================================================
+// AmaskNoClipU16 — unchecked 16-bit alpha mask
+// ============================================================================
+
+/// 16-bit alpha mask without bounds checking — faster but caller must
+/// ensure in-range.
+///
+/// 16-bit counterpart of `AmaskNoClipU8` (C++ `amask_no_clip_u8<Step, Offset, MaskF>`).
+pub struct AmaskNoClipU16<'a, const STEP: usize, const OFFSET: usize, MF: MaskFunctionU16> {
+    buf: MaskBuf<'a, STEP, OFFSET, MF>,
+}
+
+impl<'a, const STEP: usize, const OFFSET: usize, MF: MaskFunctionU16>
+    AmaskNoClipU16<'a, STEP, OFFSET, MF>
+{
+    pub fn new(rbuf: &'a RowAccessor, mask_function: MF) -> Self {
+        Self {
+            buf: MaskBuf {
+                rbuf,
+                mask_function,
+            },
+        }
+    }
+
+    pub fn mask_function(&self) -> &MF {
+        &self.buf.mask_function
+    }
+
+    /// Full-precision mask value at `(x, y)`.
+    pub fn pixel_u16(&self, x: i32, y: i32) -> u16 {
+        self.buf.value(x, y)
+    }
+}
+
+impl<const STEP: usize, const OFFSET: usize, MF: MaskFunctionU16> AlphaMask
+    for AmaskNoClipU16<'_, STEP, OFFSET, MF>
+{
+    fn pixel(&self, x: i32, y: i32) -> u8 {
+        to_cover(self.buf.value(x, y))
+    }
+
+    fn combine_pixel(&self, x: i32, y: i32, val: u8) -> u8 {
+        combine(val, self.buf.value(x, y))
+    }
+
+    fn fill_hspan(&self, x: i32, y: i32, dst: &mut [u8]) {
+        for (i, d) in dst.iter_mut().enumerate() {
+            *d = to_cover(self.buf.value(x + i as i32, y));
+        }
+    }
+
+    fn combine_hspan(&self, x: i32, y: i32, dst: &mut [u8]) {
+        for (i, d) in dst.iter_mut().enumerate() {
+            *d = combine(*d, self.buf.value(x + i as i32, y));
+        }
+    }
+
+    fn fill_vspan(&self, x: i32, y: i32, dst: &mut [u8]) {
+        for (i, d) in dst.iter_mut().enumerate() {
+            *d = to_cover(self.buf.value(x, y + i as i32));
+        }
+    }
+
+    fn combine_vspan(&self, x: i32, y: i32, dst: &mut [u8]) {
+        for (i, d) in dst.iter_mut().enumerate() {
+            *d = combine(*d, self.buf.value(x, y + i as i32));
+        }
+    }
+}
+
+// ============================================================================
+// Type aliases for common configurations
+// ============================================================================
+
+/// Gray16 alpha mask (1 component per pixel, offset 0).
+pub type AlphaMaskGray16<'a> = AlphaMaskU16<'a, 1, 0, OneComponentMaskU16>;
+
+/// RGBA64 red channel mask.
+pub type AlphaMaskRgba64r<'a> = AlphaMaskU16<'a, 4, 0, OneComponentMaskU16>;
+/// RGBA64 green channel mask.
+pub type AlphaMaskRgba64g<'a> = AlphaMaskU16<'a, 4, 1, OneComponentMaskU16>;
+/// RGBA64 blue channel mask.
+pub type AlphaMaskRgba64b<'a> = AlphaMaskU16<'a, 4, 2, OneComponentMaskU16>;
+/// RGBA64 alpha channel mask.
+pub type AlphaMaskRgba64a<'a> = AlphaMaskU16<'a, 4, 3, OneComponentMaskU16>;
+
+// No-clip variants
+/// Gray16 alpha mask, no clipping.
+pub type AmaskNoClipGray16<'a> = AmaskNoClipU16<'a, 1, 0, OneComponentMaskU16>;
+/// RGBA64 alpha channel mask, no clipping.
+pub type AmaskNoClipRgba64a<'a> = AmaskNoClipU16<'a, 4, 3, OneComponentMaskU16>;
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gray16 buffer holding `values` row-major.
+    fn make_gray16_buffer(
+        width: u32,
+        height: u32,
+        data: &mut Vec<u8>,
+        values: &[u16],
+    ) -> RowAccessor {
+        data.resize((width * height * 2) as usize, 0);
+        for (i, v) in values.iter().enumerate() {
+            data[i * 2..i * 2 + 2].copy_from_slice(&v.to_ne_bytes());
+        }
+        unsafe { RowAccessor::new_with_buf(data.as_mut_ptr(), width, height, (width * 2) as i32) }
+    }
+
+    #[test]
+    fn test_pixel_u16_and_cover() {
+        let mut data = Vec::new();
+        let rbuf = make_gray16_buffer(4, 4, &mut data, &[0, 0x1234, 0xFFFF, 0x80FF]);
+        let mask = AlphaMaskGray16::new(&rbuf, OneComponentMaskU16);
+        assert_eq!(mask.pixel_u16(1, 0), 0x1234);
+        assert_eq!(mask.pixel(1, 0), 0x12);
+        assert_eq!(mask.pixel(2, 0), 255);
+        assert_eq!(mask.pixel_u16(-1, 0), 0);
+        assert_eq!(mask.pixel(0, 4), 0);
+    }
+
+    #[test]
+    fn test_combine_pixel_uses_full_precision() {
+        let mut data = Vec::new();
+        // 0x80FF truncates to 0x80 in an 8-bit mask, which would combine a
+        // full cover down to 128; the 16-bit value rounds to 129.
+        let rbuf = make_gray16_buffer(4, 4, &mut data, &[0xFFFF, 0x80FF]);
+        let mask = AlphaMaskGray16::new(&rbuf, OneComponentMaskU16);
+        assert_eq!(mask.combine_pixel(0, 0, 200), 200);
+        assert_eq!(mask.combine_pixel(1, 0, 255), 129);
+        assert_eq!(mask.combine_pixel(5, 0, 255), 0);
+    }
+
+    #[test]
+    fn test_fill_hspan_clipping() {
+        let mut data = Vec::new();
+        let rbuf = make_gray16_buffer(4, 2, &mut data, &[0x1000, 0x2000, 0x3000, 0x4000]);
+        let mask = AlphaMaskGray16::new(&rbuf, OneComponentMaskU16);
+
+        let mut dst = vec![99u8; 6];
+        mask.fill_hspan(-1, 0, &mut dst);
+        assert_eq!(dst, vec![0, 0x10, 0x20, 0x30, 0x40, 0]);
+
+        let mut dst = vec![99u8; 3];
+        mask.fill_hspan(0, 2, &mut dst);
+        assert_eq!(dst, vec![0, 0, 0]);
+
+        let mut dst = vec![99u8; 2];
+        mask.fill_hspan(-5, 0, &mut dst);
+        assert_eq!(dst, vec![0, 0]);
+    }
+
+    #[test]
+    fn test_combine_hspan_and_vspan() {
+        let mut data = Vec::new();
+        let rbuf = make_gray16_buffer(2, 2, &mut data, &[0xFFFF, 0x0000, 0x8000, 0xFFFF]);
+        let mask = AlphaMaskGray16::new(&rbuf, OneComponentMaskU16);
+
+        let mut dst = vec![200u8, 200, 200];
+        mask.combine_hspan(0, 0, &mut dst);
+        assert_eq!(dst, vec![200, 0, 0]);
+
+        let mut dst = vec![255u8, 255, 255];
+        mask.combine_vspan(0, -1, &mut dst);
+        assert_eq!(dst, vec![0, 255, combine(255, 0x8000)]);
+
+        let mut dst = vec![9u8; 2];
+        mask.fill_vspan(1, 0, &mut dst);
+        assert_eq!(dst, vec![0, 255]);
+    }
+
+    #[test]
+    fn test_rgba64_alpha_mask() {
+        let mut data = Vec::new();
+        // 2 RGBA64 pixels = 8 components in one row
+        make_gray16_buffer(8, 1, &mut data, &[1, 2, 3, 0xABCD, 5, 6, 7, 0x1111]);
+        let rbuf = unsafe { RowAccessor::new_with_buf(data.as_mut_ptr(), 2, 1, 16) };
+        let mask = AlphaMaskRgba64a::new(&rbuf, OneComponentMaskU16);
+        assert_eq!(mask.pixel_u16(0, 0), 0xABCD);
+        assert_eq!(mask.pixel_u16(1, 0), 0x1111);
+        let red = AlphaMaskRgba64r::new(&rbuf, OneComponentMaskU16);
+        assert_eq!(red.pixel_u16(1, 0), 5);
+    }
+
+    #[test]
+    fn test_rgb_to_gray_mask_u16() {
+        let m = RgbToGrayMaskU16::new(0, 1, 2);
+        assert_eq!(m.calculate(&[0xFFFF, 0xFFFF, 0xFFFF]), 0xFFFF);
+        assert_eq!(m.calculate(&[0, 0, 0]), 0);
+    }
+
+    #[test]
+    fn test_no_clip() {
+        let mut data = Vec::new();
+        let rbuf = make_gray16_buffer(3, 1, &mut data, &[0x0100, 0x8000, 0xFFFF]);
+        let mask = AmaskNoClipGray16::new(&rbuf, OneComponentMaskU16);
+        assert_eq!(mask.pixel_u16(1, 0), 0x8000);
+        let mut dst = vec![0u8; 3];
+        mask.fill_hspan(0, 0, &mut dst);
+        assert_eq!(dst, vec![1, 128, 255]);
+        let mut dst = vec![255u8; 3];
+        mask.combine_hspan(0, 0, &mut dst);
+        assert_eq!(dst, vec![1, 128, 255]);
+    }
+}
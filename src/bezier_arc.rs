@@ -3,6 +3,9 @@
 //! Port of `agg_bezier_arc.h` / `agg_bezier_arc.cpp` — converts elliptical
 //! arcs into sequences of cubic Bezier curves. Produces at most 4 consecutive
 //! cubic Bezier curves (4, 7, 10, or 13 vertices).
+//!
+//! Unlike C++ AGG, non-finite parameters produce an empty arc, and
+//! `BezierArcSvg` also reports them through `radii_ok()`.
 
 use crate::basics::{
     VertexSource, PATH_CMD_CURVE4, PATH_CMD_LINE_TO, PATH_CMD_MOVE_TO, PATH_CMD_STOP, PI,
@@ -79,7 +82,15 @@ impl BezierArc {
     }
 
     /// Initialize the arc with center, radii, and angle parameters.
+    ///
+    /// The arc is empty if any parameter is non-finite; an infinite sweep
+    /// is clamped to a full turn like any other.
     pub fn init(&mut self, x: f64, y: f64, rx: f64, ry: f64, start_angle: f64, sweep_angle: f64) {
+        if ![x, y, rx, ry, start_angle].iter().all(|v| v.is_finite()) || sweep_angle.is_nan() {
+            self.num_vertices = 0;
+            self.cmd = PATH_CMD_LINE_TO;
+            return;
+        }
         let mut start_angle = start_angle % (2.0 * PI);
         let mut sweep_angle = sweep_angle;
 
@@ -314,6 +325,15 @@ impl BezierArcSvg {
             sweep_angle += PI * 2.0;
         }
 
+        // Degenerate radii or non-finite input leave nothing to draw
+        let params = [x0, y0, x2, y2, rx, ry, angle];
+        let arc = [cx, cy, start_angle, sweep_angle];
+        if !params.iter().chain(&arc).all(|v| v.is_finite()) {
+            self.radii_ok = false;
+            self.arc.num_vertices = 0;
+            return;
+        }
+
         // Build and transform the arc
         self.arc.init(0.0, 0.0, rx, ry, start_angle, sweep_angle);
 
@@ -342,7 +362,8 @@ impl BezierArcSvg {
         }
     }
 
-    /// Whether the radii were sufficient (not enlarged).
+    /// Whether the radii were sufficient (not enlarged) and the arc could
+    /// be computed at all.
     pub fn radii_ok(&self) -> bool {
         self.radii_ok
     }
@@ -507,4 +528,28 @@ mod tests {
         // Radii were enlarged significantly
         assert!(!svg.radii_ok());
     }
+
+    #[test]
+    fn test_bezier_arc_non_finite_is_empty() {
+        let arc = BezierArc::new_with_params(0.0, f64::NAN, 10.0, 10.0, 0.0, PI);
+        assert_eq!(arc.num_vertices(), 0);
+        let arc = BezierArc::new_with_params(0.0, 0.0, 10.0, 10.0, 0.0, f64::INFINITY);
+        assert_eq!(arc.num_vertices(), 26);
+
+        let mut svg = BezierArcSvg::new_with_params(
+            0.0,
+            0.0,
+            f64::INFINITY,
+            10.0,
+            0.0,
+            false,
+            true,
+            10.0,
+            0.0,
+        );
+        assert!(!svg.radii_ok());
+        svg.rewind(0);
+        let (mut x, mut y) = (0.0, 0.0);
+        assert!(is_stop(svg.vertex(&mut x, &mut y)));
+    }
 }
@@ -6,6 +6,7 @@
 //! - `Rgba` — f64 components (linear working space)
 //! - `Rgba8` — u8 components (8-bit per channel)
 //! - `Rgba16` — u16 components (16-bit per channel)
+//! - `Rgba32` — f32 components (linear light, unclamped)
 //! - `Gray8` — u8 grayscale + alpha
 //! - `Gray16` — u16 grayscale + alpha
 //!
@@ -77,6 +78,53 @@ impl OrderBgra {
     pub const N: usize = 4;
 }
 
+/// Channel positions of a four-component pixel, usable as a type parameter.
+///
+/// The order structs above expose the same values as inherent constants;
+/// this trait lets pixel formats and image spans be generic over them, like
+/// the C++ `Order` template parameter.
+pub trait RgbaOrder {
+    const R: usize;
+    const G: usize;
+    const B: usize;
+    const A: usize;
+}
+
+macro_rules! impl_rgba_order {
+    ($($order:ident),*) => {
+        $(
+            impl RgbaOrder for $order {
+                const R: usize = $order::R;
+                const G: usize = $order::G;
+                const B: usize = $order::B;
+                const A: usize = $order::A;
+            }
+        )*
+    };
+}
+
+impl_rgba_order!(OrderRgba, OrderArgb, OrderAbgr, OrderBgra);
+
+/// Channel positions of a three-component pixel; the [`RgbaOrder`]
+/// counterpart for the RGB24 and RGB48 pixel formats.
+pub trait RgbOrder {
+    const R: usize;
+    const G: usize;
+    const B: usize;
+}
+
+impl RgbOrder for OrderRgb {
+    const R: usize = OrderRgb::R;
+    const G: usize = OrderRgb::G;
+    const B: usize = OrderRgb::B;
+}
+
+impl RgbOrder for OrderBgr {
+    const R: usize = OrderBgr::R;
+    const G: usize = OrderBgr::G;
+    const B: usize = OrderBgr::B;
+}
+
 // ============================================================================
 // Rgba (f64 precision color)
 // ============================================================================
@@ -683,10 +731,19 @@ impl Rgba16 {
     }
 
     /// Interpolate p to q by a.
+    ///
+    /// The product `(q - p) * a` needs 33 bits, so unlike C++ (which
+    /// overflows `int` here) the intermediate is computed in `i64`.
     #[inline]
     pub fn lerp(p: u16, q: u16, a: u16) -> u16 {
-        let t = (q as i32 - p as i32) * a as i32 + Self::BASE_MSB as i32 - (p > q) as i32;
-        (p as i32 + (((t >> Self::BASE_SHIFT) + t) >> Self::BASE_SHIFT)) as u16
+        let t = (q as i64 - p as i64) * a as i64 + Self::BASE_MSB as i64 - (p > q) as i64;
+        (p as i64 + (((t >> Self::BASE_SHIFT) + t) >> Self::BASE_SHIFT)) as u16
+    }
+
+    /// Interpolate p to q by a, assuming q is premultiplied by a.
+    #[inline]
+    pub fn prelerp(p: u16, q: u16, a: u16) -> u16 {
+        p.wrapping_add(q).wrapping_sub(Self::multiply(p, a))
     }
 
     /// Multiply a color component by a cover (8-bit).
@@ -766,6 +823,278 @@ impl Default for Rgba16 {
     }
 }
 
+// ============================================================================
+// Rgba32 (32-bit float per channel)
+// ============================================================================
+
+/// RGBA color with f32 components in linear light.
+///
+/// Channels are nominally in `[0, 1]` but are never clamped by the blending
+/// arithmetic, so values above 1.0 survive compositing (HDR). Convert with
+/// [`Rgba32::to_rgba8`] or [`Rgba32::to_rgba16`] to tone-map at the end.
+///
+/// Port of C++ `rgba32`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgba32 {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Rgba32 {
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub fn new_opaque(r: f32, g: f32, b: f32) -> Self {
+        Self::new(r, g, b, 1.0)
+    }
+
+    /// Convert from Rgba (f64).
+    pub fn from_rgba(c: &Rgba) -> Self {
+        Self {
+            r: c.r as f32,
+            g: c.g as f32,
+            b: c.b as f32,
+            a: c.a as f32,
+        }
+    }
+
+    /// Convert from Rgba8 by scaling each channel to `[0, 1]`.
+    pub fn from_rgba8(c: &Rgba8) -> Self {
+        Self {
+            r: c.r as f32 / 255.0,
+            g: c.g as f32 / 255.0,
+            b: c.b as f32 / 255.0,
+            a: c.a as f32 / 255.0,
+        }
+    }
+
+    /// Convert from Rgba16 by scaling each channel to `[0, 1]`.
+    pub fn from_rgba16(c: &Rgba16) -> Self {
+        Self {
+            r: c.r as f32 / 65535.0,
+            g: c.g as f32 / 65535.0,
+            b: c.b as f32 / 65535.0,
+            a: c.a as f32 / 65535.0,
+        }
+    }
+
+    pub fn to_rgba(&self) -> Rgba {
+        Rgba {
+            r: self.r as f64,
+            g: self.g as f64,
+            b: self.b as f64,
+            a: self.a as f64,
+        }
+    }
+
+    /// Convert to Rgba8, saturating channels outside `[0, 1]`.
+    ///
+    /// C++ wraps out-of-range values here; saturating is what a final
+    /// tone-mapping step wants.
+    pub fn to_rgba8(&self) -> Rgba8 {
+        let cvt = |v: f32| uround(v.clamp(0.0, 1.0) as f64 * 255.0);
+        Rgba8::new(cvt(self.r), cvt(self.g), cvt(self.b), cvt(self.a))
+    }
+
+    /// Convert to Rgba16, saturating channels outside `[0, 1]`.
+    pub fn to_rgba16(&self) -> Rgba16 {
+        let cvt = |v: f32| uround(v.clamp(0.0, 1.0) as f64 * 65535.0);
+        Rgba16::new(cvt(self.r), cvt(self.g), cvt(self.b), cvt(self.a))
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        self.a <= 0.0
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.a >= 1.0
+    }
+
+    pub fn invert(x: f32) -> f32 {
+        1.0 - x
+    }
+
+    #[inline]
+    pub fn multiply(a: f32, b: f32) -> f32 {
+        a * b
+    }
+
+    /// Multiply a color component by a cover (8-bit).
+    #[inline]
+    pub fn mult_cover(a: f32, b: CoverType) -> f32 {
+        a * b as f32 / COVER_MASK as f32
+    }
+
+    /// Interpolate p to q by a.
+    #[inline]
+    pub fn lerp(p: f32, q: f32, a: f32) -> f32 {
+        (1.0 - a) * p + a * q
+    }
+
+    /// Interpolate p to q by a, assuming q is premultiplied by a.
+    #[inline]
+    pub fn prelerp(p: f32, q: f32, a: f32) -> f32 {
+        (1.0 - a) * p + q
+    }
+
+    pub fn clear(&mut self) -> &mut Self {
+        self.r = 0.0;
+        self.g = 0.0;
+        self.b = 0.0;
+        self.a = 0.0;
+        self
+    }
+
+    pub fn premultiply(&mut self) -> &mut Self {
+        if self.a < 1.0 {
+            if self.a <= 0.0 {
+                self.r = 0.0;
+                self.g = 0.0;
+                self.b = 0.0;
+            } else {
+                self.r *= self.a;
+                self.g *= self.a;
+                self.b *= self.a;
+            }
+        }
+        self
+    }
+
+    pub fn demultiply(&mut self) -> &mut Self {
+        if self.a < 1.0 {
+            if self.a <= 0.0 {
+                self.r = 0.0;
+                self.g = 0.0;
+                self.b = 0.0;
+            } else {
+                self.r /= self.a;
+                self.g /= self.a;
+                self.b /= self.a;
+            }
+        }
+        self
+    }
+
+    /// Interpolate between `self` and `c` by parameter `k` (0.0 to 1.0).
+    pub fn gradient(&self, c: &Rgba32, k: f64) -> Rgba32 {
+        let k = k as f32;
+        Rgba32 {
+            r: self.r + (c.r - self.r) * k,
+            g: self.g + (c.g - self.g) * k,
+            b: self.b + (c.b - self.b) * k,
+            a: self.a + (c.a - self.a) * k,
+        }
+    }
+
+    /// Add color `c` with coverage `cover`.
+    ///
+    /// Like C++, alpha is capped at 1.0 and the color channels at alpha.
+    pub fn add(&mut self, c: &Rgba32, cover: u32) {
+        if cover == COVER_MASK {
+            if c.is_opaque() {
+                *self = *c;
+                return;
+            }
+            self.r += c.r;
+            self.g += c.g;
+            self.b += c.b;
+            self.a += c.a;
+        } else {
+            self.r += Self::mult_cover(c.r, cover as CoverType);
+            self.g += Self::mult_cover(c.g, cover as CoverType);
+            self.b += Self::mult_cover(c.b, cover as CoverType);
+            self.a += Self::mult_cover(c.a, cover as CoverType);
+        }
+        self.a = self.a.min(1.0);
+        self.r = self.r.min(self.a);
+        self.g = self.g.min(self.a);
+        self.b = self.b.min(self.a);
+    }
+
+    pub fn no_color() -> Self {
+        Self {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+            a: 0.0,
+        }
+    }
+
+    pub fn from_wavelength(wl: f64, gamma: f64) -> Self {
+        Self::from_rgba(&Rgba::from_wavelength(wl, gamma))
+    }
+}
+
+impl Default for Rgba32 {
+    fn default() -> Self {
+        Self::no_color()
+    }
+}
+
+// ============================================================================
+// RgbaIntColor — integer RGBA colors of any channel depth
+// ============================================================================
+
+/// Integer RGBA color with `BASE_MASK`-ranged channels.
+///
+/// Abstracts over [`Rgba8`] and [`Rgba16`] for code that C++ templates on
+/// `ColorT` and that only needs the raw channel values: the gradient LUT and
+/// the RGBA Gouraud span generator.
+pub trait RgbaIntColor: Copy + Default {
+    /// Largest channel value (`255` or `65535`).
+    const BASE_MASK: u32;
+
+    /// Build a color from raw channel values (each in `0..=BASE_MASK`).
+    fn from_channels(r: u32, g: u32, b: u32, a: u32) -> Self;
+
+    /// Raw channel values as `[r, g, b, a]`.
+    fn channels(&self) -> [u32; 4];
+
+    /// Interpolate between `self` and `c` by parameter `k` (0.0 to 1.0).
+    fn gradient(&self, c: &Self, k: f64) -> Self;
+}
+
+impl RgbaIntColor for Rgba8 {
+    const BASE_MASK: u32 = Rgba8::BASE_MASK;
+
+    #[inline]
+    fn from_channels(r: u32, g: u32, b: u32, a: u32) -> Self {
+        Rgba8::new(r, g, b, a)
+    }
+
+    #[inline]
+    fn channels(&self) -> [u32; 4] {
+        [self.r as u32, self.g as u32, self.b as u32, self.a as u32]
+    }
+
+    #[inline]
+    fn gradient(&self, c: &Self, k: f64) -> Self {
+        Rgba8::gradient(self, c, k)
+    }
+}
+
+impl RgbaIntColor for Rgba16 {
+    const BASE_MASK: u32 = Rgba16::BASE_MASK;
+
+    #[inline]
+    fn from_channels(r: u32, g: u32, b: u32, a: u32) -> Self {
+        Rgba16::new(r, g, b, a)
+    }
+
+    #[inline]
+    fn channels(&self) -> [u32; 4] {
+        [self.r as u32, self.g as u32, self.b as u32, self.a as u32]
+    }
+
+    #[inline]
+    fn gradient(&self, c: &Self, k: f64) -> Self {
+        Rgba16::gradient(self, c, k)
+    }
+}
+
 // ============================================================================
 // Gray8 (8-bit grayscale)
 // ============================================================================
@@ -970,10 +1299,17 @@ impl Gray16 {
         (((t >> Self::BASE_SHIFT) + t) >> Self::BASE_SHIFT) as u16
     }
 
+    /// Interpolate p to q by a (computed in `i64`, see [`Rgba16::lerp`]).
     #[inline]
     pub fn lerp(p: u16, q: u16, a: u16) -> u16 {
-        let t = (q as i32 - p as i32) * a as i32 + Self::BASE_MSB as i32 - (p > q) as i32;
-        (p as i32 + (((t >> Self::BASE_SHIFT) + t) >> Self::BASE_SHIFT)) as u16
+        let t = (q as i64 - p as i64) * a as i64 + Self::BASE_MSB as i64 - (p > q) as i64;
+        (p as i64 + (((t >> Self::BASE_SHIFT) + t) >> Self::BASE_SHIFT)) as u16
+    }
+
+    /// Multiply a value by a cover (8-bit).
+    #[inline]
+    pub fn mult_cover(a: u16, b: CoverType) -> u16 {
+        Self::multiply(a, (b as u16) << 8 | b as u16)
     }
 
     pub fn clear(&mut self) -> &mut Self {
@@ -1185,6 +1521,40 @@ mod tests {
         assert_eq!(Rgba16::multiply(65535, 0), 0);
     }
 
+    #[test]
+    fn test_rgba16_lerp_full_range() {
+        // (q - p) * a exceeds i32 here; must not overflow.
+        assert_eq!(Rgba16::lerp(0, 65535, 65535), 65535);
+        assert_eq!(Rgba16::lerp(65535, 0, 65535), 0);
+        assert_eq!(Rgba16::lerp(0, 65535, 32768), 32768);
+        assert_eq!(Gray16::lerp(0, 65535, 65535), 65535);
+        assert_eq!(Gray16::lerp(65535, 0, 0), 65535);
+    }
+
+    #[test]
+    fn test_rgba32_conversions() {
+        let c = Rgba32::from_rgba8(&Rgba8::new(255, 0, 51, 255));
+        assert_eq!(c, Rgba32::new(1.0, 0.0, 0.2, 1.0));
+        assert_eq!(Rgba32::from_rgba16(&Rgba16::new(65535, 0, 0, 65535)).r, 1.0);
+        // Out-of-range HDR values saturate when converting back
+        let hdr = Rgba32::new(3.0, -0.5, 0.5, 1.0);
+        assert_eq!(hdr.to_rgba8(), Rgba8::new(255, 0, 128, 255));
+        assert_eq!(hdr.to_rgba16(), Rgba16::new(65535, 0, 32768, 65535));
+    }
+
+    #[test]
+    fn test_rgba32_premultiply_and_gradient() {
+        let mut c = Rgba32::new(2.0, 1.0, 0.5, 0.5);
+        c.premultiply();
+        assert_eq!(c, Rgba32::new(1.0, 0.5, 0.25, 0.5));
+        c.demultiply();
+        assert_eq!(c, Rgba32::new(2.0, 1.0, 0.5, 0.5));
+        let g = Rgba32::new(0.0, 0.0, 0.0, 1.0).gradient(&Rgba32::new(4.0, 2.0, 1.0, 1.0), 0.25);
+        assert_eq!(g, Rgba32::new(1.0, 0.5, 0.25, 1.0));
+        assert_eq!(Rgba32::prelerp(0.5, 2.0, 0.5), 2.25);
+        assert_eq!(Rgba32::mult_cover(2.0, 255), 2.0);
+    }
+
     #[test]
     fn test_gray8_luminance() {
         let white = Rgba8::new(255, 255, 255, 255);
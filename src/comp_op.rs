@@ -1,14 +1,22 @@
 //! SVG compositing operations and compositing pixel format.
 //!
 //! Port of the compositing portion of `agg_pixfmt_rgba.h`.
-//! Provides 25 SVG compositing modes (the standard 24 plus `minus`)
-//! and `PixfmtRgba32CompOp`, a pixel format that dispatches blending
-//! through a runtime-selectable compositing operation.
-
-use crate::basics::CoverType;
-use crate::color::Rgba8;
-use crate::pixfmt_rgba::PixelFormat;
-use crate::rendering_buffer::RowAccessor;
+//! Provides 25 SVG compositing modes (the standard 24 plus `minus`), the
+//! four non-separable modes of the W3C Compositing spec, and pixel formats
+//! that dispatch blending through a runtime-selectable compositing
+//! operation: `PixfmtCustomBlendRgba` (`PixfmtRgba32CompOp`,
+//! `PixfmtBgra32CompOp`, ...) and the generic `PixfmtCompOp`
+//! (`PixfmtRgb24CompOp`, `PixfmtGray8CompOp`, `PixfmtRgba64CompOp`, ...).
+
+use crate::basics::{uround, CoverType};
+use crate::color::{
+    Gray16, Gray8, OrderAbgr, OrderArgb, OrderBgr, OrderBgra, OrderRgb, OrderRgba, RgbOrder,
+    Rgba16, Rgba8, RgbaOrder,
+};
+use crate::pixfmt_rgba::{pixel_bytes, PixelFormat};
+use crate::pixfmt_rgba_simd::{blend_colors, blend_span, BlendOp, SpanCover, SpanSrc};
+use crate::rendering_buffer::{assert_row_len, RenderingBufferMut, RowAccessor};
+use std::marker::PhantomData;
 
 // ============================================================================
 // CompOp enum — 25 SVG/AGG compositing modes
@@ -18,6 +26,9 @@ use crate::rendering_buffer::RowAccessor;
 ///
 /// Port of C++ `comp_op_e`. Each variant corresponds to a specific
 /// alpha-compositing formula from the SVG Compositing specification.
+/// `Hue` through `Luminosity` are the non-separable blend modes of the W3C
+/// Compositing and Blending spec (as used by PDF and CSS `mix-blend-mode`),
+/// which AGG does not provide.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum CompOp {
@@ -46,6 +57,10 @@ pub enum CompOp {
     SoftLight = 22,
     Difference = 23,
     Exclusion = 24,
+    Hue = 25,
+    Saturation = 26,
+    Color = 27,
+    Luminosity = 28,
 }
 
 impl Default for CompOp {
@@ -186,6 +201,10 @@ fn comp_op_blend(op: CompOp, p: &mut [u8; 4], sr: u8, sg: u8, sb: u8, sa: u8, co
         CompOp::SoftLight => blend_soft_light(p, r, g, b, a, cover),
         CompOp::Difference => blend_difference(p, r, g, b, a, cover),
         CompOp::Exclusion => blend_exclusion(p, r, g, b, a, cover),
+        CompOp::Hue => blend_non_separable(p, r, g, b, a, cover, hue),
+        CompOp::Saturation => blend_non_separable(p, r, g, b, a, cover, saturation),
+        CompOp::Color => blend_non_separable(p, r, g, b, a, cover, color),
+        CompOp::Luminosity => blend_non_separable(p, r, g, b, a, cover, luminosity),
     }
 }
 
@@ -653,6 +672,254 @@ fn blend_exclusion(p: &mut [u8; 4], r: u8, g: u8, b: u8, a: u8, cover: u8) {
     }
 }
 
+// ---- Non-separable modes (W3C Compositing and Blending Level 1, 5.8)
+//
+// B(Cb, Cs) works on non-premultiplied colors, so the premultiplied inputs
+// are divided out first:
+//   Dca' = Sca.(1 - Da) + Dca.(1 - Sa) + Sa.Da.B(Dca/Da, Sca/Sa)
+//   Da'  = Sa + Da - Sa.Da
+
+#[inline]
+fn lum(c: [f64; 3]) -> f64 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+#[inline]
+fn clip_color(mut c: [f64; 3]) -> [f64; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    if n < 0.0 {
+        c.iter_mut().for_each(|v| *v = l + (*v - l) * l / (l - n));
+    }
+    if x > 1.0 {
+        c.iter_mut()
+            .for_each(|v| *v = l + (*v - l) * (1.0 - l) / (x - l));
+    }
+    c
+}
+
+#[inline]
+fn set_lum(c: [f64; 3], l: f64) -> [f64; 3] {
+    let d = l - lum(c);
+    clip_color([c[0] + d, c[1] + d, c[2] + d])
+}
+
+#[inline]
+fn sat(c: [f64; 3]) -> f64 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+#[inline]
+fn set_sat(c: [f64; 3], s: f64) -> [f64; 3] {
+    let mut idx = [0, 1, 2];
+    idx.sort_by(|&i, &j| c[i].total_cmp(&c[j]));
+    let [min, mid, max] = idx;
+    let mut out = [0.0; 3];
+    if c[max] > c[min] {
+        out[mid] = (c[mid] - c[min]) * s / (c[max] - c[min]);
+        out[max] = s;
+    }
+    out
+}
+
+fn hue(cb: [f64; 3], cs: [f64; 3]) -> [f64; 3] {
+    set_lum(set_sat(cs, sat(cb)), lum(cb))
+}
+
+fn saturation(cb: [f64; 3], cs: [f64; 3]) -> [f64; 3] {
+    set_lum(set_sat(cb, sat(cs)), lum(cb))
+}
+
+fn color(cb: [f64; 3], cs: [f64; 3]) -> [f64; 3] {
+    set_lum(cs, lum(cb))
+}
+
+fn luminosity(cb: [f64; 3], cs: [f64; 3]) -> [f64; 3] {
+    set_lum(cb, lum(cs))
+}
+
+/// Composite premultiplied `s` onto premultiplied `d` (both `[r, g, b, a]`)
+/// with the non-separable blend function `f`. The result is not clipped.
+#[inline]
+fn non_separable(d: &[f64; 4], s: &[f64; 4], f: fn([f64; 3], [f64; 3]) -> [f64; 3]) -> [f64; 4] {
+    let (sa, da) = (s[3], d[3]);
+    let b = if sa > 0.0 && da > 0.0 {
+        f(
+            [d[0] / da, d[1] / da, d[2] / da],
+            [s[0] / sa, s[1] / sa, s[2] / sa],
+        )
+    } else {
+        [0.0; 3]
+    };
+    let mut out = [0.0; 4];
+    for i in 0..3 {
+        out[i] = s[i] * (1.0 - da) + d[i] * (1.0 - sa) + sa * da * b[i];
+    }
+    out[3] = sa + da - sa * da;
+    out
+}
+
+#[inline(always)]
+fn blend_non_separable(
+    p: &mut [u8; 4],
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+    cover: u8,
+    f: fn([f64; 3], [f64; 3]) -> [f64; 3],
+) {
+    let s = PremulRgba::get(r, g, b, a, cover);
+    if s.a > 0.0 {
+        let d = PremulRgba::get_pix(p, 255);
+        let [r, g, b, a] = non_separable(&[d.r, d.g, d.b, d.a], &[s.r, s.g, s.b, s.a], f);
+        let mut d = PremulRgba { r, g, b, a };
+        PremulRgba::clip(&mut d);
+        PremulRgba::set(p, &d);
+    }
+}
+
+// ============================================================================
+// Floating-point compositing (no clipping)
+// ============================================================================
+
+/// Composite premultiplied source `s` onto premultiplied destination `d`
+/// (both `[r, g, b, a]`) using `op`, with `cover` in `[0, 1]`.
+///
+/// Port of the `comp_op_rgba_*` blenders as instantiated for C++ `rgba32`,
+/// used by the floating-point pixel formats. The formulas match the 8-bit
+/// path above, but the result is NOT clipped to `[0, 1]`, so values above
+/// 1.0 survive (HDR). The `SrcAtop` blue-channel typo that the 8-bit path
+/// keeps for byte-identity is not reproduced here.
+pub(crate) fn comp_op_blend_float(op: CompOp, d: &mut [f64; 4], s: &[f64; 4], cover: f64) {
+    let sc = [s[0] * cover, s[1] * cover, s[2] * cover, s[3] * cover];
+    let icover = 1.0 - cover;
+    let (sa, da) = (s[3], d[3]);
+    let (sca, s1a, d1a) = (sc[3], 1.0 - sc[3], 1.0 - da);
+    // Separable blend: Dca' = f(Sca, Dca) with Da' = Sa + Da - Sa.Da.
+    let separable = |d: &mut [f64; 4], f: &dyn Fn(f64, f64) -> f64| {
+        for i in 0..3 {
+            d[i] = f(sc[i], d[i]);
+        }
+        d[3] = sca + da - sca * da;
+    };
+    let sada = sca * da;
+    // Plus through Exclusion leave the destination alone for a fully
+    // transparent source, as the 8-bit blenders do.
+    if sca <= 0.0 && op as u8 >= CompOp::Plus as u8 {
+        return;
+    }
+
+    match op {
+        CompOp::Clear => d.iter_mut().for_each(|v| *v *= icover),
+        CompOp::Src => {
+            for i in 0..4 {
+                d[i] = d[i] * icover + sc[i];
+            }
+        }
+        CompOp::Dst => {}
+        CompOp::SrcOver => {
+            for i in 0..4 {
+                d[i] = sc[i] + d[i] * s1a;
+            }
+        }
+        CompOp::DstOver => {
+            for i in 0..4 {
+                d[i] += sc[i] * d1a;
+            }
+        }
+        CompOp::SrcIn => {
+            for i in 0..4 {
+                d[i] = d[i] * icover + sc[i] * da;
+            }
+        }
+        CompOp::DstIn => {
+            for v in d.iter_mut() {
+                *v = *v * icover + *v * cover * sa;
+            }
+        }
+        CompOp::SrcOut => {
+            for i in 0..4 {
+                d[i] = d[i] * icover + sc[i] * d1a;
+            }
+        }
+        CompOp::DstOut => {
+            for v in d.iter_mut() {
+                *v = *v * icover + *v * cover * (1.0 - sa);
+            }
+        }
+        CompOp::SrcAtop => {
+            for i in 0..3 {
+                d[i] = sc[i] * da + d[i] * s1a;
+            }
+        }
+        CompOp::DstAtop => {
+            for i in 0..3 {
+                d[i] = d[i] * icover + d[i] * cover * sa + sc[i] * d1a;
+            }
+            d[3] = da * icover + sca;
+        }
+        CompOp::Xor => {
+            for i in 0..3 {
+                d[i] = sc[i] * d1a + d[i] * s1a;
+            }
+            d[3] = sca + da - 2.0 * sca * da;
+        }
+        CompOp::Plus => {
+            for i in 0..4 {
+                d[i] += sc[i];
+            }
+        }
+        CompOp::Minus => {
+            for i in 0..3 {
+                d[i] = (d[i] - sc[i]).max(0.0);
+            }
+            d[3] = sca + da - sca * da;
+        }
+        CompOp::Multiply => {
+            separable(d, &|s, d| s * d + s * d1a + d * s1a);
+        }
+        CompOp::Screen => separable(d, &|s, d| s + d - s * d),
+        CompOp::Overlay => {
+            separable(d, &|s, d| overlay_calc(d, s, da, sca, sada, d1a, s1a));
+        }
+        CompOp::Darken => {
+            separable(d, &|s, d| (s * da).min(d * sca) + s * d1a + d * s1a);
+        }
+        CompOp::Lighten => {
+            separable(d, &|s, d| (s * da).max(d * sca) + s * d1a + d * s1a);
+        }
+        CompOp::ColorDodge | CompOp::ColorBurn | CompOp::SoftLight if da <= 0.0 => {
+            *d = sc;
+        }
+        CompOp::ColorDodge => {
+            separable(d, &|s, d| color_dodge_calc(d, s, da, sca, sada, d1a, s1a));
+        }
+        CompOp::ColorBurn => {
+            separable(d, &|s, d| color_burn_calc(d, s, da, sca, sada, d1a, s1a));
+        }
+        CompOp::HardLight => {
+            separable(d, &|s, d| hard_light_calc(d, s, da, sca, sada, d1a, s1a));
+        }
+        CompOp::SoftLight => {
+            separable(d, &|s, d| soft_light_calc(d, s, da, sca, sada, d1a, s1a));
+        }
+        CompOp::Difference => {
+            separable(d, &|s, d| s + d - 2.0 * (s * da).min(d * sca));
+        }
+        CompOp::Exclusion => {
+            separable(d, &|s, d| {
+                (s * da + d * sca - 2.0 * s * d) + s * d1a + d * s1a
+            });
+        }
+        CompOp::Hue => *d = non_separable(d, &sc, hue),
+        CompOp::Saturation => *d = non_separable(d, &sc, saturation),
+        CompOp::Color => *d = non_separable(d, &sc, color),
+        CompOp::Luminosity => *d = non_separable(d, &sc, luminosity),
+    }
+}
+
 // ============================================================================
 // Span-level compositing dispatch
 // ============================================================================
@@ -770,6 +1037,22 @@ macro_rules! comp_op_span {
                 let $blend = |p: &mut [u8; 4], r: u8, g: u8, b: u8, a: u8, cover: u8| blend_exclusion(p, r, g, b, a, cover);
                 $body
             }
+            CompOp::Hue => {
+                let $blend = |p: &mut [u8; 4], r: u8, g: u8, b: u8, a: u8, cover: u8| blend_non_separable(p, r, g, b, a, cover, hue);
+                $body
+            }
+            CompOp::Saturation => {
+                let $blend = |p: &mut [u8; 4], r: u8, g: u8, b: u8, a: u8, cover: u8| blend_non_separable(p, r, g, b, a, cover, saturation);
+                $body
+            }
+            CompOp::Color => {
+                let $blend = |p: &mut [u8; 4], r: u8, g: u8, b: u8, a: u8, cover: u8| blend_non_separable(p, r, g, b, a, cover, color);
+                $body
+            }
+            CompOp::Luminosity => {
+                let $blend = |p: &mut [u8; 4], r: u8, g: u8, b: u8, a: u8, cover: u8| blend_non_separable(p, r, g, b, a, cover, luminosity);
+                $body
+            }
         }
     };
 }
@@ -780,28 +1063,68 @@ macro_rules! comp_op_span {
 
 const BPP: usize = 4;
 
-/// RGBA32 pixel format with runtime-selectable SVG compositing operations.
+/// 32-bit RGBA pixel format (any channel order) with runtime-selectable SVG
+/// compositing operations.
 ///
-/// Port of C++ `pixfmt_custom_blend_rgba<comp_op_adaptor_rgba<rgba8, order_rgba>, rendering_buffer>`.
+/// Port of C++ `pixfmt_custom_blend_rgba<comp_op_adaptor_rgba<rgba8, Order>, rendering_buffer>`.
 /// Wraps a `RowAccessor` and stores the current compositing operation.
-/// All blending is dispatched through `comp_op_blend()`.
-pub struct PixfmtRgba32CompOp<'a> {
-    rbuf: &'a mut RowAccessor,
+/// All blending is dispatched through `comp_op_blend()`, which works on
+/// logical `[r, g, b, a]` pixels; `O` only decides where each channel lives
+/// in memory.
+pub struct PixfmtCustomBlendRgba<'a, O, RB = RowAccessor> {
+    rbuf: &'a mut RB,
     comp_op: CompOp,
+    order: PhantomData<O>,
+}
+
+/// Compositing RGBA byte order. Port of C++ `pixfmt_rgba32` with `comp_op_adaptor_rgba`.
+pub type PixfmtRgba32CompOp<'a, RB = RowAccessor> = PixfmtCustomBlendRgba<'a, OrderRgba, RB>;
+/// Compositing BGRA byte order.
+pub type PixfmtBgra32CompOp<'a, RB = RowAccessor> = PixfmtCustomBlendRgba<'a, OrderBgra, RB>;
+/// Compositing ARGB byte order.
+pub type PixfmtArgb32CompOp<'a, RB = RowAccessor> = PixfmtCustomBlendRgba<'a, OrderArgb, RB>;
+/// Compositing ABGR byte order.
+pub type PixfmtAbgr32CompOp<'a, RB = RowAccessor> = PixfmtCustomBlendRgba<'a, OrderAbgr, RB>;
+
+/// `c` premultiplied by its alpha, as the bytes of channel order `O`: the
+/// source `blend_src_over` sees, laid out for the SIMD `SrcOver` spans.
+#[inline]
+fn premultiplied_bytes<O: RgbaOrder>(c: &Rgba8) -> [u8; 4] {
+    let mut p = [0u8; 4];
+    p[O::R] = Rgba8::multiply(c.r, c.a);
+    p[O::G] = Rgba8::multiply(c.g, c.a);
+    p[O::B] = Rgba8::multiply(c.b, c.a);
+    p[O::A] = c.a;
+    p
+}
+
+/// Run `blend` on pixel `px` viewed as `[r, g, b, a]`, then store the
+/// result back in channel order `O`. For `OrderRgba` the shuffles are the
+/// identity and compile away.
+#[inline(always)]
+fn blend_ordered<O: RgbaOrder>(px: &mut [u8; 4], blend: impl FnOnce(&mut [u8; 4])) {
+    let mut p = [px[O::R], px[O::G], px[O::B], px[O::A]];
+    blend(&mut p);
+    px[O::R] = p[0];
+    px[O::G] = p[1];
+    px[O::B] = p[2];
+    px[O::A] = p[3];
 }
 
-impl<'a> PixfmtRgba32CompOp<'a> {
-    pub fn new(rbuf: &'a mut RowAccessor) -> Self {
+impl<'a, O: RgbaOrder, RB: RenderingBufferMut> PixfmtCustomBlendRgba<'a, O, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self::new_with_op(rbuf, CompOp::SrcOver)
+    }
+
+    pub fn new_with_op(rbuf: &'a mut RB, op: CompOp) -> Self {
+        assert_row_len(rbuf, BPP);
         Self {
             rbuf,
-            comp_op: CompOp::SrcOver,
+            comp_op: op,
+            order: PhantomData,
         }
     }
 
-    pub fn new_with_op(rbuf: &'a mut RowAccessor, op: CompOp) -> Self {
-        Self { rbuf, comp_op: op }
-    }
-
     pub fn comp_op(&self) -> CompOp {
         self.comp_op
     }
@@ -814,7 +1137,7 @@ impl<'a> PixfmtRgba32CompOp<'a> {
     #[inline]
     fn row(&self, y: i32) -> &[u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
+            let ptr = self.rbuf.row_ptr_const(y);
             std::slice::from_raw_parts(ptr, self.rbuf.width() as usize * BPP)
         }
     }
@@ -823,29 +1146,26 @@ impl<'a> PixfmtRgba32CompOp<'a> {
     #[inline]
     fn row_mut(&mut self, y: i32) -> &mut [u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
-            std::slice::from_raw_parts_mut(ptr, self.rbuf.width() as usize * BPP)
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * BPP)
         }
     }
 
     /// Clear the entire buffer to a solid color.
     pub fn clear(&mut self, c: &Rgba8) {
-        let w = self.rbuf.width();
         let h = self.rbuf.height();
+        let pat = pixel_bytes::<O>(c);
         for y in 0..h {
             let row = self.row_mut(y as i32);
-            for x in 0..w as usize {
-                let off = x * BPP;
-                row[off] = c.r;
-                row[off + 1] = c.g;
-                row[off + 2] = c.b;
-                row[off + 3] = c.a;
+            for px in row.chunks_exact_mut(BPP) {
+                px.copy_from_slice(&pat);
             }
         }
     }
 }
 
-impl<'a> PixelFormat for PixfmtRgba32CompOp<'a> {
+impl<'a, O: RgbaOrder, RB: RenderingBufferMut> PixelFormat for PixfmtCustomBlendRgba<'a, O, RB> {
     type ColorType = Rgba8;
 
     fn width(&self) -> u32 {
@@ -860,30 +1180,25 @@ impl<'a> PixelFormat for PixfmtRgba32CompOp<'a> {
         let row = self.row(y);
         let off = x as usize * BPP;
         Rgba8::new(
-            row[off] as u32,
-            row[off + 1] as u32,
-            row[off + 2] as u32,
-            row[off + 3] as u32,
+            row[off + O::R] as u32,
+            row[off + O::G] as u32,
+            row[off + O::B] as u32,
+            row[off + O::A] as u32,
         )
     }
 
     fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba8) {
         let row = self.row_mut(y);
         let off = x as usize * BPP;
-        row[off] = c.r;
-        row[off + 1] = c.g;
-        row[off + 2] = c.b;
-        row[off + 3] = c.a;
+        row[off..off + BPP].copy_from_slice(&pixel_bytes::<O>(c));
     }
 
     fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8) {
         let row = self.row_mut(y);
+        let pat = pixel_bytes::<O>(c);
         for i in 0..len as usize {
             let off = (x as usize + i) * BPP;
-            row[off] = c.r;
-            row[off + 1] = c.g;
-            row[off + 2] = c.b;
-            row[off + 3] = c.a;
+            row[off..off + BPP].copy_from_slice(&pat);
         }
     }
 
@@ -892,7 +1207,7 @@ impl<'a> PixelFormat for PixfmtRgba32CompOp<'a> {
         let row = self.row_mut(y);
         let off = x as usize * BPP;
         let px: &mut [u8; 4] = (&mut row[off..off + BPP]).try_into().unwrap();
-        comp_op_blend(comp_op, px, c.r, c.g, c.b, c.a, cover);
+        blend_ordered::<O>(px, |p| comp_op_blend(comp_op, p, c.r, c.g, c.b, c.a, cover));
     }
 
     fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, cover: CoverType) {
@@ -901,6 +1216,11 @@ impl<'a> PixelFormat for PixfmtRgba32CompOp<'a> {
         // Slice the span once so the inner loop carries no per-pixel bounds checks
         // or index arithmetic — the C++ path walks a raw pointer here.
         let span = &mut row[x as usize * BPP..(x as usize + len as usize) * BPP];
+        if comp_op == CompOp::SrcOver {
+            let src = SpanSrc::Solid(premultiplied_bytes::<O>(c));
+            blend_span(BlendOp::Prelerp, span, src, SpanCover::Uniform(cover), O::A);
+            return;
+        }
         comp_op_span!(comp_op, |blend| {
             // Colour and cover are constant across the span, so the premultiply is
             // loop-invariant (byte-identical to premultiplying per pixel).
@@ -912,7 +1232,7 @@ impl<'a> PixelFormat for PixfmtRgba32CompOp<'a> {
             // 4-byte array, letting the load/compute/store stay in XMM registers.
             let (pixels, _rem) = span.as_chunks_mut::<BPP>();
             for px in pixels {
-                blend(px, r, g, b, a, cover);
+                blend_ordered::<O>(px, |p| blend(p, r, g, b, a, cover));
             }
         });
     }
@@ -920,6 +1240,13 @@ impl<'a> PixelFormat for PixfmtRgba32CompOp<'a> {
     fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, covers: &[CoverType]) {
         let comp_op = self.comp_op;
         let row = self.row_mut(y);
+        if comp_op == CompOp::SrcOver {
+            let n = (len as usize).min(covers.len());
+            let span = &mut row[x as usize * BPP..(x as usize + n) * BPP];
+            let src = SpanSrc::Solid(premultiplied_bytes::<O>(c));
+            blend_span(BlendOp::Prelerp, span, src, SpanCover::Covers(covers), O::A);
+            return;
+        }
         let span = &mut row[x as usize * BPP..(x as usize + len as usize) * BPP];
         comp_op_span!(comp_op, |blend| {
             // Solid span: constant colour, so premultiply once outside the loop.
@@ -929,7 +1256,7 @@ impl<'a> PixelFormat for PixfmtRgba32CompOp<'a> {
             let a = c.a;
             let (pixels, _rem) = span.as_chunks_mut::<BPP>();
             for (px, &cov) in pixels.iter_mut().zip(covers.iter()) {
-                blend(px, r, g, b, a, cov);
+                blend_ordered::<O>(px, |p| blend(p, r, g, b, a, cov));
             }
         });
     }
@@ -945,6 +1272,19 @@ impl<'a> PixelFormat for PixfmtRgba32CompOp<'a> {
     ) {
         let comp_op = self.comp_op;
         let row = self.row_mut(y);
+        if comp_op == CompOp::SrcOver {
+            let mut n = (len as usize).min(colors.len());
+            let cover = if covers.is_empty() {
+                SpanCover::Uniform(cover)
+            } else {
+                n = n.min(covers.len());
+                SpanCover::Covers(covers)
+            };
+            let span = &mut row[x as usize * BPP..(x as usize + n) * BPP];
+            let colors = colors.iter().map(premultiplied_bytes::<O>);
+            blend_colors(BlendOp::Prelerp, span, colors, cover, O::A);
+            return;
+        }
         let span = &mut row[x as usize * BPP..(x as usize + len as usize) * BPP];
         comp_op_span!(comp_op, |blend| {
             // Colour differs per pixel here, so the premultiply must stay per pixel
@@ -956,20 +1296,409 @@ impl<'a> PixelFormat for PixfmtRgba32CompOp<'a> {
                     let r = Rgba8::multiply(c.r, c.a);
                     let g = Rgba8::multiply(c.g, c.a);
                     let b = Rgba8::multiply(c.b, c.a);
-                    blend(px, r, g, b, c.a, cover);
+                    blend_ordered::<O>(px, |p| blend(p, r, g, b, c.a, cover));
                 }
             } else {
                 for ((px, c), &cov) in pixels.iter_mut().zip(colors.iter()).zip(covers.iter()) {
                     let r = Rgba8::multiply(c.r, c.a);
                     let g = Rgba8::multiply(c.g, c.a);
                     let b = Rgba8::multiply(c.b, c.a);
-                    blend(px, r, g, b, c.a, cov);
+                    blend_ordered::<O>(px, |p| blend(p, r, g, b, c.a, cov));
                 }
             }
         });
     }
 }
 
+// ============================================================================
+// PixfmtCompOp — runtime compositing for the other pixel formats
+// ============================================================================
+
+/// A pixel layout that [`PixfmtCompOp`] can composite into.
+///
+/// `load` and `store` convert between a stored pixel and premultiplied
+/// `[r, g, b, a]` in `[0, 1]`, which is what `comp_op_blend_float` works
+/// on. Layouts without an alpha channel load as opaque and store the color
+/// as if composited over black; gray layouts store the red channel, which
+/// every op keeps equal to green and blue for gray inputs.
+pub trait CompOpLayout {
+    type ColorType;
+    /// Bytes per pixel.
+    const BPP: usize;
+
+    fn load(p: &[u8]) -> [f64; 4];
+    fn store(p: &mut [u8], c: &[f64; 4]);
+    /// The non-premultiplied source color `c`, premultiplied.
+    fn source(c: &Self::ColorType) -> [f64; 4];
+    fn pixel(p: &[u8]) -> Self::ColorType;
+    fn copy(p: &mut [u8], c: &Self::ColorType);
+}
+
+#[inline]
+fn get16(p: &[u8], i: usize) -> u16 {
+    u16::from_ne_bytes([p[i * 2], p[i * 2 + 1]])
+}
+
+#[inline]
+fn set16(p: &mut [u8], i: usize, v: u16) {
+    p[i * 2..i * 2 + 2].copy_from_slice(&v.to_ne_bytes());
+}
+
+#[inline]
+fn to_double16(v: u16) -> f64 {
+    v as f64 / Rgba16::BASE_MASK as f64
+}
+
+#[inline]
+fn from_double16(v: f64) -> u16 {
+    uround(v * Rgba16::BASE_MASK as f64) as u16
+}
+
+/// 24-bit RGB layout in channel order `O`.
+pub struct CompOpRgb24<O>(PhantomData<O>);
+
+impl<O: RgbOrder> CompOpLayout for CompOpRgb24<O> {
+    type ColorType = Rgba8;
+    const BPP: usize = 3;
+
+    fn load(p: &[u8]) -> [f64; 4] {
+        let [r, g, b] = [p[O::R], p[O::G], p[O::B]].map(Rgba8::to_double);
+        [r, g, b, 1.0]
+    }
+
+    fn store(p: &mut [u8], c: &[f64; 4]) {
+        p[O::R] = Rgba8::from_double(c[0]);
+        p[O::G] = Rgba8::from_double(c[1]);
+        p[O::B] = Rgba8::from_double(c[2]);
+    }
+
+    fn source(c: &Rgba8) -> [f64; 4] {
+        let a = Rgba8::to_double(c.a);
+        let [r, g, b] = [c.r, c.g, c.b].map(Rgba8::to_double);
+        [r * a, g * a, b * a, a]
+    }
+
+    fn pixel(p: &[u8]) -> Rgba8 {
+        Rgba8::new(p[O::R] as u32, p[O::G] as u32, p[O::B] as u32, 255)
+    }
+
+    fn copy(p: &mut [u8], c: &Rgba8) {
+        p[O::R] = c.r;
+        p[O::G] = c.g;
+        p[O::B] = c.b;
+    }
+}
+
+/// 48-bit RGB layout (native-endian `u16` channels) in channel order `O`.
+pub struct CompOpRgb48<O>(PhantomData<O>);
+
+impl<O: RgbOrder> CompOpLayout for CompOpRgb48<O> {
+    type ColorType = Rgba16;
+    const BPP: usize = 6;
+
+    fn load(p: &[u8]) -> [f64; 4] {
+        let [r, g, b] = [get16(p, O::R), get16(p, O::G), get16(p, O::B)].map(to_double16);
+        [r, g, b, 1.0]
+    }
+
+    fn store(p: &mut [u8], c: &[f64; 4]) {
+        set16(p, O::R, from_double16(c[0]));
+        set16(p, O::G, from_double16(c[1]));
+        set16(p, O::B, from_double16(c[2]));
+    }
+
+    fn source(c: &Rgba16) -> [f64; 4] {
+        let a = to_double16(c.a);
+        let [r, g, b] = [c.r, c.g, c.b].map(to_double16);
+        [r * a, g * a, b * a, a]
+    }
+
+    fn pixel(p: &[u8]) -> Rgba16 {
+        Rgba16::new(
+            get16(p, O::R) as u32,
+            get16(p, O::G) as u32,
+            get16(p, O::B) as u32,
+            Rgba16::BASE_MASK,
+        )
+    }
+
+    fn copy(p: &mut [u8], c: &Rgba16) {
+        set16(p, O::R, c.r);
+        set16(p, O::G, c.g);
+        set16(p, O::B, c.b);
+    }
+}
+
+/// 64-bit premultiplied RGBA layout (native-endian `u16` channels).
+pub struct CompOpRgba64;
+
+impl CompOpLayout for CompOpRgba64 {
+    type ColorType = Rgba16;
+    const BPP: usize = 8;
+
+    fn load(p: &[u8]) -> [f64; 4] {
+        [get16(p, 0), get16(p, 1), get16(p, 2), get16(p, 3)].map(to_double16)
+    }
+
+    fn store(p: &mut [u8], c: &[f64; 4]) {
+        for (i, &v) in c.iter().enumerate() {
+            set16(p, i, from_double16(v));
+        }
+    }
+
+    fn source(c: &Rgba16) -> [f64; 4] {
+        let a = to_double16(c.a);
+        let [r, g, b] = [c.r, c.g, c.b].map(to_double16);
+        [r * a, g * a, b * a, a]
+    }
+
+    fn pixel(p: &[u8]) -> Rgba16 {
+        let [r, g, b, a] = [get16(p, 0), get16(p, 1), get16(p, 2), get16(p, 3)];
+        Rgba16::new(r as u32, g as u32, b as u32, a as u32)
+    }
+
+    fn copy(p: &mut [u8], c: &Rgba16) {
+        for (i, v) in [c.r, c.g, c.b, c.a].into_iter().enumerate() {
+            set16(p, i, v);
+        }
+    }
+}
+
+/// 8-bit grayscale layout.
+pub struct CompOpGray8;
+
+impl CompOpLayout for CompOpGray8 {
+    type ColorType = Gray8;
+    const BPP: usize = 1;
+
+    fn load(p: &[u8]) -> [f64; 4] {
+        let v = Rgba8::to_double(p[0]);
+        [v, v, v, 1.0]
+    }
+
+    fn store(p: &mut [u8], c: &[f64; 4]) {
+        p[0] = Rgba8::from_double(c[0]);
+    }
+
+    fn source(c: &Gray8) -> [f64; 4] {
+        let a = Rgba8::to_double(c.a);
+        let v = Rgba8::to_double(c.v) * a;
+        [v, v, v, a]
+    }
+
+    fn pixel(p: &[u8]) -> Gray8 {
+        Gray8::new(p[0] as u32, 255)
+    }
+
+    fn copy(p: &mut [u8], c: &Gray8) {
+        p[0] = c.v;
+    }
+}
+
+/// 16-bit grayscale layout (native-endian `u16`).
+pub struct CompOpGray16;
+
+impl CompOpLayout for CompOpGray16 {
+    type ColorType = Gray16;
+    const BPP: usize = 2;
+
+    fn load(p: &[u8]) -> [f64; 4] {
+        let v = to_double16(get16(p, 0));
+        [v, v, v, 1.0]
+    }
+
+    fn store(p: &mut [u8], c: &[f64; 4]) {
+        set16(p, 0, from_double16(c[0]));
+    }
+
+    fn source(c: &Gray16) -> [f64; 4] {
+        let a = to_double16(c.a);
+        let v = to_double16(c.v) * a;
+        [v, v, v, a]
+    }
+
+    fn pixel(p: &[u8]) -> Gray16 {
+        Gray16::new(get16(p, 0) as u32, Gray16::BASE_MASK)
+    }
+
+    fn copy(p: &mut [u8], c: &Gray16) {
+        set16(p, 0, c.v);
+    }
+}
+
+/// Pixel format with runtime-selectable compositing for any
+/// [`CompOpLayout`].
+///
+/// The generic counterpart of [`PixfmtCustomBlendRgba`]: every blend loads
+/// the destination as premultiplied `f64`, runs `comp_op_blend_float`, and
+/// clips and stores the result. RGBA32 keeps its dedicated fixed-point
+/// path, which is byte-identical to C++.
+pub struct PixfmtCompOp<'a, L, RB = RowAccessor> {
+    rbuf: &'a mut RB,
+    comp_op: CompOp,
+    layout: PhantomData<L>,
+}
+
+/// Compositing RGB24. The buffer holds no alpha.
+pub type PixfmtRgb24CompOp<'a, RB = RowAccessor> = PixfmtCompOp<'a, CompOpRgb24<OrderRgb>, RB>;
+/// Compositing BGR24. The buffer holds no alpha.
+pub type PixfmtBgr24CompOp<'a, RB = RowAccessor> = PixfmtCompOp<'a, CompOpRgb24<OrderBgr>, RB>;
+/// Compositing RGB48. The buffer holds no alpha.
+pub type PixfmtRgb48CompOp<'a, RB = RowAccessor> = PixfmtCompOp<'a, CompOpRgb48<OrderRgb>, RB>;
+/// Compositing BGR48. The buffer holds no alpha.
+pub type PixfmtBgr48CompOp<'a, RB = RowAccessor> = PixfmtCompOp<'a, CompOpRgb48<OrderBgr>, RB>;
+/// Compositing premultiplied RGBA64.
+pub type PixfmtRgba64CompOp<'a, RB = RowAccessor> = PixfmtCompOp<'a, CompOpRgba64, RB>;
+/// Compositing Gray8. The buffer holds no alpha.
+pub type PixfmtGray8CompOp<'a, RB = RowAccessor> = PixfmtCompOp<'a, CompOpGray8, RB>;
+/// Compositing Gray16. The buffer holds no alpha.
+pub type PixfmtGray16CompOp<'a, RB = RowAccessor> = PixfmtCompOp<'a, CompOpGray16, RB>;
+
+impl<'a, L: CompOpLayout, RB: RenderingBufferMut> PixfmtCompOp<'a, L, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self::new_with_op(rbuf, CompOp::SrcOver)
+    }
+
+    pub fn new_with_op(rbuf: &'a mut RB, op: CompOp) -> Self {
+        assert_row_len(rbuf, L::BPP);
+        Self {
+            rbuf,
+            comp_op: op,
+            layout: PhantomData,
+        }
+    }
+
+    pub fn comp_op(&self) -> CompOp {
+        self.comp_op
+    }
+
+    pub fn set_comp_op(&mut self, op: CompOp) {
+        self.comp_op = op;
+    }
+
+    #[inline]
+    fn row(&self, y: i32) -> &[u8] {
+        unsafe {
+            let ptr = self.rbuf.row_ptr_const(y);
+            std::slice::from_raw_parts(ptr, self.rbuf.width() as usize * L::BPP)
+        }
+    }
+
+    #[inline]
+    fn row_mut(&mut self, y: i32) -> &mut [u8] {
+        unsafe {
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * L::BPP)
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &L::ColorType) {
+        let h = self.rbuf.height();
+        for y in 0..h {
+            let row = self.row_mut(y as i32);
+            for px in row.chunks_exact_mut(L::BPP) {
+                L::copy(px, c);
+            }
+        }
+    }
+
+    /// Composite premultiplied source `s` into pixel `x` of `row`.
+    #[inline]
+    fn blend(op: CompOp, row: &mut [u8], x: usize, s: &[f64; 4], cover: CoverType) {
+        if cover == 0 || op == CompOp::Dst {
+            return;
+        }
+        let p = &mut row[x * L::BPP..(x + 1) * L::BPP];
+        let mut d = L::load(p);
+        comp_op_blend_float(op, &mut d, s, cover as f64 / 255.0);
+        let mut c = PremulRgba {
+            r: d[0],
+            g: d[1],
+            b: d[2],
+            a: d[3],
+        };
+        PremulRgba::clip(&mut c);
+        L::store(p, &[c.r, c.g, c.b, c.a]);
+    }
+}
+
+impl<'a, L: CompOpLayout, RB: RenderingBufferMut> PixelFormat for PixfmtCompOp<'a, L, RB> {
+    type ColorType = L::ColorType;
+
+    fn width(&self) -> u32 {
+        self.rbuf.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.rbuf.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> L::ColorType {
+        let off = x as usize * L::BPP;
+        L::pixel(&self.row(y)[off..off + L::BPP])
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &L::ColorType) {
+        let off = x as usize * L::BPP;
+        L::copy(&mut self.row_mut(y)[off..off + L::BPP], c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &L::ColorType) {
+        let start = x as usize * L::BPP;
+        let row = self.row_mut(y);
+        for px in row[start..start + len as usize * L::BPP].chunks_exact_mut(L::BPP) {
+            L::copy(px, c);
+        }
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &L::ColorType, cover: CoverType) {
+        let (op, s) = (self.comp_op, L::source(c));
+        Self::blend(op, self.row_mut(y), x as usize, &s, cover);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &L::ColorType, cover: CoverType) {
+        let (op, s) = (self.comp_op, L::source(c));
+        let row = self.row_mut(y);
+        for i in 0..len as usize {
+            Self::blend(op, row, x as usize + i, &s, cover);
+        }
+    }
+
+    fn blend_solid_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        c: &L::ColorType,
+        covers: &[CoverType],
+    ) {
+        let (op, s) = (self.comp_op, L::source(c));
+        let row = self.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            Self::blend(op, row, x as usize + i, &s, cov);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[L::ColorType],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let op = self.comp_op;
+        let row = self.row_mut(y);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            Self::blend(op, row, x as usize + i, &L::source(c), cov);
+        }
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1290,4 +2019,297 @@ mod tests {
         assert_eq!(p.g, 10);
         assert_eq!(p.b, 10);
     }
+
+    #[test]
+    fn test_bgra_comp_op_matches_rgba() {
+        let bg = Rgba8::new(200, 100, 50, 180);
+        let c = Rgba8::new(30, 160, 240, 128);
+        for op in [
+            CompOp::SrcOver,
+            CompOp::Multiply,
+            CompOp::SrcAtop,
+            CompOp::Xor,
+        ] {
+            let (_b1, mut ra1) = make_buffer(3, 1);
+            let mut rgba = PixfmtRgba32CompOp::new_with_op(&mut ra1, op);
+            rgba.clear(&bg);
+            rgba.blend_solid_hspan(0, 0, 3, &c, &[255, 100, 0]);
+
+            let (buf, mut ra2) = make_buffer(3, 1);
+            let mut bgra = PixfmtBgra32CompOp::new_with_op(&mut ra2, op);
+            bgra.clear(&bg);
+            bgra.blend_solid_hspan(0, 0, 3, &c, &[255, 100, 0]);
+
+            for x in 0..3 {
+                let p = rgba.pixel(x, 0);
+                assert_eq!(bgra.pixel(x, 0), p, "{:?} at {}", op, x);
+                let (b, g, r, a) = get_pixel(&buf, 3, x as u32, 0);
+                assert_eq!((r, g, b, a), (p.r, p.g, p.b, p.a));
+            }
+        }
+    }
+
+    /// W3C Compositing and Blending Level 1, section 5.8, written out
+    /// literally on non-premultiplied `[0, 1]` colors.
+    mod spec {
+        pub fn lum(c: [f64; 3]) -> f64 {
+            0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+        }
+
+        pub fn clip_color(c: [f64; 3]) -> [f64; 3] {
+            let l = lum(c);
+            let n = c.iter().cloned().fold(f64::INFINITY, f64::min);
+            let x = c.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let mut c = c;
+            if n < 0.0 {
+                c = c.map(|v| l + (v - l) * l / (l - n));
+            }
+            if x > 1.0 {
+                c = c.map(|v| l + (v - l) * (1.0 - l) / (x - l));
+            }
+            c
+        }
+
+        pub fn set_lum(c: [f64; 3], l: f64) -> [f64; 3] {
+            let d = l - lum(c);
+            clip_color(c.map(|v| v + d))
+        }
+
+        pub fn sat(c: [f64; 3]) -> f64 {
+            c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+        }
+
+        pub fn set_sat(c: [f64; 3], s: f64) -> [f64; 3] {
+            let (mx, mn) = (c[0].max(c[1]).max(c[2]), c[0].min(c[1]).min(c[2]));
+            if mx == mn {
+                return [0.0; 3];
+            }
+            c.map(|v| (v - mn) * s / (mx - mn))
+        }
+
+        /// Blend `cs` (alpha `sa`) over `cb` (alpha `ba`) with `b`; returns
+        /// the premultiplied result.
+        pub fn composite(
+            cb: [f64; 3],
+            ba: f64,
+            cs: [f64; 3],
+            sa: f64,
+            b: fn([f64; 3], [f64; 3]) -> [f64; 3],
+        ) -> [f64; 4] {
+            let bl = b(cb, cs);
+            let mut out = [0.0; 4];
+            for i in 0..3 {
+                out[i] = (1.0 - ba) * sa * cs[i] + (1.0 - sa) * ba * cb[i] + sa * ba * bl[i];
+            }
+            out[3] = sa + ba * (1.0 - sa);
+            out
+        }
+    }
+
+    fn spec_mode(op: CompOp) -> fn([f64; 3], [f64; 3]) -> [f64; 3] {
+        match op {
+            CompOp::Hue => |cb, cs| spec::set_lum(spec::set_sat(cs, spec::sat(cb)), spec::lum(cb)),
+            CompOp::Saturation => {
+                |cb, cs| spec::set_lum(spec::set_sat(cb, spec::sat(cs)), spec::lum(cb))
+            }
+            CompOp::Color => |cb, cs| spec::set_lum(cs, spec::lum(cb)),
+            CompOp::Luminosity => |cb, cs| spec::set_lum(cb, spec::lum(cs)),
+            _ => unreachable!(),
+        }
+    }
+
+    const NON_SEPARABLE: [CompOp; 4] = [
+        CompOp::Hue,
+        CompOp::Saturation,
+        CompOp::Color,
+        CompOp::Luminosity,
+    ];
+
+    #[test]
+    fn test_non_separable_known_values() {
+        let (_buf, mut ra) = make_buffer(3, 1);
+        let mut pf = PixfmtRgba32CompOp::new(&mut ra);
+        pf.copy_pixel(0, 0, &Rgba8::new(128, 128, 128, 255));
+        pf.copy_pixel(1, 0, &Rgba8::new(255, 0, 0, 255));
+        pf.copy_pixel(2, 0, &Rgba8::new(255, 255, 255, 255));
+
+        // A grey backdrop has no saturation to give a red hue.
+        pf.set_comp_op(CompOp::Hue);
+        pf.blend_pixel(0, 0, &Rgba8::new(255, 0, 0, 255), 255);
+        assert_eq!(pf.pixel(0, 0), Rgba8::new(128, 128, 128, 255));
+
+        // White luminosity over red clips to white.
+        pf.set_comp_op(CompOp::Luminosity);
+        pf.blend_pixel(1, 0, &Rgba8::new(255, 255, 255, 255), 255);
+        assert_eq!(pf.pixel(1, 0), Rgba8::new(255, 255, 255, 255));
+
+        // Saturation from a grey source desaturates to the backdrop's lum.
+        pf.set_comp_op(CompOp::Saturation);
+        pf.blend_pixel(2, 0, &Rgba8::new(10, 10, 10, 255), 255);
+        assert_eq!(pf.pixel(2, 0), Rgba8::new(255, 255, 255, 255));
+    }
+
+    #[test]
+    fn test_non_separable_match_spec() {
+        let backdrops = [
+            Rgba8::new(200, 100, 50, 255),
+            Rgba8::new(20, 150, 90, 160),
+            Rgba8::new(128, 128, 128, 255),
+        ];
+        let sources = [
+            Rgba8::new(30, 160, 240, 255),
+            Rgba8::new(250, 20, 120, 128),
+            Rgba8::new(90, 90, 90, 200),
+        ];
+        let unit = |c: &Rgba8| [c.r as f64 / 255.0, c.g as f64 / 255.0, c.b as f64 / 255.0];
+        for op in NON_SEPARABLE {
+            for bd in &backdrops {
+                for src in &sources {
+                    let (_buf, mut ra) = make_buffer(1, 1);
+                    let mut pf = PixfmtRgba32CompOp::new_with_op(&mut ra, op);
+                    pf.copy_pixel(0, 0, bd);
+                    pf.blend_pixel(0, 0, src, 255);
+                    let got = pf.pixel(0, 0);
+
+                    // The buffer holds premultiplied values.
+                    let ba = bd.a as f64 / 255.0;
+                    let cb = unit(bd).map(|v| v / ba);
+                    let sa = src.a as f64 / 255.0;
+                    let want = spec::composite(cb, ba, unit(src), sa, spec_mode(op));
+                    let got = [got.r, got.g, got.b, got.a];
+                    for i in 0..4 {
+                        let w = want[i] * 255.0;
+                        assert!(
+                            (got[i] as f64 - w).abs() <= 1.5,
+                            "{op:?} {bd:?} {src:?}: channel {i} got {} want {w:.2}",
+                            got[i]
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_separable_float_matches_spec() {
+        let cb = [0.9, 0.2, 0.4];
+        let cs = [0.1, 0.7, 0.3];
+        for op in NON_SEPARABLE {
+            let mut d = [cb[0] * 0.8, cb[1] * 0.8, cb[2] * 0.8, 0.8];
+            let s = [cs[0] * 0.5, cs[1] * 0.5, cs[2] * 0.5, 0.5];
+            comp_op_blend_float(op, &mut d, &s, 1.0);
+            let want = spec::composite(cb, 0.8, cs, 0.5, spec_mode(op));
+            for i in 0..4 {
+                assert!((d[i] - want[i]).abs() < 1e-12, "{op:?} channel {i}");
+            }
+        }
+    }
+
+    fn make_buffer_bpp(w: u32, h: u32, bpp: u32) -> (Vec<u8>, RowAccessor) {
+        let buf = vec![0u8; (h * w * bpp) as usize];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, w, h, (w * bpp) as i32);
+        }
+        (buf, ra)
+    }
+
+    #[test]
+    fn test_rgb24_comp_op_matches_rgba32_on_opaque_dst() {
+        let dst = Rgba8::new(200, 90, 30, 255);
+        let src = Rgba8::new(40, 160, 220, 180);
+        for op in [
+            CompOp::Multiply,
+            CompOp::Screen,
+            CompOp::Overlay,
+            CompOp::Hue,
+        ] {
+            let (_b32, mut ra32) = make_buffer(1, 1);
+            let mut pf32 = PixfmtRgba32CompOp::new_with_op(&mut ra32, op);
+            pf32.copy_pixel(0, 0, &dst);
+            pf32.blend_pixel(0, 0, &src, 200);
+            let want = pf32.pixel(0, 0);
+
+            let (_b24, mut ra24) = make_buffer_bpp(1, 1, 3);
+            let mut pf24 = PixfmtRgb24CompOp::new_with_op(&mut ra24, op);
+            pf24.copy_pixel(0, 0, &dst);
+            pf24.blend_pixel(0, 0, &src, 200);
+            let got = pf24.pixel(0, 0);
+
+            assert_eq!(want.a, 255, "{op:?}");
+            for (g, w) in [(got.r, want.r), (got.g, want.g), (got.b, want.b)] {
+                assert!(
+                    (g as i32 - w as i32).abs() <= 1,
+                    "{op:?}: {got:?} vs {want:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bgr24_comp_op_byte_layout() {
+        let (buf, mut ra) = make_buffer_bpp(1, 1, 3);
+        let mut pf = PixfmtBgr24CompOp::new_with_op(&mut ra, CompOp::Src);
+        pf.blend_pixel(0, 0, &Rgba8::new(10, 20, 30, 255), 255);
+        assert_eq!(&buf[..], &[30, 20, 10]);
+    }
+
+    #[test]
+    fn test_rgb24_comp_op_clear_is_black() {
+        let (buf, mut ra) = make_buffer_bpp(2, 1, 3);
+        let mut pf = PixfmtRgb24CompOp::new(&mut ra);
+        pf.clear(&Rgba8::new(255, 255, 255, 255));
+        pf.set_comp_op(CompOp::Clear);
+        pf.blend_hline(0, 0, 2, &Rgba8::new(1, 2, 3, 255), 255);
+        assert!(buf.iter().all(|&v| v == 0));
+    }
+
+    #[test]
+    fn test_comp_op_zero_cover_is_noop() {
+        let (buf, mut ra) = make_buffer_bpp(3, 1, 1);
+        let mut pf = PixfmtGray8CompOp::new_with_op(&mut ra, CompOp::Clear);
+        pf.clear(&Gray8::new(77, 255));
+        pf.blend_solid_hspan(0, 0, 3, &Gray8::new(0, 255), &[0, 255, 0]);
+        assert_eq!(&buf[..], &[77, 0, 77]);
+    }
+
+    #[test]
+    fn test_gray8_screen() {
+        let (_buf, mut ra) = make_buffer_bpp(1, 1, 1);
+        let mut pf = PixfmtGray8CompOp::new_with_op(&mut ra, CompOp::Screen);
+        pf.copy_pixel(0, 0, &Gray8::new(128, 255));
+        pf.blend_pixel(0, 0, &Gray8::new(128, 255), 255);
+        // 1 - (1 - 0.5)^2 = 0.75
+        assert_eq!(pf.pixel(0, 0).v, 192);
+    }
+
+    #[test]
+    fn test_gray16_multiply() {
+        let (_buf, mut ra) = make_buffer_bpp(1, 1, 2);
+        let mut pf = PixfmtGray16CompOp::new_with_op(&mut ra, CompOp::Multiply);
+        pf.copy_pixel(0, 0, &Gray16::new(65535, 65535));
+        pf.blend_pixel(0, 0, &Gray16::new(32768, 65535), 255);
+        assert_eq!(pf.pixel(0, 0).v, 32768);
+    }
+
+    #[test]
+    fn test_rgba64_multiply_keeps_alpha() {
+        let (_buf, mut ra) = make_buffer_bpp(1, 1, 8);
+        let mut pf = PixfmtRgba64CompOp::new_with_op(&mut ra, CompOp::Multiply);
+        pf.copy_pixel(0, 0, &Rgba16::new(65535, 65535, 0, 65535));
+        pf.blend_pixel(0, 0, &Rgba16::new(32768, 0, 65535, 65535), 255);
+        let p = pf.pixel(0, 0);
+        assert_eq!((p.r, p.g, p.b, p.a), (32768, 0, 0, 65535));
+    }
+
+    #[test]
+    fn test_rgb48_comp_op_src_over() {
+        let (_buf, mut ra) = make_buffer_bpp(1, 1, 6);
+        let mut pf = PixfmtBgr48CompOp::new(&mut ra);
+        pf.copy_pixel(0, 0, &Rgba16::new(0, 0, 0, 65535));
+        pf.blend_pixel(0, 0, &Rgba16::new(65535, 0, 0, 65535), 128);
+        let p = pf.pixel(0, 0);
+        assert_eq!((p.g, p.b, p.a), (0, 0, 65535));
+        assert!((p.r as i32 - 32896).abs() <= 1, "r={}", p.r);
+    }
 }
@@ -0,0 +1,162 @@
+//! Convenience polygon clipper.
+//!
+//! Port of `agg_conv_clip_polygon.h`.
+//! Wraps `ConvAdaptorVpgen` with `VpgenClipPolygon` for clipping polygons
+//! to a rectangle at the vertex level, e.g. before stroking or exporting.
+
+use crate::basics::VertexSource;
+use crate::conv_adaptor_vpgen::ConvAdaptorVpgen;
+use crate::vpgen_clip_polygon::VpgenClipPolygon;
+
+/// Clips the polygons of a vertex source to a rectangle.
+///
+/// Port of C++ `conv_clip_polygon<VertexSource>`.
+/// Thin wrapper around `ConvAdaptorVpgen<VS, VpgenClipPolygon>`.
+/// Every contour comes out closed.
+pub struct ConvClipPolygon<VS> {
+    inner: ConvAdaptorVpgen<VS, VpgenClipPolygon>,
+}
+
+impl<VS: VertexSource> ConvClipPolygon<VS> {
+    pub fn new(source: VS) -> Self {
+        Self {
+            inner: ConvAdaptorVpgen::new(source, VpgenClipPolygon::new()),
+        }
+    }
+
+    pub fn clip_box(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        self.inner.vpgen_mut().clip_box(x1, y1, x2, y2);
+    }
+
+    pub fn x1(&self) -> f64 {
+        self.inner.vpgen().x1()
+    }
+
+    pub fn y1(&self) -> f64 {
+        self.inner.vpgen().y1()
+    }
+
+    pub fn x2(&self) -> f64 {
+        self.inner.vpgen().x2()
+    }
+
+    pub fn y2(&self) -> f64 {
+        self.inner.vpgen().y2()
+    }
+
+    pub fn source(&self) -> &VS {
+        self.inner.source()
+    }
+
+    pub fn source_mut(&mut self) -> &mut VS {
+        self.inner.source_mut()
+    }
+}
+
+impl<VS: VertexSource> VertexSource for ConvClipPolygon<VS> {
+    fn rewind(&mut self, path_id: u32) {
+        self.inner.rewind(path_id);
+    }
+
+    fn vertex(&mut self, x: &mut f64, y: &mut f64) -> u32 {
+        self.inner.vertex(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::basics::{
+        is_end_poly, is_move_to, is_stop, is_vertex, PATH_CMD_END_POLY, PATH_FLAGS_CLOSE,
+        PATH_FLAGS_NONE,
+    };
+    use crate::path_storage::PathStorage;
+
+    fn collect(vs: &mut impl VertexSource) -> Vec<(u32, f64, f64)> {
+        let mut out = Vec::new();
+        let (mut x, mut y) = (0.0, 0.0);
+        vs.rewind(0);
+        loop {
+            let cmd = vs.vertex(&mut x, &mut y);
+            if is_stop(cmd) {
+                return out;
+            }
+            out.push((cmd, x, y));
+        }
+    }
+
+    fn signed_area(vertices: &[(u32, f64, f64)]) -> f64 {
+        let pts: Vec<(f64, f64)> = vertices
+            .iter()
+            .filter(|v| is_vertex(v.0))
+            .map(|v| (v.1, v.2))
+            .collect();
+        let n = pts.len();
+        (0..n)
+            .map(|i| pts[i].0 * pts[(i + 1) % n].1 - pts[(i + 1) % n].0 * pts[i].1)
+            .sum::<f64>()
+            / 2.0
+    }
+
+    #[test]
+    fn test_clip_box_accessors() {
+        let mut clip = ConvClipPolygon::new(PathStorage::new());
+        clip.clip_box(10.0, 20.0, 30.0, 40.0);
+        assert_eq!(
+            (clip.x1(), clip.y1(), clip.x2(), clip.y2()),
+            (10.0, 20.0, 30.0, 40.0)
+        );
+    }
+
+    #[test]
+    fn test_triangle_clipped_to_box() {
+        // Right triangle with legs of 200 clipped to [0, 100]^2: the
+        // hypotenuse x + y = 200 misses the box, leaving the whole square.
+        let mut path = PathStorage::new();
+        path.move_to(0.0, 0.0);
+        path.line_to(200.0, 0.0);
+        path.line_to(0.0, 200.0);
+        path.close_polygon(PATH_FLAGS_NONE);
+        let mut clip = ConvClipPolygon::new(path);
+        clip.clip_box(0.0, 0.0, 100.0, 100.0);
+        let out = collect(&mut clip);
+
+        assert!(is_move_to(out[0].0));
+        assert_eq!(out.last().unwrap().0, PATH_CMD_END_POLY | PATH_FLAGS_CLOSE);
+        for &(cmd, x, y) in &out {
+            if is_vertex(cmd) {
+                assert!((0.0..=100.0).contains(&x) && (0.0..=100.0).contains(&y));
+            }
+        }
+        assert!((signed_area(&out) - 10000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_open_contour_closed() {
+        // A triangle crossing the box edge, without close_polygon.
+        let mut path = PathStorage::new();
+        path.move_to(50.0, 50.0);
+        path.line_to(150.0, 50.0);
+        path.line_to(50.0, 90.0);
+        let mut clip = ConvClipPolygon::new(path);
+        clip.clip_box(0.0, 0.0, 100.0, 100.0);
+        let out = collect(&mut clip);
+
+        assert_eq!(out.iter().filter(|v| is_end_poly(v.0)).count(), 1);
+        // Trapezoid between x = 50 and x = 100: heights 40 and 20.
+        assert!((signed_area(&out) - 1500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polygon_outside_collapses() {
+        let mut path = PathStorage::new();
+        path.move_to(200.0, 200.0);
+        path.line_to(300.0, 200.0);
+        path.line_to(300.0, 300.0);
+        path.close_polygon(PATH_FLAGS_NONE);
+        let mut clip = ConvClipPolygon::new(path);
+        clip.clip_box(0.0, 0.0, 100.0, 100.0);
+        let out = collect(&mut clip);
+        assert!(signed_area(&out).abs() < 1e-9);
+    }
+}
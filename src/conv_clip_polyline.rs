@@ -0,0 +1,134 @@
+//! Convenience polyline clipper.
+//!
+//! Port of `agg_conv_clip_polyline.h`.
+//! Wraps `ConvAdaptorVpgen` with `VpgenClipPolyline` for clipping open
+//! paths to a rectangle at the vertex level, e.g. before stroking.
+
+use crate::basics::VertexSource;
+use crate::conv_adaptor_vpgen::ConvAdaptorVpgen;
+use crate::vpgen_clip_polyline::VpgenClipPolyline;
+
+/// Clips the polylines of a vertex source to a rectangle.
+///
+/// Port of C++ `conv_clip_polyline<VertexSource>`.
+/// Thin wrapper around `ConvAdaptorVpgen<VS, VpgenClipPolyline>`.
+/// Each visible piece comes out as an open sub-path.
+pub struct ConvClipPolyline<VS> {
+    inner: ConvAdaptorVpgen<VS, VpgenClipPolyline>,
+}
+
+impl<VS: VertexSource> ConvClipPolyline<VS> {
+    pub fn new(source: VS) -> Self {
+        Self {
+            inner: ConvAdaptorVpgen::new(source, VpgenClipPolyline::new()),
+        }
+    }
+
+    pub fn clip_box(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        self.inner.vpgen_mut().clip_box(x1, y1, x2, y2);
+    }
+
+    pub fn x1(&self) -> f64 {
+        self.inner.vpgen().x1()
+    }
+
+    pub fn y1(&self) -> f64 {
+        self.inner.vpgen().y1()
+    }
+
+    pub fn x2(&self) -> f64 {
+        self.inner.vpgen().x2()
+    }
+
+    pub fn y2(&self) -> f64 {
+        self.inner.vpgen().y2()
+    }
+
+    pub fn source(&self) -> &VS {
+        self.inner.source()
+    }
+
+    pub fn source_mut(&mut self) -> &mut VS {
+        self.inner.source_mut()
+    }
+}
+
+impl<VS: VertexSource> VertexSource for ConvClipPolyline<VS> {
+    fn rewind(&mut self, path_id: u32) {
+        self.inner.rewind(path_id);
+    }
+
+    fn vertex(&mut self, x: &mut f64, y: &mut f64) -> u32 {
+        self.inner.vertex(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::basics::{is_stop, PATH_CMD_LINE_TO, PATH_CMD_MOVE_TO, PATH_FLAGS_NONE};
+    use crate::path_storage::PathStorage;
+
+    fn collect(vs: &mut impl VertexSource) -> Vec<(u32, f64, f64)> {
+        let mut out = Vec::new();
+        let (mut x, mut y) = (0.0, 0.0);
+        vs.rewind(0);
+        loop {
+            let cmd = vs.vertex(&mut x, &mut y);
+            if is_stop(cmd) {
+                return out;
+            }
+            out.push((cmd, x, y));
+        }
+    }
+
+    #[test]
+    fn test_zigzag_split_into_pieces() {
+        let mut path = PathStorage::new();
+        path.move_to(10.0, 50.0);
+        path.line_to(150.0, 50.0);
+        path.line_to(150.0, 60.0);
+        path.line_to(10.0, 60.0);
+        let mut clip = ConvClipPolyline::new(path);
+        clip.clip_box(0.0, 0.0, 100.0, 100.0);
+        assert_eq!(
+            collect(&mut clip),
+            vec![
+                (PATH_CMD_MOVE_TO, 10.0, 50.0),
+                (PATH_CMD_LINE_TO, 100.0, 50.0),
+                (PATH_CMD_MOVE_TO, 100.0, 60.0),
+                (PATH_CMD_LINE_TO, 10.0, 60.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_closed_path_comes_out_open() {
+        let mut path = PathStorage::new();
+        path.move_to(10.0, 10.0);
+        path.line_to(50.0, 10.0);
+        path.line_to(50.0, 50.0);
+        path.close_polygon(PATH_FLAGS_NONE);
+        let mut clip = ConvClipPolyline::new(path);
+        clip.clip_box(0.0, 0.0, 100.0, 100.0);
+        assert_eq!(
+            collect(&mut clip),
+            vec![
+                (PATH_CMD_MOVE_TO, 10.0, 10.0),
+                (PATH_CMD_LINE_TO, 50.0, 10.0),
+                (PATH_CMD_LINE_TO, 50.0, 50.0),
+                (PATH_CMD_LINE_TO, 10.0, 10.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_path_outside_is_dropped() {
+        let mut path = PathStorage::new();
+        path.move_to(-50.0, -50.0);
+        path.line_to(-10.0, -20.0);
+        let mut clip = ConvClipPolyline::new(path);
+        clip.clip_box(0.0, 0.0, 100.0, 100.0);
+        assert!(collect(&mut clip).is_empty());
+    }
+}
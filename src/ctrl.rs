@@ -21,6 +21,7 @@ use crate::math_stroke::{LineCap, LineJoin};
 use crate::path_storage::PathStorage;
 use crate::pixfmt_rgba::PixfmtRgba32;
 use crate::rasterizer_scanline_aa::RasterizerScanlineAa;
+use crate::rasterizer_sl_clip::RasterizerClip;
 use crate::renderer_base::RendererBase;
 use crate::renderer_scanline::render_scanlines_aa_solid;
 use crate::scanline_u::ScanlineU8;
@@ -32,8 +33,8 @@ use crate::scanline_u::ScanlineU8;
 /// Render a control by iterating its paths and rendering each with its color.
 ///
 /// Port of C++ `render_ctrl()` template function.
-pub fn render_ctrl(
-    ras: &mut RasterizerScanlineAa,
+pub fn render_ctrl<CL: RasterizerClip>(
+    ras: &mut RasterizerScanlineAa<CL>,
     sl: &mut ScanlineU8,
     ren: &mut RendererBase<PixfmtRgba32>,
     ctrl: &mut dyn Ctrl,
@@ -10,6 +10,13 @@
 //!
 //! The facade types `Curve3` and `Curve4` delegate to either algorithm.
 //!
+//! Unlike C++ AGG, a curve is flattened into at most 65536 points, and the
+//! subdivision curves stop subdividing when their flatness test overflows
+//! (non-finite or enormous control points) rather than recursing to the
+//! depth limit. Branches that do reach the depth limit emit their end
+//! point, so the point limit also bounds the work. Any non-finite points
+//! they emit are dropped further down the pipeline.
+//!
 //! Also provides conversion functions: `catrom_to_bezier`,
 //! `ubspline_to_bezier`, `hermite_to_bezier`.
 
@@ -23,6 +30,9 @@ use crate::math::calc_sq_distance;
 const CURVE_COLLINEARITY_EPSILON: f64 = 1e-30;
 const CURVE_ANGLE_TOLERANCE_EPSILON: f64 = 0.01;
 const CURVE_RECURSION_LIMIT: u32 = 32;
+/// Most points a single curve is flattened into. Only curves far larger
+/// than any raster reach it; without it they can take ~2^32 points.
+const CURVE_POINT_LIMIT: usize = 1 << 16;
 
 // ============================================================================
 // Curve approximation method
@@ -222,7 +232,8 @@ impl Curve3Inc {
 
         let len = (dx1 * dx1 + dy1 * dy1).sqrt() + (dx2 * dx2 + dy2 * dy2).sqrt();
 
-        self.num_steps = crate::basics::uround(len * 0.25 * self.scale) as i32;
+        self.num_steps =
+            crate::basics::uround(len * 0.25 * self.scale).min(CURVE_POINT_LIMIT as u32) as i32;
 
         if self.num_steps < 4 {
             self.num_steps = 4;
@@ -383,7 +394,13 @@ impl Curve3Div {
         y3: f64,
         level: u32,
     ) {
+        if self.points.len() >= CURVE_POINT_LIMIT {
+            return;
+        }
         if level > CURVE_RECURSION_LIMIT {
+            // Only huge curves get this deep. Emitting the end point keeps
+            // them under the point limit instead of recursing ~2^32 times.
+            self.points.push(PointD { x: x3, y: y3 });
             return;
         }
 
@@ -399,6 +416,13 @@ impl Curve3Div {
         let dy = y3 - y1;
         let d = ((x2 - x3) * dy - (y2 - y3) * dx).abs();
 
+        if !d.is_finite() {
+            // Non-finite or overflowing coordinates: the flatness tests can
+            // never pass, so stop here instead of recursing to the limit.
+            self.points.push(PointD { x: x123, y: y123 });
+            return;
+        }
+
         if d > CURVE_COLLINEARITY_EPSILON {
             // Regular case
             if d * d <= self.distance_tolerance_square * (dx * dx + dy * dy) {
@@ -581,7 +605,7 @@ impl Curve4Inc {
             * 0.25
             * self.scale;
 
-        self.num_steps = crate::basics::uround(len) as i32;
+        self.num_steps = crate::basics::uround(len).min(CURVE_POINT_LIMIT as u32) as i32;
 
         if self.num_steps < 4 {
             self.num_steps = 4;
@@ -804,7 +828,13 @@ impl Curve4Div {
         y4: f64,
         level: u32,
     ) {
+        if self.points.len() >= CURVE_POINT_LIMIT {
+            return;
+        }
         if level > CURVE_RECURSION_LIMIT {
+            // Only huge curves get this deep. Emitting the end point keeps
+            // them under the point limit instead of recursing ~2^32 times.
+            self.points.push(PointD { x: x4, y: y4 });
             return;
         }
 
@@ -829,6 +859,13 @@ impl Curve4Div {
         let mut d2 = ((x2 - x4) * dy - (y2 - y4) * dx).abs();
         let mut d3 = ((x3 - x4) * dy - (y3 - y4) * dx).abs();
 
+        if !(d2 + d3).is_finite() {
+            // Non-finite or overflowing coordinates: the flatness tests can
+            // never pass, so stop here instead of recursing to the limit.
+            self.points.push(PointD { x: x1234, y: y1234 });
+            return;
+        }
+
         let case = ((d2 > CURVE_COLLINEARITY_EPSILON) as u32) << 1
             | (d3 > CURVE_COLLINEARITY_EPSILON) as u32;
 
@@ -1563,4 +1600,30 @@ mod tests {
         assert!((ld.0 - 90.0).abs() < 1e-6);
         assert!((ld.1 - 20.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_div_non_finite_points_terminate() {
+        // Without the overflow guard these recurse to the depth limit,
+        // emitting ~2^32 points.
+        let mut c3 = Curve3Div::new_with_points(0.0, 0.0, f64::NAN, 5.0, 10.0, 0.0);
+        assert!(collect_vertices(&mut c3).len() <= 3);
+
+        let mut c4 =
+            Curve4Div::new_with_points(0.0, 0.0, 1e300, -1e300, f64::INFINITY, 0.0, 10.0, 0.0);
+        let v = collect_vertices(&mut c4);
+        assert!(v.len() <= 3);
+        assert_eq!((v[0].0, v[0].1), (0.0, 0.0));
+        assert_eq!((v[v.len() - 1].0, v[v.len() - 1].1), (10.0, 0.0));
+    }
+
+    #[test]
+    fn test_huge_curves_bounded() {
+        let mut div = Curve3Div::new_with_points(0.0, 0.0, 1e100, 1e100, 10.0, 0.0);
+        let v = collect_vertices(&mut div);
+        assert!(v.len() <= CURVE_POINT_LIMIT + 2);
+        assert_eq!((v[v.len() - 1].0, v[v.len() - 1].1), (10.0, 0.0));
+
+        let mut inc = Curve4Inc::new_with_points(0.0, 0.0, 1e15, 0.0, 0.0, 1e15, 10.0, 0.0);
+        assert!(collect_vertices(&mut inc).len() <= CURVE_POINT_LIMIT + 1);
+    }
 }
@@ -31,6 +31,18 @@ impl GammaFunction for GammaNone {
     }
 }
 
+/// Build a 256-entry coverage table from a gamma function.
+///
+/// Port of the table fill in C++ `rasterizer_scanline_aa::gamma()`:
+/// `table[i] = uround(f(i / 255) * 255)`, clamped to the cover range.
+pub(crate) fn coverage_gamma_table<G: GammaFunction + ?Sized>(gamma_function: &G) -> [u8; 256] {
+    let mut table = [0u8; 256];
+    for (i, v) in table.iter_mut().enumerate() {
+        *v = uround(gamma_function.call(i as f64 / 255.0) * 255.0).min(255) as u8;
+    }
+    table
+}
+
 // ============================================================================
 // Gamma power
 // ============================================================================
@@ -239,6 +251,71 @@ pub fn linear_to_srgb(x: f64) -> f64 {
     }
 }
 
+// ============================================================================
+// sRGB LUT
+// ============================================================================
+
+/// Lookup tables converting 8-bit sRGB values to 16-bit linear light and
+/// back. Port of C++ `sRGB_lut<int16u>`.
+///
+/// `inv` is an exact inverse: it returns the sRGB value whose
+/// linear-light interval contains `v`, found by a binary search over the
+/// interval midpoints.
+pub struct SrgbLut {
+    dir_table: [u16; 256],
+    inv_table: [u16; 256],
+}
+
+impl SrgbLut {
+    pub fn new() -> Self {
+        let mut dir_table = [0u16; 256];
+        let mut inv_table = [0u16; 256];
+        for i in 1..256 {
+            dir_table[i] = uround(65535.0 * srgb_to_linear(i as f64 / 255.0)) as u16;
+            inv_table[i] = uround(65535.0 * srgb_to_linear((i as f64 - 0.5) / 255.0)) as u16;
+        }
+        Self {
+            dir_table,
+            inv_table,
+        }
+    }
+
+    /// Shared instance, built on first use (the C++ static
+    /// `sRGB_conv_base::lut`).
+    pub fn get() -> &'static SrgbLut {
+        static LUT: std::sync::OnceLock<SrgbLut> = std::sync::OnceLock::new();
+        LUT.get_or_init(SrgbLut::new)
+    }
+
+    /// sRGB byte to 16-bit linear.
+    #[inline]
+    pub fn dir(&self, v: u8) -> u16 {
+        self.dir_table[v as usize]
+    }
+
+    /// 16-bit linear to sRGB byte.
+    #[inline]
+    pub fn inv(&self, v: u16) -> u8 {
+        let t = &self.inv_table;
+        let mut x = 0usize;
+        if v > t[128] {
+            x = 128;
+        }
+        for step in [64, 32, 16, 8, 4, 2, 1] {
+            if v > t[x + step] {
+                x += step;
+            }
+        }
+        x as u8
+    }
+}
+
+impl Default for SrgbLut {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ============================================================================
 // Gamma LUT (Lookup Table)
 // ============================================================================
@@ -556,6 +633,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_srgb_lut_roundtrip() {
+        let lut = SrgbLut::get();
+        for v in 0..=255u8 {
+            assert_eq!(lut.inv(lut.dir(v)), v);
+        }
+        assert_eq!(lut.dir(0), 0);
+        assert_eq!(lut.dir(255), 65535);
+        // Linear mid-grey is sRGB 188, not 128.
+        assert_eq!(lut.inv(32768), 188);
+    }
+
     #[test]
     fn test_srgb_endpoints() {
         assert!((srgb_to_linear(0.0)).abs() < EPSILON);
@@ -4,8 +4,9 @@
 //! color stops. Used by `SpanGradient` to map gradient distances to colors.
 
 use crate::basics::uround;
-use crate::color::Rgba8;
+use crate::color::{Rgba32, Rgba8, RgbaIntColor};
 use crate::dda_line::DdaLineInterpolator;
+use std::marker::PhantomData;
 
 // ============================================================================
 // ColorFunction trait
@@ -50,34 +51,39 @@ impl<C: Clone> ColorInterpolatorGeneric<C> {
     }
 }
 
-impl ColorInterpolatorGeneric<Rgba8> {
-    fn color(&self) -> Rgba8 {
+impl<C: GradientColor> ColorInterpolatorGeneric<C> {
+    fn color(&self) -> C {
         self.c1
             .gradient(&self.c2, self.count as f64 / self.len as f64)
     }
 }
 
 // ============================================================================
-// ColorInterpolatorRgba8 — fast DDA specialization for Rgba8
+// ColorInterpolatorRgba — fast DDA specialization for integer RGBA
 // ============================================================================
 
-/// Fast RGBA8 color interpolator using 14-bit DDA interpolation.
+/// Fast integer RGBA color interpolator using 14-bit DDA interpolation.
 ///
-/// Port of C++ `color_interpolator<rgba8>` specialization.
-struct ColorInterpolatorRgba8 {
+/// Port of C++ `color_interpolator<rgba8>` specialization, applied to any
+/// [`RgbaIntColor`] (the 14-bit fraction leaves room for 16-bit channels).
+struct ColorInterpolatorRgba<C> {
     r: DdaLineInterpolator<14, 0>,
     g: DdaLineInterpolator<14, 0>,
     b: DdaLineInterpolator<14, 0>,
     a: DdaLineInterpolator<14, 0>,
+    color: PhantomData<C>,
 }
 
-impl ColorInterpolatorRgba8 {
-    fn new(c1: &Rgba8, c2: &Rgba8, len: u32) -> Self {
+impl<C: RgbaIntColor> ColorInterpolatorRgba<C> {
+    fn new(c1: &C, c2: &C, len: u32) -> Self {
+        let [r1, g1, b1, a1] = c1.channels();
+        let [r2, g2, b2, a2] = c2.channels();
         Self {
-            r: DdaLineInterpolator::new(c1.r as i32, c2.r as i32, len),
-            g: DdaLineInterpolator::new(c1.g as i32, c2.g as i32, len),
-            b: DdaLineInterpolator::new(c1.b as i32, c2.b as i32, len),
-            a: DdaLineInterpolator::new(c1.a as i32, c2.a as i32, len),
+            r: DdaLineInterpolator::new(r1 as i32, r2 as i32, len),
+            g: DdaLineInterpolator::new(g1 as i32, g2 as i32, len),
+            b: DdaLineInterpolator::new(b1 as i32, b2 as i32, len),
+            a: DdaLineInterpolator::new(a1 as i32, a2 as i32, len),
+            color: PhantomData,
         }
     }
 
@@ -88,8 +94,8 @@ impl ColorInterpolatorRgba8 {
         self.a.inc();
     }
 
-    fn color(&self) -> Rgba8 {
-        Rgba8::new(
+    fn color(&self) -> C {
+        C::from_channels(
             self.r.y() as u32,
             self.g.y() as u32,
             self.b.y() as u32,
@@ -98,19 +104,68 @@ impl ColorInterpolatorRgba8 {
     }
 }
 
+// ============================================================================
+// GradientColor — color types a gradient can be built from
+// ============================================================================
+
+/// Color types that [`GradientLut`] and [`GradientLinearColor`] can
+/// interpolate.
+///
+/// Stands in for the C++ `color_interpolator<ColorT>` template argument:
+/// every integer RGBA color gets the fast DDA interpolator, while
+/// [`Rgba32`] interpolates in floating point and never quantizes.
+pub trait GradientColor: Copy + Default {
+    /// Interpolate between `self` and `c` by parameter `k` (0.0 to 1.0).
+    fn gradient(&self, c: &Self, k: f64) -> Self;
+
+    /// Fill `out` with the ramp from `c1` toward `c2` that reaches `c2`
+    /// after `len` steps, using the type's fast interpolator.
+    ///
+    /// The default evaluates [`GradientColor::gradient`] per entry.
+    fn fill_fast(c1: &Self, c2: &Self, len: u32, out: &mut [Self]) {
+        let mut ci = ColorInterpolatorGeneric::new(c1, c2, len);
+        for c in out.iter_mut() {
+            *c = ci.color();
+            ci.inc();
+        }
+    }
+}
+
+impl<C: RgbaIntColor> GradientColor for C {
+    #[inline]
+    fn gradient(&self, c: &Self, k: f64) -> Self {
+        RgbaIntColor::gradient(self, c, k)
+    }
+
+    fn fill_fast(c1: &Self, c2: &Self, len: u32, out: &mut [Self]) {
+        let mut ci = ColorInterpolatorRgba::new(c1, c2, len);
+        for c in out.iter_mut() {
+            *c = ci.color();
+            ci.inc();
+        }
+    }
+}
+
+impl GradientColor for Rgba32 {
+    #[inline]
+    fn gradient(&self, c: &Self, k: f64) -> Self {
+        Rgba32::gradient(self, c, k)
+    }
+}
+
 // ============================================================================
 // GradientLut
 // ============================================================================
 
 /// Color stop for gradient definition.
 #[derive(Clone)]
-struct ColorPoint {
+struct ColorPoint<C> {
     offset: f64,
-    color: Rgba8,
+    color: C,
 }
 
-impl ColorPoint {
-    fn new(offset: f64, color: Rgba8) -> Self {
+impl<C> ColorPoint<C> {
+    fn new(offset: f64, color: C) -> Self {
         Self {
             offset: offset.clamp(0.0, 1.0),
             color,
@@ -123,10 +178,14 @@ impl ColorPoint {
 /// Builds a 256-entry (or custom size) color LUT from SVG-style color stops.
 /// Supports arbitrary numbers of stops at positions [0..1].
 ///
+/// The color type defaults to [`Rgba8`]; use `GradientLut<Rgba16>` to feed
+/// 16-bit pixel formats or `GradientLut<Rgba32>` for floating-point ones,
+/// built with `new_for` / `new_default_for`.
+///
 /// Port of C++ `gradient_lut<ColorInterpolator, ColorLutSize>`.
-pub struct GradientLut {
-    color_profile: Vec<ColorPoint>,
-    color_lut: Vec<Rgba8>,
+pub struct GradientLut<C = Rgba8> {
+    color_profile: Vec<ColorPoint<C>>,
+    color_lut: Vec<C>,
     lut_size: usize,
     use_fast_interpolator: bool,
 }
@@ -134,17 +193,29 @@ pub struct GradientLut {
 impl GradientLut {
     /// Create a new gradient LUT with the specified size (default 256).
     pub fn new(lut_size: usize) -> Self {
+        Self::new_for(lut_size)
+    }
+
+    /// Create a new gradient LUT with default size of 256.
+    pub fn new_default() -> Self {
+        Self::new_default_for()
+    }
+}
+
+impl<C: GradientColor> GradientLut<C> {
+    /// Like `new`, for a LUT of colors of type `C`.
+    pub fn new_for(lut_size: usize) -> Self {
         Self {
             color_profile: Vec::new(),
-            color_lut: vec![Rgba8::default(); lut_size],
+            color_lut: vec![C::default(); lut_size],
             lut_size,
             use_fast_interpolator: true,
         }
     }
 
-    /// Create a new gradient LUT with default size of 256.
-    pub fn new_default() -> Self {
-        Self::new(256)
+    /// Like `new_default`, for a LUT of colors of type `C`.
+    pub fn new_default_for() -> Self {
+        Self::new_for(256)
     }
 
     /// Set whether to use the fast DDA interpolator (default: true).
@@ -158,7 +229,7 @@ impl GradientLut {
     }
 
     /// Add a color stop at the given offset (clamped to [0..1]).
-    pub fn add_color(&mut self, offset: f64, color: Rgba8) {
+    pub fn add_color(&mut self, offset: f64, color: C) {
         self.color_profile.push(ColorPoint::new(offset, color));
     }
 
@@ -201,15 +272,15 @@ impl GradientLut {
             let seg_len = if end > start { (end - start - 1).max(1) } else { 1 };
 
             if self.use_fast_interpolator {
-                let mut ci = ColorInterpolatorRgba8::new(
-                    &self.color_profile[i - 1].color,
-                    &self.color_profile[i].color,
-                    seg_len as u32,
-                );
-                while start < end && start < size {
-                    self.color_lut[start] = ci.color();
-                    ci.inc();
-                    start += 1;
+                let stop = end.min(size);
+                if start < stop {
+                    C::fill_fast(
+                        &self.color_profile[i - 1].color,
+                        &self.color_profile[i].color,
+                        seg_len as u32,
+                        &mut self.color_lut[start..stop],
+                    );
+                    start = stop;
                 }
             } else {
                 let mut ci = ColorInterpolatorGeneric::new(
@@ -235,15 +306,15 @@ impl GradientLut {
     }
 }
 
-impl ColorFunction for GradientLut {
-    type Color = Rgba8;
+impl<C: GradientColor> ColorFunction for GradientLut<C> {
+    type Color = C;
 
     fn size(&self) -> usize {
         self.lut_size
     }
 
     #[inline]
-    fn get(&self, index: usize) -> Rgba8 {
+    fn get(&self, index: usize) -> C {
         self.color_lut[index]
     }
 }
@@ -257,31 +328,31 @@ impl ColorFunction for GradientLut {
 /// Interpolates between two colors based on index/size ratio.
 ///
 /// Port of C++ `gradient_linear_color<ColorT>`.
-pub struct GradientLinearColor {
-    c1: Rgba8,
-    c2: Rgba8,
+pub struct GradientLinearColor<C = Rgba8> {
+    c1: C,
+    c2: C,
     size: usize,
 }
 
-impl GradientLinearColor {
-    pub fn new(c1: Rgba8, c2: Rgba8, size: usize) -> Self {
+impl<C: GradientColor> GradientLinearColor<C> {
+    pub fn new(c1: C, c2: C, size: usize) -> Self {
         Self { c1, c2, size }
     }
 
-    pub fn colors(&mut self, c1: Rgba8, c2: Rgba8) {
+    pub fn colors(&mut self, c1: C, c2: C) {
         self.c1 = c1;
         self.c2 = c2;
     }
 }
 
-impl ColorFunction for GradientLinearColor {
-    type Color = Rgba8;
+impl<C: GradientColor> ColorFunction for GradientLinearColor<C> {
+    type Color = C;
 
     fn size(&self) -> usize {
         self.size
     }
 
-    fn get(&self, index: usize) -> Rgba8 {
+    fn get(&self, index: usize) -> C {
         self.c1
             .gradient(&self.c2, index as f64 / (self.size - 1).max(1) as f64)
     }
@@ -459,7 +530,7 @@ mod tests {
     fn test_color_interpolator_rgba8_fast() {
         let c1 = Rgba8::new(0, 0, 0, 255);
         let c2 = Rgba8::new(255, 255, 255, 255);
-        let mut ci = ColorInterpolatorRgba8::new(&c1, &c2, 10);
+        let mut ci = ColorInterpolatorRgba::new(&c1, &c2, 10);
 
         let first = ci.color();
         assert_eq!(first.r, 0);
@@ -484,4 +555,15 @@ mod tests {
         assert_eq!(gc.get(255).r, 0);
         assert_eq!(gc.get(255).g, 255);
     }
+
+    #[test]
+    fn test_gradient_lut_float_is_unquantized() {
+        let mut lut: GradientLut<Rgba32> = GradientLut::new_for(5);
+        lut.add_color(0.0, Rgba32::new(0.0, 0.0, 0.0, 1.0));
+        lut.add_color(1.0, Rgba32::new(10.0, 1.0, 0.0, 1.0));
+        lut.build_lut();
+        assert_eq!(lut.get(0).r, 0.0);
+        assert_eq!(lut.get(2).r, 5.0);
+        assert_eq!(lut.get(4), Rgba32::new(10.0, 1.0, 0.0, 1.0));
+    }
 }
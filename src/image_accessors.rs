@@ -382,7 +382,7 @@ impl WrapMode for WrapModeReflectAutoPow2 {
 /// Port of C++ `image_accessor_clip<PixFmt>`.
 pub struct ImageAccessorClip<'a, const PIX_WIDTH: usize> {
     rbuf: &'a RowAccessor,
-    bk_buf: [u8; 8], // background color buffer (max 8 bytes per pixel)
+    bk_buf: [u8; 16], // background color buffer (max 16 bytes per pixel)
     x: i32,
     x0: i32,
     y: i32,
@@ -392,8 +392,8 @@ pub struct ImageAccessorClip<'a, const PIX_WIDTH: usize> {
 
 impl<'a, const PIX_WIDTH: usize> ImageAccessorClip<'a, PIX_WIDTH> {
     pub fn new(rbuf: &'a RowAccessor, bk_color: &[u8]) -> Self {
-        let mut bk_buf = [0u8; 8];
-        let len = bk_color.len().min(8);
+        let mut bk_buf = [0u8; 16];
+        let len = bk_color.len().min(16);
         bk_buf[..len].copy_from_slice(&bk_color[..len]);
         Self {
             rbuf,
@@ -407,7 +407,7 @@ impl<'a, const PIX_WIDTH: usize> ImageAccessorClip<'a, PIX_WIDTH> {
     }
 
     pub fn set_background(&mut self, bk_color: &[u8]) {
-        let len = bk_color.len().min(8);
+        let len = bk_color.len().min(16);
         self.bk_buf[..len].copy_from_slice(&bk_color[..len]);
     }
 
@@ -0,0 +1,429 @@
+//! Safe owned and borrowed image buffers.
+//!
+//! Not part of AGG. [`ImageBuffer`] owns its pixels and [`ImageView`] borrows
+//! a caller's slice. An [`ImageView`] is a rendering buffer whose lifetime is
+//! tied to the borrow, so rendering needs no `unsafe`:
+//!
+//! ```
+//! use agg_rust::color::Rgba8;
+//! use agg_rust::image_buffer::{ImageBuffer, PixelFormatTag};
+//! use agg_rust::pixfmt_rgba::{PixelFormat, PixfmtRgba32};
+//!
+//! let mut img = ImageBuffer::new(64, 64, PixelFormatTag::Rgba32);
+//! {
+//!     let mut view = img.view();
+//!     let mut pf = PixfmtRgba32::new(&mut view);
+//!     pf.clear(&Rgba8::new(255, 255, 255, 255));
+//! }
+//! assert_eq!(&img.data()[..4], &[255, 255, 255, 255]);
+//! ```
+//!
+//! Each buffer carries a [`PixelFormatTag`] that fixes its bytes per pixel,
+//! and can be narrowed to a sub-rectangle view.
+
+use std::marker::PhantomData;
+
+use crate::basics::RectI;
+use crate::rendering_buffer::{RenderingBufferAccess, RenderingBufferMut, RowAccessor};
+
+// ============================================================================
+// PixelFormatTag
+// ============================================================================
+
+/// Memory layout of the pixels in an image buffer.
+///
+/// Names match the pixel format type aliases (`Rgba32` for `PixfmtRgba32`
+/// and its `Pre`/`CompOp`/sRGB variants, and so on).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelFormatTag {
+    Gray8,
+    Gray16,
+    Rgb565,
+    Rgb555,
+    Rgb444,
+    Rgb24,
+    Bgr24,
+    Rgba32,
+    Bgra32,
+    Argb32,
+    Abgr32,
+    Rgb48,
+    Bgr48,
+    Rgba64,
+    Rgba128,
+}
+
+impl PixelFormatTag {
+    /// Bytes occupied by one pixel.
+    pub const fn bytes_per_pixel(self) -> u32 {
+        match self {
+            PixelFormatTag::Gray8 => 1,
+            PixelFormatTag::Gray16
+            | PixelFormatTag::Rgb565
+            | PixelFormatTag::Rgb555
+            | PixelFormatTag::Rgb444 => 2,
+            PixelFormatTag::Rgb24 | PixelFormatTag::Bgr24 => 3,
+            PixelFormatTag::Rgba32
+            | PixelFormatTag::Bgra32
+            | PixelFormatTag::Argb32
+            | PixelFormatTag::Abgr32 => 4,
+            PixelFormatTag::Rgb48 | PixelFormatTag::Bgr48 => 6,
+            PixelFormatTag::Rgba64 => 8,
+            PixelFormatTag::Rgba128 => 16,
+        }
+    }
+}
+
+// ============================================================================
+// ImageBuffer — owned pixels
+// ============================================================================
+
+/// Owned, tightly packed, top-down image.
+pub struct ImageBuffer {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    format: PixelFormatTag,
+}
+
+impl ImageBuffer {
+    /// Create a zero-filled image.
+    pub fn new(width: u32, height: u32, format: PixelFormatTag) -> Self {
+        let len = width as usize * height as usize * format.bytes_per_pixel() as usize;
+        Self {
+            data: vec![0; len],
+            width,
+            height,
+            format,
+        }
+    }
+
+    /// Wrap existing pixel data, laid out top-down with no row padding.
+    ///
+    /// # Panics
+    /// If `data` is shorter than `width * height * bytes_per_pixel`.
+    pub fn from_vec(data: Vec<u8>, width: u32, height: u32, format: PixelFormatTag) -> Self {
+        let len = width as usize * height as usize * format.bytes_per_pixel() as usize;
+        assert!(
+            data.len() >= len,
+            "image data too short: {} bytes, need {len}",
+            data.len()
+        );
+        Self {
+            data,
+            width,
+            height,
+            format,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn format(&self) -> PixelFormatTag {
+        self.format
+    }
+
+    /// Bytes per row.
+    pub fn stride(&self) -> u32 {
+        self.width * self.format.bytes_per_pixel()
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// A view of the whole image.
+    pub fn view(&mut self) -> ImageView<'_> {
+        let stride = self.stride() as i32;
+        ImageView::from_slice(&mut self.data, self.width, self.height, stride, self.format)
+    }
+
+    /// A view of the inclusive rectangle `rect`, clipped to the image.
+    pub fn sub_view(&mut self, rect: RectI) -> ImageView<'_> {
+        self.view().into_sub_view(rect)
+    }
+}
+
+// ============================================================================
+// ImageView — borrowed pixels
+// ============================================================================
+
+/// A rendering buffer over pixels borrowed for `'a`.
+///
+/// Implements [`RenderingBufferMut`], so `&mut view` can be passed straight
+/// to a pixel format constructor. The constructor panics if the format's
+/// pixels are wider than the view's [`PixelFormatTag`].
+pub struct ImageView<'a> {
+    rbuf: RowAccessor,
+    format: PixelFormatTag,
+    data: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> ImageView<'a> {
+    /// View `height` rows of `width` pixels in `data`, `stride` bytes apart.
+    ///
+    /// A negative `stride` stores the image bottom-up: row 0 is the last
+    /// row in memory.
+    ///
+    /// # Panics
+    /// If a row is wider than `|stride|` or `data` is too short.
+    pub fn from_slice(
+        data: &'a mut [u8],
+        width: u32,
+        height: u32,
+        stride: i32,
+        format: PixelFormatTag,
+    ) -> Self {
+        let row_len = width as usize * format.bytes_per_pixel() as usize;
+        let stride_abs = stride.unsigned_abs() as usize;
+        assert!(
+            row_len <= stride_abs,
+            "row of {row_len} bytes exceeds stride {stride}"
+        );
+        let need = match height {
+            0 => 0,
+            h => (h as usize - 1) * stride_abs + row_len,
+        };
+        assert!(
+            data.len() >= need,
+            "image data too short: {} bytes, need {need}",
+            data.len()
+        );
+
+        let mut rbuf = RowAccessor::new();
+        if width > 0 && height > 0 {
+            // SAFETY: the asserts above keep every row inside `data`, which
+            // stays mutably borrowed for `'a`.
+            unsafe {
+                rbuf.attach_rows(data.as_mut_ptr(), width, height, stride, row_len as u32);
+            }
+        }
+        Self {
+            rbuf,
+            format,
+            data: PhantomData,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.rbuf.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.rbuf.height()
+    }
+
+    pub fn format(&self) -> PixelFormatTag {
+        self.format
+    }
+
+    /// Bytes between the starts of adjacent rows; negative for bottom-up.
+    pub fn stride(&self) -> i32 {
+        self.rbuf.stride()
+    }
+
+    /// The `width * bytes_per_pixel` bytes of row `y`.
+    ///
+    /// # Panics
+    /// If `y` is not below `height()`.
+    pub fn row_slice(&self, y: u32) -> &[u8] {
+        self.rbuf.row_slice(y)
+    }
+
+    /// Mutable form of [`row_slice`](Self::row_slice).
+    pub fn row_slice_mut(&mut self, y: u32) -> &mut [u8] {
+        self.rbuf.row_slice_mut(y)
+    }
+
+    /// Fill every byte of the view with `value`.
+    pub fn clear(&mut self, value: u8) {
+        self.rbuf.clear(value);
+    }
+
+    /// A view of the inclusive rectangle `rect`, clipped to this view.
+    pub fn sub_view(&mut self, rect: RectI) -> ImageView<'_> {
+        self.narrow(rect)
+    }
+
+    /// Like `sub_view`, but consumes this view and keeps its lifetime.
+    pub fn into_sub_view(mut self, rect: RectI) -> ImageView<'a> {
+        self.narrow(rect)
+    }
+
+    fn narrow<'b>(&mut self, rect: RectI) -> ImageView<'b> {
+        let x1 = rect.x1.max(0);
+        let y1 = rect.y1.max(0);
+        let x2 = rect.x2.min(self.rbuf.width() as i32 - 1);
+        let y2 = rect.y2.min(self.rbuf.height() as i32 - 1);
+
+        let mut rbuf = RowAccessor::new();
+        if x1 <= x2 && y1 <= y2 {
+            let bpp = self.format.bytes_per_pixel();
+            let (w, h) = ((x2 - x1 + 1) as u32, (y2 - y1 + 1) as u32);
+            let stride = self.rbuf.stride();
+            // SAFETY: the rectangle lies inside this view, so every row of
+            // the sub-view lies inside rows this view already covers. For a
+            // bottom-up buffer the lowest address is the rectangle's last row.
+            unsafe {
+                let first = self.rbuf.row_ptr(y1).add((x1 as u32 * bpp) as usize);
+                let buf = if stride < 0 {
+                    first.offset((h as isize - 1) * stride as isize)
+                } else {
+                    first
+                };
+                rbuf.attach_rows(buf, w, h, stride, w * bpp);
+            }
+        }
+        ImageView {
+            rbuf,
+            format: self.format,
+            data: PhantomData,
+        }
+    }
+}
+
+impl RenderingBufferAccess for ImageView<'_> {
+    fn width(&self) -> u32 {
+        self.rbuf.width()
+    }
+    fn height(&self) -> u32 {
+        self.rbuf.height()
+    }
+    fn stride_abs(&self) -> u32 {
+        self.rbuf.stride_abs()
+    }
+    fn row_len(&self) -> u32 {
+        RenderingBufferAccess::row_len(&self.rbuf)
+    }
+    unsafe fn row_ptr_const(&self, y: i32) -> *const u8 {
+        self.rbuf.row_ptr_const(y)
+    }
+}
+
+impl RenderingBufferMut for ImageView<'_> {
+    #[inline]
+    unsafe fn row_ptr_mut(&mut self, x: i32, y: i32, len: u32) -> *mut u8 {
+        self.rbuf.row_ptr_mut(x, y, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{Gray8, Rgba8};
+    use crate::pixfmt_gray::PixfmtGray8;
+    use crate::pixfmt_rgba::{PixelFormat, PixfmtRgba32};
+    use crate::rasterizer_scanline_aa::RasterizerScanlineAa;
+    use crate::renderer_base::RendererBase;
+    use crate::renderer_scanline::render_scanlines_aa_solid;
+    use crate::scanline_u::ScanlineU8;
+
+    #[test]
+    fn test_render_without_unsafe() {
+        let mut img = ImageBuffer::new(20, 20, PixelFormatTag::Rgba32);
+        {
+            let mut view = img.view();
+            let mut ren = RendererBase::new(PixfmtRgba32::new(&mut view));
+            ren.clear(&Rgba8::new(255, 255, 255, 255));
+            let mut ras = RasterizerScanlineAa::new();
+            let mut sl = ScanlineU8::new();
+            ras.move_to_d(2.0, 2.0);
+            ras.line_to_d(18.0, 2.0);
+            ras.line_to_d(10.0, 18.0);
+            render_scanlines_aa_solid(&mut ras, &mut sl, &mut ren, &Rgba8::new(255, 0, 0, 255));
+        }
+        let px = |x: usize, y: usize| &img.data()[(y * 20 + x) * 4..][..4];
+        assert_eq!(px(10, 6), &[255, 0, 0, 255]);
+        assert_eq!(px(0, 19), &[255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_sub_view_is_confined() {
+        let mut img = ImageBuffer::new(6, 4, PixelFormatTag::Rgba32);
+        {
+            let mut view = img.sub_view(RectI::new(2, 1, 3, 2));
+            assert_eq!((view.width(), view.height()), (2, 2));
+            view.clear(0xAA);
+            let mut pf = PixfmtRgba32::new(&mut view);
+            pf.copy_pixel(0, 0, &Rgba8::new(1, 2, 3, 4));
+        }
+        let data = img.data();
+        for y in 0..4 {
+            for x in 0..6 {
+                let p = &data[(y * 6 + x) * 4..][..4];
+                let inside = (2..=3).contains(&x) && (1..=2).contains(&y);
+                match (x, y) {
+                    (2, 1) => assert_eq!(p, &[1, 2, 3, 4]),
+                    _ if inside => assert_eq!(p, &[0xAA; 4]),
+                    _ => assert_eq!(p, &[0; 4], "({x},{y}) touched"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gray_sub_view() {
+        let mut img = ImageBuffer::new(5, 3, PixelFormatTag::Gray8);
+        {
+            let mut view = img.sub_view(RectI::new(1, 1, 3, 1));
+            let mut pf = PixfmtGray8::new(&mut view);
+            pf.copy_hline(0, 0, 3, &Gray8::new(7, 255));
+        }
+        assert_eq!(img.data(), &[0, 0, 0, 0, 0, 0, 7, 7, 7, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot hold")]
+    fn test_pixfmt_wider_than_tag_panics() {
+        let mut img = ImageBuffer::new(4, 4, PixelFormatTag::Gray8);
+        let mut view = img.view();
+        let _pf = PixfmtRgba32::new(&mut view);
+    }
+
+    #[test]
+    fn test_sub_view_clips_to_bounds() {
+        let mut img = ImageBuffer::new(4, 4, PixelFormatTag::Gray8);
+        let mut view = img.view();
+        let sub = view.sub_view(RectI::new(-3, 2, 10, 10));
+        assert_eq!((sub.width(), sub.height()), (4, 2));
+        let empty = view.sub_view(RectI::new(5, 5, 8, 8));
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+    }
+
+    #[test]
+    fn test_bottom_up_slice() {
+        // Two 2-pixel Gray8 rows with 1 byte of padding, stored bottom-up.
+        let mut data = vec![0u8; 5];
+        {
+            let mut view = ImageView::from_slice(&mut data, 2, 2, -3, PixelFormatTag::Gray8);
+            view.row_slice_mut(0).copy_from_slice(&[1, 2]);
+            view.row_slice_mut(1).copy_from_slice(&[3, 4]);
+            let mut sub = view.sub_view(RectI::new(1, 0, 1, 1));
+            assert_eq!(sub.row_slice(0), &[2]);
+            assert_eq!(sub.row_slice(1), &[4]);
+            sub.row_slice_mut(1)[0] = 9;
+        }
+        assert_eq!(data, vec![3, 9, 0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "too short")]
+    fn test_short_slice_panics() {
+        let mut data = vec![0u8; 10];
+        ImageView::from_slice(&mut data, 2, 2, 8, PixelFormatTag::Rgba32);
+    }
+}
@@ -43,9 +43,11 @@ pub mod clip_liang_barsky;
 pub mod curves;
 pub mod dda_line;
 pub mod ellipse;
+pub mod image_buffer;
 pub mod math_stroke;
 pub mod path_storage;
 pub mod rendering_buffer;
+pub mod rendering_buffer_dynarow;
 pub mod rounded_rect;
 pub mod simul_eq;
 pub mod trans_affine;
@@ -62,9 +64,15 @@ pub mod scanline_u;
 pub mod pixfmt_gray;
 pub mod pixfmt_lcd;
 pub mod pixfmt_rgb;
+pub mod pixfmt_rgb48;
+pub mod pixfmt_rgb_packed;
 pub mod pixfmt_rgba;
+pub mod pixfmt_rgba64;
+pub mod pixfmt_rgba128;
+mod pixfmt_rgba_simd;
 pub mod renderer_base;
 pub mod renderer_scanline;
+pub mod renderer_tiled;
 
 // Phase 4: Converter Pipeline
 pub mod conv_adaptor_vcgen;
@@ -89,11 +97,13 @@ pub mod span_interpolator_linear;
 pub mod span_solid;
 
 // Phase 6: Transforms, Image Filters, Text & Alpha Masking
+pub mod alpha_mask_u16;
 pub mod alpha_mask_u8;
 pub mod conv_marker;
 pub mod gsv_text;
 pub mod image_accessors;
 pub mod image_filters;
+pub mod pixfmt_amask_adaptor;
 pub mod span_image_filter;
 pub mod trans_bilinear;
 pub mod trans_perspective;
@@ -113,11 +123,15 @@ pub mod ctrl;
 
 // Phase 9: Quick Transforms & Segmentator
 pub mod conv_adaptor_vpgen;
+pub mod conv_clip_polygon;
+pub mod conv_clip_polyline;
 pub mod conv_segmentator;
 pub mod span_interpolator_adaptor;
 pub mod trans_polar;
 pub mod trans_single_path;
 pub mod trans_warp_magnifier;
+pub mod vpgen_clip_polygon;
+pub mod vpgen_clip_polyline;
 pub mod vpgen_segmentator;
 
 // Phase 10: Patterns & Perspective Interpolator
@@ -131,6 +145,7 @@ pub mod pixfmt_transposer;
 pub mod renderer_mclip;
 
 // Phase 12: Scanline Boolean Algebra
+pub mod polygon_boolean_algebra;
 pub mod scanline_boolean_algebra;
 pub mod scanline_storage_aa;
 pub mod scanline_storage_bin;
@@ -0,0 +1,211 @@
+//! Pixel format alpha-mask adaptor.
+//!
+//! Port of `agg_pixfmt_amask_adaptor.h`.
+//! Wraps a `PixelFormat`, multiplying the coverage of every blend call by an
+//! `AlphaMask`, so renderers draw through the mask without a special
+//! scanline type.
+
+use crate::alpha_mask_u8::AlphaMask;
+use crate::basics::{CoverType, COVER_FULL};
+use crate::pixfmt_rgba::PixelFormat;
+
+/// Pixel format wrapper that applies an alpha mask to all blending.
+///
+/// Port of C++ `pixfmt_amask_adaptor<PixFmt, AlphaMask>`.
+/// Copy operations become blends with the mask value as coverage.
+pub struct PixfmtAmaskAdaptor<'a, PF, AM> {
+    pixf: PF,
+    mask: &'a AM,
+    span: Vec<CoverType>,
+}
+
+impl<'a, PF: PixelFormat, AM: AlphaMask> PixfmtAmaskAdaptor<'a, PF, AM> {
+    pub fn new(pixf: PF, mask: &'a AM) -> Self {
+        Self {
+            pixf,
+            mask,
+            span: Vec::new(),
+        }
+    }
+
+    pub fn attach_alpha_mask(&mut self, mask: &'a AM) {
+        self.mask = mask;
+    }
+
+    pub fn inner(&self) -> &PF {
+        &self.pixf
+    }
+
+    pub fn inner_mut(&mut self) -> &mut PF {
+        &mut self.pixf
+    }
+
+    /// Resize the span to `len` covers, all set to `cover`.
+    fn init_span(&mut self, len: usize, cover: CoverType) {
+        self.span.clear();
+        self.span.resize(len, cover);
+    }
+
+    /// Resize the span to `len` and copy `covers` into it.
+    fn init_span_covers(&mut self, len: usize, covers: &[CoverType]) {
+        self.span.clear();
+        self.span.extend_from_slice(&covers[..len]);
+    }
+}
+
+impl<PF: PixelFormat, AM: AlphaMask> PixelFormat for PixfmtAmaskAdaptor<'_, PF, AM> {
+    type ColorType = PF::ColorType;
+
+    fn width(&self) -> u32 {
+        self.pixf.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.pixf.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> PF::ColorType {
+        self.pixf.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &PF::ColorType) {
+        let cover = self.mask.pixel(x, y);
+        self.pixf.blend_pixel(x, y, c, cover);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &PF::ColorType) {
+        self.init_span(len as usize, 0);
+        self.mask.fill_hspan(x, y, &mut self.span);
+        self.pixf.blend_solid_hspan(x, y, len, c, &self.span);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &PF::ColorType, cover: CoverType) {
+        let cover = self.mask.combine_pixel(x, y, cover);
+        self.pixf.blend_pixel(x, y, c, cover);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &PF::ColorType, cover: CoverType) {
+        self.init_span(len as usize, cover);
+        self.mask.combine_hspan(x, y, &mut self.span);
+        self.pixf.blend_solid_hspan(x, y, len, c, &self.span);
+    }
+
+    fn blend_solid_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        c: &PF::ColorType,
+        covers: &[CoverType],
+    ) {
+        self.init_span_covers(len as usize, covers);
+        self.mask.combine_hspan(x, y, &mut self.span);
+        self.pixf.blend_solid_hspan(x, y, len, c, &self.span);
+    }
+
+    /// Unlike C++, which fills the span from the mask alone when `covers` is
+    /// null, a uniform `cover` is combined with the mask rather than dropped.
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[PF::ColorType],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        if covers.is_empty() {
+            self.init_span(len as usize, cover);
+        } else {
+            self.init_span_covers(len as usize, covers);
+        }
+        self.mask.combine_hspan(x, y, &mut self.span);
+        self.pixf
+            .blend_color_hspan(x, y, len, colors, &self.span, COVER_FULL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alpha_mask_u8::{AlphaMaskGray8, OneComponentMask};
+    use crate::color::Rgba8;
+    use crate::pixfmt_rgba::PixfmtRgba32;
+    use crate::rendering_buffer::RowAccessor;
+
+    fn make_buffer(w: u32, h: u32, bpp: u32) -> (Vec<u8>, RowAccessor) {
+        let buf = vec![0u8; (h * w * bpp) as usize];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, w, h, (w * bpp) as i32);
+        }
+        (buf, ra)
+    }
+
+    /// 4x1 gray mask: 0, 128, 255, 64.
+    fn make_mask() -> (Vec<u8>, RowAccessor) {
+        let buf = vec![0u8, 128, 255, 64];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, 4, 1, 4);
+        }
+        (buf, ra)
+    }
+
+    #[test]
+    fn test_blend_hline_is_masked() {
+        let (_mbuf, mra) = make_mask();
+        let mask = AlphaMaskGray8::new(&mra, OneComponentMask);
+        let (buf, mut ra) = make_buffer(4, 1, 4);
+        let mut pf = PixfmtAmaskAdaptor::new(PixfmtRgba32::new(&mut ra), &mask);
+        pf.blend_hline(0, 0, 4, &Rgba8::new(255, 255, 255, 255), 255);
+        let reds: Vec<u8> = buf.chunks(4).map(|p| p[0]).collect();
+        assert_eq!(reds[0], 0);
+        assert!((reds[1] as i32 - 128).abs() <= 1, "{reds:?}");
+        assert_eq!(reds[2], 255);
+        assert!((reds[3] as i32 - 64).abs() <= 1, "{reds:?}");
+    }
+
+    #[test]
+    fn test_copy_becomes_masked_blend() {
+        let (_mbuf, mra) = make_mask();
+        let mask = AlphaMaskGray8::new(&mra, OneComponentMask);
+        let (buf, mut ra) = make_buffer(4, 1, 4);
+        let mut pf = PixfmtAmaskAdaptor::new(PixfmtRgba32::new(&mut ra), &mask);
+        pf.copy_hline(0, 0, 4, &Rgba8::new(200, 0, 0, 255));
+        assert_eq!(buf[0], 0);
+        assert_eq!(buf[8], 200);
+        pf.copy_pixel(1, 0, &Rgba8::new(200, 0, 0, 255));
+        assert!(buf[4] > 0 && buf[4] < 200);
+    }
+
+    #[test]
+    fn test_combines_with_covers() {
+        let (_mbuf, mra) = make_mask();
+        let mask = AlphaMaskGray8::new(&mra, OneComponentMask);
+        let (buf, mut ra) = make_buffer(4, 1, 4);
+        let mut pf = PixfmtAmaskAdaptor::new(PixfmtRgba32::new(&mut ra), &mask);
+        let c = Rgba8::new(255, 255, 255, 255);
+        pf.blend_solid_hspan(0, 0, 4, &c, &[255, 255, 128, 0]);
+        assert_eq!(buf[8], mask.combine_pixel(2, 0, 128));
+        assert_eq!(buf[12], 0);
+
+        let (buf2, mut ra2) = make_buffer(4, 1, 4);
+        let mut pf2 = PixfmtAmaskAdaptor::new(PixfmtRgba32::new(&mut ra2), &mask);
+        pf2.blend_color_hspan(0, 0, 4, &[c; 4], &[], 128);
+        assert_eq!(buf2[8], buf[8]);
+        assert_eq!(buf2[0], 0);
+    }
+
+    #[test]
+    fn test_outside_mask_is_clipped() {
+        let (_mbuf, mra) = make_mask();
+        let mask = AlphaMaskGray8::new(&mra, OneComponentMask);
+        let (buf, mut ra) = make_buffer(4, 2, 4);
+        let mut pf = PixfmtAmaskAdaptor::new(PixfmtRgba32::new(&mut ra), &mask);
+        // Row 1 lies outside the 4x1 mask.
+        pf.blend_hline(0, 1, 4, &Rgba8::new(255, 255, 255, 255), 255);
+        pf.blend_pixel(2, 1, &Rgba8::new(255, 255, 255, 255), 255);
+        assert!(buf[16..].iter().all(|&v| v == 0));
+    }
+}
@@ -1,20 +1,24 @@
-//! Grayscale pixel format with alpha blending.
+//! Grayscale pixel formats with alpha blending.
 //!
-//! Port of `agg_pixfmt_gray.h` — pixel format that reads and writes 8-bit
-//! grayscale pixels (1 byte per pixel) with non-premultiplied alpha blending.
+//! Port of `agg_pixfmt_gray.h` — pixel formats that read and write 8-bit
+//! (1 byte per pixel) and 16-bit (2 bytes per pixel) grayscale pixels with
+//! non-premultiplied alpha blending.
 //!
 //! The alpha value comes from the source color only; the buffer stores
 //! only a single gray value channel. Blending treats missing alpha as
 //! fully opaque.
 
 use crate::basics::CoverType;
-use crate::color::Gray8;
+use crate::color::{Gray16, Gray8};
 use crate::pixfmt_rgba::PixelFormat;
-use crate::rendering_buffer::RowAccessor;
+use crate::rendering_buffer::{assert_row_len, RenderingBufferMut, RowAccessor};
 
 /// Bytes per pixel for Gray8.
 const BPP: usize = 1;
 
+/// Bytes per pixel for Gray16.
+const BPP16: usize = 2;
+
 /// Pixel format for non-premultiplied Gray8 (1 byte per pixel).
 ///
 /// Port of C++ `pixfmt_alpha_blend_gray<blender_gray<gray8>, rendering_buf, 1, 0>`.
@@ -23,12 +27,13 @@ const BPP: usize = 1;
 /// Since there is no alpha channel stored in the buffer, `pixel()` always
 /// returns `a=255`. Blending uses the source color's alpha to interpolate
 /// the gray value.
-pub struct PixfmtGray8<'a> {
-    rbuf: &'a mut RowAccessor,
+pub struct PixfmtGray8<'a, RB = RowAccessor> {
+    rbuf: &'a mut RB,
 }
 
-impl<'a> PixfmtGray8<'a> {
-    pub fn new(rbuf: &'a mut RowAccessor) -> Self {
+impl<'a, RB: RenderingBufferMut> PixfmtGray8<'a, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        assert_row_len(rbuf, BPP);
         Self { rbuf }
     }
 
@@ -36,7 +41,7 @@ impl<'a> PixfmtGray8<'a> {
     #[inline]
     fn row(&self, y: i32) -> &[u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
+            let ptr = self.rbuf.row_ptr_const(y);
             std::slice::from_raw_parts(ptr, self.rbuf.width() as usize * BPP)
         }
     }
@@ -45,8 +50,9 @@ impl<'a> PixfmtGray8<'a> {
     #[inline]
     fn row_mut(&mut self, y: i32) -> &mut [u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
-            std::slice::from_raw_parts_mut(ptr, self.rbuf.width() as usize * BPP)
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * BPP)
         }
     }
 
@@ -68,7 +74,7 @@ impl<'a> PixfmtGray8<'a> {
     }
 }
 
-impl<'a> PixelFormat for PixfmtGray8<'a> {
+impl<'a, RB: RenderingBufferMut> PixelFormat for PixfmtGray8<'a, RB> {
     type ColorType = Gray8;
 
     fn width(&self) -> u32 {
@@ -173,6 +179,157 @@ impl<'a> PixelFormat for PixfmtGray8<'a> {
     }
 }
 
+// ============================================================================
+// PixfmtGray16 — 16-bit grayscale
+// ============================================================================
+
+/// Pixel format for non-premultiplied Gray16 (2 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_gray<blender_gray<gray16>, rendering_buf, 1, 0>`
+/// (a.k.a. `pixfmt_gray16`). Each pixel is one native-endian `u16`.
+///
+/// As with [`PixfmtGray8`], `pixel()` always returns an opaque color and
+/// blending uses the source color's alpha.
+pub struct PixfmtGray16<'a, RB = RowAccessor> {
+    rbuf: &'a mut RB,
+}
+
+impl<'a, RB: RenderingBufferMut> PixfmtGray16<'a, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        assert_row_len(rbuf, BPP16);
+        Self { rbuf }
+    }
+
+    /// Row of pixels at `y` as a byte slice (shared, read-only view).
+    #[inline]
+    fn row(&self, y: i32) -> &[u8] {
+        unsafe {
+            let ptr = self.rbuf.row_ptr_const(y);
+            std::slice::from_raw_parts(ptr, self.rbuf.width() as usize * BPP16)
+        }
+    }
+
+    /// Row of pixels at `y` as a mutable byte slice.
+    #[inline]
+    fn row_mut(&mut self, y: i32) -> &mut [u8] {
+        unsafe {
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * BPP16)
+        }
+    }
+
+    #[inline]
+    fn get(row: &[u8], x: usize) -> u16 {
+        u16::from_ne_bytes([row[x * BPP16], row[x * BPP16 + 1]])
+    }
+
+    #[inline]
+    fn set(row: &mut [u8], x: usize, v: u16) {
+        row[x * BPP16..x * BPP16 + BPP16].copy_from_slice(&v.to_ne_bytes());
+    }
+
+    /// Clear the entire buffer to a solid gray value.
+    pub fn clear(&mut self, c: &Gray16) {
+        let h = self.rbuf.height();
+        for y in 0..h {
+            let row = self.row_mut(y as i32);
+            for px in row.chunks_exact_mut(BPP16) {
+                px.copy_from_slice(&c.v.to_ne_bytes());
+            }
+        }
+    }
+
+    /// Copy or blend a single pixel (internal helper, no bounds checking).
+    #[inline]
+    fn copy_or_blend(row: &mut [u8], x: usize, cv: u16, alpha: u16) {
+        if alpha == Gray16::BASE_MASK as u16 {
+            Self::set(row, x, cv);
+        } else if alpha > 0 {
+            Self::set(row, x, Gray16::lerp(Self::get(row, x), cv, alpha));
+        }
+    }
+}
+
+impl<'a, RB: RenderingBufferMut> PixelFormat for PixfmtGray16<'a, RB> {
+    type ColorType = Gray16;
+
+    fn width(&self) -> u32 {
+        self.rbuf.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.rbuf.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Gray16 {
+        let row = self.row(y);
+        Gray16::new(Self::get(row, x as usize) as u32, Gray16::BASE_MASK)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Gray16) {
+        let row = self.row_mut(y);
+        Self::set(row, x as usize, c.v);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Gray16) {
+        let row = self.row_mut(y);
+        for i in 0..len as usize {
+            Self::set(row, x as usize + i, c.v);
+        }
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Gray16, cover: CoverType) {
+        let row = self.row_mut(y);
+        let alpha = Gray16::mult_cover(c.a, cover);
+        Self::copy_or_blend(row, x as usize, c.v, alpha);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Gray16, cover: CoverType) {
+        let row = self.row_mut(y);
+        let alpha = Gray16::mult_cover(c.a, cover);
+        for i in 0..len as usize {
+            Self::copy_or_blend(row, x as usize + i, c.v, alpha);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Gray16, covers: &[CoverType]) {
+        let row = self.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let alpha = Gray16::mult_cover(c.a, cov);
+            Self::copy_or_blend(row, x as usize + i, c.v, alpha);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Gray16],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.row_mut(y);
+        if !covers.is_empty() {
+            for i in 0..len as usize {
+                let c = &colors[i];
+                let alpha = Gray16::mult_cover(c.a, covers[i]);
+                Self::copy_or_blend(row, x as usize + i, c.v, alpha);
+            }
+        } else if cover == 255 {
+            for (i, c) in colors.iter().enumerate().take(len as usize) {
+                Self::copy_or_blend(row, x as usize + i, c.v, c.a);
+            }
+        } else {
+            for (i, c) in colors.iter().enumerate().take(len as usize) {
+                let alpha = Gray16::mult_cover(c.a, cover);
+                Self::copy_or_blend(row, x as usize + i, c.v, alpha);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,4 +465,67 @@ mod tests {
         let p2 = pf.pixel(2, 0);
         assert!(p2.v > 20 && p2.v < 35, "v={}", p2.v);
     }
+
+    // ===== Gray16 =====
+
+    fn make_buffer16(w: u32, h: u32) -> (Vec<u8>, RowAccessor) {
+        let stride = (w * BPP16 as u32) as i32;
+        let buf = vec![0u8; (h * w * BPP16 as u32) as usize];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, w, h, stride);
+        }
+        (buf, ra)
+    }
+
+    #[test]
+    fn test_gray16_copy_pixel_roundtrip() {
+        let (buf, mut ra) = make_buffer16(10, 10);
+        let mut pf = PixfmtGray16::new(&mut ra);
+        pf.copy_pixel(5, 5, &Gray16::new(0x1234, 0xFFFF));
+        let p = pf.pixel(5, 5);
+        assert_eq!(p.v, 0x1234);
+        assert_eq!(p.a, 0xFFFF);
+        let off = (5 * 10 + 5) * BPP16;
+        assert_eq!(&buf[off..off + 2], &0x1234u16.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_gray16_clear_and_copy_hline() {
+        let (_buf, mut ra) = make_buffer16(20, 4);
+        let mut pf = PixfmtGray16::new(&mut ra);
+        pf.clear(&Gray16::new(1000, 0xFFFF));
+        pf.copy_hline(5, 2, 10, &Gray16::new(40000, 0xFFFF));
+        assert_eq!(pf.pixel(4, 2).v, 1000);
+        for x in 5..15 {
+            assert_eq!(pf.pixel(x, 2).v, 40000);
+        }
+        assert_eq!(pf.pixel(15, 2).v, 1000);
+    }
+
+    #[test]
+    fn test_gray16_blend_keeps_precision() {
+        let (_buf, mut ra) = make_buffer16(10, 1);
+        let mut pf = PixfmtGray16::new(&mut ra);
+        pf.clear(&Gray16::new(0xFFFF, 0xFFFF));
+        // 50% black over white
+        pf.blend_pixel(0, 0, &Gray16::new(0, 0x8000), 255);
+        assert_eq!(pf.pixel(0, 0).v, Gray16::lerp(0xFFFF, 0, 0x8000));
+        // Full alpha copies, zero cover is a no-op
+        pf.blend_solid_hspan(1, 0, 2, &Gray16::new(777, 0xFFFF), &[255, 0]);
+        assert_eq!(pf.pixel(1, 0).v, 777);
+        assert_eq!(pf.pixel(2, 0).v, 0xFFFF);
+    }
+
+    #[test]
+    fn test_gray16_blend_color_hspan() {
+        let (_buf, mut ra) = make_buffer16(10, 1);
+        let mut pf = PixfmtGray16::new(&mut ra);
+        let colors = [Gray16::new(100, 0xFFFF), Gray16::new(60000, 0xFFFF)];
+        pf.blend_color_hspan(3, 0, 2, &colors, &[], 255);
+        assert_eq!(pf.pixel(3, 0).v, 100);
+        assert_eq!(pf.pixel(4, 0).v, 60000);
+        pf.blend_color_hspan(3, 0, 2, &colors, &[], 0);
+        assert_eq!(pf.pixel(4, 0).v, 60000);
+    }
 }
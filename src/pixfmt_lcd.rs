@@ -12,7 +12,7 @@
 use crate::basics::CoverType;
 use crate::color::Rgba8;
 use crate::pixfmt_rgba::PixelFormat;
-use crate::rendering_buffer::RowAccessor;
+use crate::rendering_buffer::{assert_row_len, RenderingBufferMut, RowAccessor};
 
 // ============================================================================
 // LcdDistributionLut
@@ -101,14 +101,15 @@ const BPP: usize = 4; // bytes per pixel in RGBA32
 /// The `blend_solid_hspan` method distributes each coverage value across
 /// 5 neighboring subpixels (tertiary, secondary, primary, secondary, tertiary)
 /// using the `LcdDistributionLut`, matching the C++ implementation exactly.
-pub struct PixfmtRgba32Lcd<'a> {
-    rbuf: &'a mut RowAccessor,
+pub struct PixfmtRgba32Lcd<'a, RB = RowAccessor> {
+    rbuf: &'a mut RB,
     lut: &'a LcdDistributionLut,
 }
 
-impl<'a> PixfmtRgba32Lcd<'a> {
+impl<'a, RB: RenderingBufferMut> PixfmtRgba32Lcd<'a, RB> {
     /// Create a new LCD pixel format wrapping an RGBA32 rendering buffer.
-    pub fn new(rbuf: &'a mut RowAccessor, lut: &'a LcdDistributionLut) -> Self {
+    pub fn new(rbuf: &'a mut RB, lut: &'a LcdDistributionLut) -> Self {
+        assert_row_len(rbuf, BPP);
         Self { rbuf, lut }
     }
 
@@ -122,7 +123,7 @@ impl<'a> PixfmtRgba32Lcd<'a> {
     #[inline]
     fn row(&self, y: i32) -> &[u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
+            let ptr = self.rbuf.row_ptr_const(y);
             std::slice::from_raw_parts(ptr, self.rbuf.width() as usize * BPP)
         }
     }
@@ -131,8 +132,9 @@ impl<'a> PixfmtRgba32Lcd<'a> {
     #[inline]
     fn row_mut(&mut self, y: i32) -> &mut [u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
-            std::slice::from_raw_parts_mut(ptr, self.rbuf.width() as usize * BPP)
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * BPP)
         }
     }
 
@@ -145,7 +147,7 @@ impl<'a> PixfmtRgba32Lcd<'a> {
     }
 }
 
-impl<'a> PixelFormat for PixfmtRgba32Lcd<'a> {
+impl<'a, RB: RenderingBufferMut> PixelFormat for PixfmtRgba32Lcd<'a, RB> {
     type ColorType = Rgba8;
 
     fn width(&self) -> u32 {
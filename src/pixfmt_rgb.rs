@@ -1,41 +1,69 @@
-//! RGB pixel format with alpha blending (no alpha channel in buffer).
+//! RGB pixel formats with alpha blending (no alpha channel in buffer).
 //!
-//! Port of `agg_pixfmt_rgb.h` — pixel format that reads and writes RGB24
-//! pixels (3 bytes per pixel) with non-premultiplied alpha blending.
+//! Port of `agg_pixfmt_rgb.h` — pixel formats that read and write 24-bit
+//! pixels (3 bytes per pixel) in RGB or BGR byte order, with either
+//! non-premultiplied or premultiplied-source blending, plus sRGB variants
+//! (`PixfmtSrgb24`, `PixfmtSbgr24`) that blend in linear light.
 //!
 //! The alpha value comes from the source color only; the buffer stores
 //! no alpha channel. Blending treats missing alpha as fully opaque.
+//!
+//! The 16-bit-per-channel variants live in [`crate::pixfmt_rgb48`].
+
+use std::marker::PhantomData;
 
 use crate::basics::CoverType;
-use crate::color::Rgba8;
-use crate::pixfmt_rgba::PixelFormat;
-use crate::rendering_buffer::RowAccessor;
+use crate::color::{OrderBgr, OrderRgb, RgbOrder, Rgba8};
+use crate::gamma::SrgbLut;
+use crate::pixfmt_rgba::{srgb_alpha, srgb_lerp, PixelFormat};
+use crate::rendering_buffer::{assert_row_len, RenderingBufferMut, RowAccessor};
 
 /// Bytes per pixel for RGB24.
 const BPP: usize = 3;
 
-/// Pixel format for non-premultiplied RGB24 (3 bytes per pixel).
-///
-/// Port of C++ `pixfmt_alpha_blend_rgb<blender_rgb<rgba8>, rendering_buf>`.
-/// Component order: R=0, G=1, B=2.
-///
-/// Since there is no alpha channel stored in the buffer, `pixel()` always
-/// returns `a=255`. Blending uses the source color's alpha to interpolate
-/// each RGB component.
-pub struct PixfmtRgb24<'a> {
-    rbuf: &'a mut RowAccessor,
+/// The three bytes of `c` laid out in channel order `O`.
+#[inline]
+fn pixel_bytes<O: RgbOrder>(c: &Rgba8) -> [u8; 3] {
+    let mut p = [0u8; 3];
+    p[O::R] = c.r;
+    p[O::G] = c.g;
+    p[O::B] = c.b;
+    p
 }
 
-impl<'a> PixfmtRgb24<'a> {
-    pub fn new(rbuf: &'a mut RowAccessor) -> Self {
-        Self { rbuf }
+// ============================================================================
+// RgbRaw — shared non-blend plumbing for the RGB24 pixel formats
+// ============================================================================
+
+/// Shared non-blend plumbing for the RGB24 pixel formats, the 3-byte
+/// counterpart of the RGBA32 `RgbaRaw` helper.
+struct RgbRaw<'a, O, RB> {
+    rbuf: &'a mut RB,
+    order: PhantomData<O>,
+}
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> RgbRaw<'a, O, RB> {
+    fn new(rbuf: &'a mut RB) -> Self {
+        assert_row_len(rbuf, BPP);
+        Self {
+            rbuf,
+            order: PhantomData,
+        }
+    }
+
+    fn width(&self) -> u32 {
+        self.rbuf.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.rbuf.height()
     }
 
     /// Row of pixels at `y` as a byte slice (shared, read-only view).
     #[inline]
     fn row(&self, y: i32) -> &[u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
+            let ptr = self.rbuf.row_ptr_const(y);
             std::slice::from_raw_parts(ptr, self.rbuf.width() as usize * BPP)
         }
     }
@@ -44,114 +72,151 @@ impl<'a> PixfmtRgb24<'a> {
     #[inline]
     fn row_mut(&mut self, y: i32) -> &mut [u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
-            std::slice::from_raw_parts_mut(ptr, self.rbuf.width() as usize * BPP)
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * BPP)
         }
     }
 
     /// Clear the entire buffer to a solid color.
-    pub fn clear(&mut self, c: &Rgba8) {
-        let w = self.rbuf.width();
-        let h = self.rbuf.height();
+    fn clear(&mut self, c: &Rgba8) {
+        let h = self.height();
+        let pat = pixel_bytes::<O>(c);
         for y in 0..h {
             let row = self.row_mut(y as i32);
-            for x in 0..w as usize {
-                let off = x * BPP;
-                row[off] = c.r;
-                row[off + 1] = c.g;
-                row[off + 2] = c.b;
+            for px in row.chunks_exact_mut(BPP) {
+                px.copy_from_slice(&pat);
             }
         }
     }
 
+    fn pixel(&self, x: i32, y: i32) -> Rgba8 {
+        let row = self.row(y);
+        let off = x as usize * BPP;
+        Rgba8::new(
+            row[off + O::R] as u32,
+            row[off + O::G] as u32,
+            row[off + O::B] as u32,
+            255,
+        )
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba8) {
+        let row = self.row_mut(y);
+        let off = x as usize * BPP;
+        row[off..off + BPP].copy_from_slice(&pixel_bytes::<O>(c));
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8) {
+        let row = self.row_mut(y);
+        let start = x as usize * BPP;
+        let pat = pixel_bytes::<O>(c);
+        for px in row[start..start + len as usize * BPP].chunks_exact_mut(BPP) {
+            px.copy_from_slice(&pat);
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtAlphaBlendRgb — non-premultiplied RGB, 8 bits per channel
+// ============================================================================
+
+/// Pixel format for non-premultiplied 24-bit RGB in either byte order
+/// (3 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_rgb<blender_rgb<rgba8, Order>, rendering_buf>`.
+/// Use the [`PixfmtRgb24`] and [`PixfmtBgr24`] aliases.
+///
+/// Since there is no alpha channel stored in the buffer, `pixel()` always
+/// returns `a=255`. Blending uses the source color's alpha to interpolate
+/// each RGB component.
+pub struct PixfmtAlphaBlendRgb<'a, O, RB = RowAccessor> {
+    raw: RgbRaw<'a, O, RB>,
+}
+
+/// RGB byte order (R=0, G=1, B=2). Port of C++ `pixfmt_rgb24`.
+pub type PixfmtRgb24<'a, RB = RowAccessor> = PixfmtAlphaBlendRgb<'a, OrderRgb, RB>;
+/// BGR byte order, as used by BMP files and most video capture APIs.
+/// Port of C++ `pixfmt_bgr24`.
+pub type PixfmtBgr24<'a, RB = RowAccessor> = PixfmtAlphaBlendRgb<'a, OrderBgr, RB>;
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixfmtAlphaBlendRgb<'a, O, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: RgbRaw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba8) {
+        self.raw.clear(c);
+    }
+
     /// Blend a single pixel (internal helper, no bounds checking).
     /// Non-premultiplied blending of R, G, B channels only.
     #[inline]
     fn blend_pix(p: &mut [u8], cr: u8, cg: u8, cb: u8, alpha: u8) {
-        p[0] = Rgba8::lerp(p[0], cr, alpha);
-        p[1] = Rgba8::lerp(p[1], cg, alpha);
-        p[2] = Rgba8::lerp(p[2], cb, alpha);
+        p[O::R] = Rgba8::lerp(p[O::R], cr, alpha);
+        p[O::G] = Rgba8::lerp(p[O::G], cg, alpha);
+        p[O::B] = Rgba8::lerp(p[O::B], cb, alpha);
+    }
+
+    /// Copy `c` when `alpha` is opaque, otherwise blend it.
+    #[inline]
+    fn copy_or_blend(p: &mut [u8], c: &Rgba8, alpha: u8) {
+        if alpha == 255 {
+            p.copy_from_slice(&pixel_bytes::<O>(c));
+        } else if alpha > 0 {
+            Self::blend_pix(p, c.r, c.g, c.b, alpha);
+        }
     }
 }
 
-impl<'a> PixelFormat for PixfmtRgb24<'a> {
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixelFormat for PixfmtAlphaBlendRgb<'a, O, RB> {
     type ColorType = Rgba8;
 
     fn width(&self) -> u32 {
-        self.rbuf.width()
+        self.raw.width()
     }
 
     fn height(&self) -> u32 {
-        self.rbuf.height()
+        self.raw.height()
     }
 
     fn pixel(&self, x: i32, y: i32) -> Rgba8 {
-        let row = self.row(y);
-        let off = x as usize * BPP;
-        Rgba8::new(row[off] as u32, row[off + 1] as u32, row[off + 2] as u32, 255)
+        self.raw.pixel(x, y)
     }
 
     fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba8) {
-        let row = self.row_mut(y);
-        let off = x as usize * BPP;
-        row[off] = c.r;
-        row[off + 1] = c.g;
-        row[off + 2] = c.b;
+        self.raw.copy_pixel(x, y, c);
     }
 
     fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8) {
-        let row = self.row_mut(y);
-        for i in 0..len as usize {
-            let off = (x as usize + i) * BPP;
-            row[off] = c.r;
-            row[off + 1] = c.g;
-            row[off + 2] = c.b;
-        }
+        self.raw.copy_hline(x, y, len, c);
     }
 
     fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba8, cover: CoverType) {
-        let row = self.row_mut(y);
+        let row = self.raw.row_mut(y);
         let off = x as usize * BPP;
         let alpha = Rgba8::mult_cover(c.a, cover);
-        if alpha == 255 {
-            row[off] = c.r;
-            row[off + 1] = c.g;
-            row[off + 2] = c.b;
-        } else if alpha > 0 {
-            Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
-        }
+        Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
     }
 
     fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, cover: CoverType) {
-        let row = self.row_mut(y);
+        let row = self.raw.row_mut(y);
         let alpha = Rgba8::mult_cover(c.a, cover);
-        if alpha == 255 {
-            for i in 0..len as usize {
-                let off = (x as usize + i) * BPP;
-                row[off] = c.r;
-                row[off + 1] = c.g;
-                row[off + 2] = c.b;
-            }
-        } else if alpha > 0 {
-            for i in 0..len as usize {
-                let off = (x as usize + i) * BPP;
-                Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
-            }
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
         }
     }
 
     fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, covers: &[CoverType]) {
-        let row = self.row_mut(y);
+        let row = self.raw.row_mut(y);
         for (i, &cov) in covers.iter().enumerate().take(len as usize) {
             let off = (x as usize + i) * BPP;
             let alpha = Rgba8::mult_cover(c.a, cov);
-            if alpha == 255 {
-                row[off] = c.r;
-                row[off + 1] = c.g;
-                row[off + 2] = c.b;
-            } else if alpha > 0 {
-                Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
-            }
+            Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
         }
     }
 
@@ -164,47 +229,258 @@ impl<'a> PixelFormat for PixfmtRgb24<'a> {
         covers: &[CoverType],
         cover: CoverType,
     ) {
-        let row = self.row_mut(y);
+        let row = self.raw.row_mut(y);
         if !covers.is_empty() {
             for i in 0..len as usize {
                 let off = (x as usize + i) * BPP;
                 let c = &colors[i];
                 let alpha = Rgba8::mult_cover(c.a, covers[i]);
-                if alpha == 255 {
-                    row[off] = c.r;
-                    row[off + 1] = c.g;
-                    row[off + 2] = c.b;
-                } else if alpha > 0 {
-                    Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
-                }
+                Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
             }
         } else if cover == 255 {
             for (i, c) in colors.iter().enumerate().take(len as usize) {
                 let off = (x as usize + i) * BPP;
-                if c.a == 255 {
-                    row[off] = c.r;
-                    row[off + 1] = c.g;
-                    row[off + 2] = c.b;
-                } else if c.a > 0 {
-                    Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, c.a);
-                }
+                Self::copy_or_blend(&mut row[off..off + BPP], c, c.a);
             }
         } else {
             for (i, c) in colors.iter().enumerate().take(len as usize) {
                 let off = (x as usize + i) * BPP;
                 let alpha = Rgba8::mult_cover(c.a, cover);
-                if alpha == 255 {
-                    row[off] = c.r;
-                    row[off + 1] = c.g;
-                    row[off + 2] = c.b;
-                } else if alpha > 0 {
-                    Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
-                }
+                Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtAlphaBlendRgbPre — premultiplied-source RGB, 8 bits per channel
+// ============================================================================
+
+/// Pixel format for 24-bit RGB that expects premultiplied source colors
+/// (3 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_rgb<blender_rgb_pre<rgba8, Order>, rendering_buf>`.
+/// Use the [`PixfmtRgb24Pre`] and [`PixfmtBgr24Pre`] aliases. Each
+/// destination channel becomes `c + p * (1 - alpha)`.
+pub struct PixfmtAlphaBlendRgbPre<'a, O, RB = RowAccessor> {
+    raw: RgbRaw<'a, O, RB>,
+}
+
+/// Premultiplied RGB byte order. Port of C++ `pixfmt_rgb24_pre`.
+pub type PixfmtRgb24Pre<'a, RB = RowAccessor> = PixfmtAlphaBlendRgbPre<'a, OrderRgb, RB>;
+/// Premultiplied BGR byte order. Port of C++ `pixfmt_bgr24_pre`.
+pub type PixfmtBgr24Pre<'a, RB = RowAccessor> = PixfmtAlphaBlendRgbPre<'a, OrderBgr, RB>;
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixfmtAlphaBlendRgbPre<'a, O, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: RgbRaw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba8) {
+        self.raw.clear(c);
+    }
+
+    /// Premultiplied blend folding a coverage value into the color.
+    /// Port of `blender_rgb_pre::blend_pix` (with cover).
+    #[inline]
+    fn blend_pix_cover(p: &mut [u8], c: &Rgba8, cover: CoverType) {
+        let alpha = Rgba8::mult_cover(c.a, cover);
+        p[O::R] = Rgba8::prelerp(p[O::R], Rgba8::mult_cover(c.r, cover), alpha);
+        p[O::G] = Rgba8::prelerp(p[O::G], Rgba8::mult_cover(c.g, cover), alpha);
+        p[O::B] = Rgba8::prelerp(p[O::B], Rgba8::mult_cover(c.b, cover), alpha);
+    }
+
+    /// Port of `pixfmt_alpha_blend_rgb::copy_or_blend_pix` (with cover).
+    #[inline]
+    fn copy_or_blend_cover(p: &mut [u8], c: &Rgba8, cover: CoverType) {
+        if c.a != 0 {
+            if c.a == 255 && cover == 255 {
+                p.copy_from_slice(&pixel_bytes::<O>(c));
+            } else {
+                Self::blend_pix_cover(p, c, cover);
             }
         }
     }
 }
 
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixelFormat for PixfmtAlphaBlendRgbPre<'a, O, RB> {
+    type ColorType = Rgba8;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba8 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba8) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba8, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        let off = x as usize * BPP;
+        Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cover);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cover);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, covers: &[CoverType]) {
+        let row = self.raw.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cov);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba8],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.raw.row_mut(y);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cov);
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtAlphaBlendSrgb — sRGB-encoded RGB, blended in linear light
+// ============================================================================
+
+/// Pixel format for sRGB-encoded 24-bit RGB in either byte order
+/// (3 bytes per pixel), blended in linear light.
+///
+/// Counterpart of the C++ `pixfmt_srgb24` / `pixfmt_sbgr24`. Channels are
+/// decoded through [`SrgbLut`] before blending and encoded again on store,
+/// exactly as in [`PixfmtSrgba32`](crate::pixfmt_rgba::PixfmtSrgba32).
+pub struct PixfmtAlphaBlendSrgb<'a, O, RB = RowAccessor> {
+    raw: RgbRaw<'a, O, RB>,
+}
+
+/// sRGB RGB byte order. Port of C++ `pixfmt_srgb24`.
+pub type PixfmtSrgb24<'a, RB = RowAccessor> = PixfmtAlphaBlendSrgb<'a, OrderRgb, RB>;
+/// sRGB BGR byte order. Port of C++ `pixfmt_sbgr24`.
+pub type PixfmtSbgr24<'a, RB = RowAccessor> = PixfmtAlphaBlendSrgb<'a, OrderBgr, RB>;
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixfmtAlphaBlendSrgb<'a, O, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: RgbRaw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba8) {
+        self.raw.clear(c);
+    }
+
+    /// Copy `c` when `alpha` is full, otherwise blend it in linear light.
+    #[inline]
+    fn copy_or_blend(lut: &SrgbLut, p: &mut [u8], c: &Rgba8, alpha: u16) {
+        if alpha == 0xFFFF {
+            p.copy_from_slice(&pixel_bytes::<O>(c));
+        } else if alpha > 0 {
+            p[O::R] = srgb_lerp(lut, p[O::R], c.r, alpha);
+            p[O::G] = srgb_lerp(lut, p[O::G], c.g, alpha);
+            p[O::B] = srgb_lerp(lut, p[O::B], c.b, alpha);
+        }
+    }
+}
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixelFormat for PixfmtAlphaBlendSrgb<'a, O, RB> {
+    type ColorType = Rgba8;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba8 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba8) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba8, cover: CoverType) {
+        let lut = SrgbLut::get();
+        let row = self.raw.row_mut(y);
+        let off = x as usize * BPP;
+        Self::copy_or_blend(lut, &mut row[off..off + BPP], c, srgb_alpha(c.a, cover));
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, cover: CoverType) {
+        let lut = SrgbLut::get();
+        let row = self.raw.row_mut(y);
+        let alpha = srgb_alpha(c.a, cover);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(lut, &mut row[off..off + BPP], c, alpha);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, covers: &[CoverType]) {
+        let lut = SrgbLut::get();
+        let row = self.raw.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(lut, &mut row[off..off + BPP], c, srgb_alpha(c.a, cov));
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba8],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let lut = SrgbLut::get();
+        let row = self.raw.row_mut(y);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            Self::copy_or_blend(lut, &mut row[off..off + BPP], c, srgb_alpha(c.a, cov));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -330,4 +606,70 @@ mod tests {
         let p = pf.pixel(0, 0);
         assert_eq!(p.a, 255);
     }
+
+    #[test]
+    fn test_bgr24_byte_layout() {
+        let (buf, mut ra) = make_buffer(2, 1);
+        let mut pf = PixfmtBgr24::new(&mut ra);
+        pf.copy_pixel(1, 0, &Rgba8::new(10, 20, 30, 255));
+        assert_eq!(&buf[3..6], &[30, 20, 10]);
+        assert_eq!(pf.pixel(1, 0), Rgba8::new(10, 20, 30, 255));
+    }
+
+    #[test]
+    fn test_orders_blend_identically() {
+        let (_b1, mut ra1) = make_buffer(4, 1);
+        let (_b2, mut ra2) = make_buffer(4, 1);
+        let mut rgb = PixfmtRgb24::new(&mut ra1);
+        let mut bgr = PixfmtBgr24::new(&mut ra2);
+        let bg = Rgba8::new(200, 100, 50, 255);
+        rgb.clear(&bg);
+        bgr.clear(&bg);
+        let c = Rgba8::new(30, 160, 240, 128);
+        rgb.blend_solid_hspan(0, 0, 4, &c, &[255, 128, 64, 0]);
+        bgr.blend_solid_hspan(0, 0, 4, &c, &[255, 128, 64, 0]);
+        for x in 0..4 {
+            assert_eq!(rgb.pixel(x, 0), bgr.pixel(x, 0));
+        }
+    }
+
+    #[test]
+    fn test_pre_blend() {
+        let (_buf, mut ra) = make_buffer(3, 1);
+        let mut pf = PixfmtRgb24Pre::new(&mut ra);
+        pf.clear(&Rgba8::new(255, 255, 255, 255));
+        // 50% black, premultiplied: c + p * (1 - a).
+        pf.blend_pixel(0, 0, &Rgba8::new(0, 0, 0, 128), 255);
+        assert_eq!(pf.pixel(0, 0), Rgba8::new(127, 127, 127, 255));
+        // 50% red premultiplied is (128, 0, 0, 128).
+        pf.blend_pixel(1, 0, &Rgba8::new(128, 0, 0, 128), 255);
+        assert_eq!(pf.pixel(1, 0), Rgba8::new(255, 127, 127, 255));
+        // Opaque at full cover copies.
+        pf.blend_pixel(2, 0, &Rgba8::new(1, 2, 3, 255), 255);
+        assert_eq!(pf.pixel(2, 0), Rgba8::new(1, 2, 3, 255));
+    }
+
+    #[test]
+    fn test_srgb_antialiased_edge_is_linear() {
+        use crate::rasterizer_scanline_aa::RasterizerScanlineAa;
+        use crate::renderer_base::RendererBase;
+        use crate::renderer_scanline::render_scanlines_aa_solid;
+        use crate::scanline_u::ScanlineU8;
+
+        // A white rectangle whose left edge covers half of column 1.
+        let (_buf, mut ra) = make_buffer(4, 2);
+        let mut pf = PixfmtSbgr24::new(&mut ra);
+        pf.clear(&Rgba8::new(0, 0, 0, 255));
+        let mut ren = RendererBase::new(pf);
+        let mut ras = RasterizerScanlineAa::new();
+        let mut sl = ScanlineU8::new();
+        ras.move_to_d(1.5, 0.0);
+        ras.line_to_d(4.0, 0.0);
+        ras.line_to_d(4.0, 2.0);
+        ras.line_to_d(1.5, 2.0);
+        render_scanlines_aa_solid(&mut ras, &mut sl, &mut ren, &Rgba8::new(255, 255, 255, 255));
+        let p = ren.ren().pixel(1, 0);
+        assert_eq!(p, Rgba8::new(188, 188, 188, 255));
+        assert_eq!(ren.ren().pixel(2, 0), Rgba8::new(255, 255, 255, 255));
+    }
 }
@@ -0,0 +1,439 @@
+//! RGB48 pixel formats (16 bits per channel, no alpha channel in buffer).
+//!
+//! Port of the `rgba16` instantiations in `agg_pixfmt_rgb.h`
+//! (`pixfmt_rgb48`, `pixfmt_bgr48` and their `_pre` variants) — pixel
+//! formats that read and write 6-byte pixels with alpha blending.
+//!
+//! Each channel is a native-endian `u16`, matching the C++ layout where a
+//! pixel is three `int16u` values. The rendering buffer is still addressed
+//! in bytes, so the stride must be at least `width * 6`.
+
+use std::marker::PhantomData;
+
+use crate::basics::CoverType;
+use crate::color::{OrderBgr, OrderRgb, RgbOrder, Rgba16};
+use crate::pixfmt_rgba::PixelFormat;
+use crate::rendering_buffer::{assert_row_len, RenderingBufferMut, RowAccessor};
+
+/// Bytes per pixel for RGB48.
+const BPP: usize = 6;
+
+/// Full channel value (`0xFFFF`).
+const FULL: u16 = Rgba16::BASE_MASK as u16;
+
+/// Read channel `i` of the pixel starting at `p[0]`.
+#[inline]
+fn get(p: &[u8], i: usize) -> u16 {
+    u16::from_ne_bytes([p[i * 2], p[i * 2 + 1]])
+}
+
+/// Write channel `i` of the pixel starting at `p[0]`.
+#[inline]
+fn set(p: &mut [u8], i: usize, v: u16) {
+    p[i * 2..i * 2 + 2].copy_from_slice(&v.to_ne_bytes());
+}
+
+/// The six bytes of `c` laid out in channel order `O`.
+#[inline]
+fn pixel_bytes<O: RgbOrder>(c: &Rgba16) -> [u8; BPP] {
+    let mut p = [0u8; BPP];
+    set(&mut p, O::R, c.r);
+    set(&mut p, O::G, c.g);
+    set(&mut p, O::B, c.b);
+    p
+}
+
+// ============================================================================
+// Rgb48Raw — shared non-blend plumbing for the RGB48 pixel formats
+// ============================================================================
+
+/// Shared non-blend plumbing for the RGB48 pixel formats, the 16-bit
+/// counterpart of the RGB24 `RgbRaw` helper.
+struct Rgb48Raw<'a, O, RB> {
+    rbuf: &'a mut RB,
+    order: PhantomData<O>,
+}
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> Rgb48Raw<'a, O, RB> {
+    fn new(rbuf: &'a mut RB) -> Self {
+        assert_row_len(rbuf, BPP);
+        Self {
+            rbuf,
+            order: PhantomData,
+        }
+    }
+
+    fn width(&self) -> u32 {
+        self.rbuf.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.rbuf.height()
+    }
+
+    #[inline]
+    fn row(&self, y: i32) -> &[u8] {
+        unsafe {
+            let ptr = self.rbuf.row_ptr_const(y);
+            std::slice::from_raw_parts(ptr, self.rbuf.width() as usize * BPP)
+        }
+    }
+
+    #[inline]
+    fn row_mut(&mut self, y: i32) -> &mut [u8] {
+        unsafe {
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * BPP)
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    fn clear(&mut self, c: &Rgba16) {
+        let h = self.height();
+        let pat = pixel_bytes::<O>(c);
+        for y in 0..h {
+            let row = self.row_mut(y as i32);
+            for px in row.chunks_exact_mut(BPP) {
+                px.copy_from_slice(&pat);
+            }
+        }
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba16 {
+        let row = self.row(y);
+        let p = &row[x as usize * BPP..];
+        Rgba16::new(
+            get(p, O::R) as u32,
+            get(p, O::G) as u32,
+            get(p, O::B) as u32,
+            Rgba16::BASE_MASK,
+        )
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba16) {
+        let row = self.row_mut(y);
+        let off = x as usize * BPP;
+        row[off..off + BPP].copy_from_slice(&pixel_bytes::<O>(c));
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16) {
+        let row = self.row_mut(y);
+        let start = x as usize * BPP;
+        let pat = pixel_bytes::<O>(c);
+        for px in row[start..start + len as usize * BPP].chunks_exact_mut(BPP) {
+            px.copy_from_slice(&pat);
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtAlphaBlendRgb48 — non-premultiplied RGB, 16 bits per channel
+// ============================================================================
+
+/// Pixel format for non-premultiplied 48-bit RGB in either channel order
+/// (6 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_rgb<blender_rgb<rgba16, Order>, rendering_buf>`.
+/// Use the [`PixfmtRgb48`] and [`PixfmtBgr48`] aliases.
+///
+/// Blending mirrors [`PixfmtRgb24`](crate::pixfmt_rgb::PixfmtRgb24) with the
+/// `Rgba16` arithmetic; `pixel()` always returns an opaque color.
+pub struct PixfmtAlphaBlendRgb48<'a, O, RB = RowAccessor> {
+    raw: Rgb48Raw<'a, O, RB>,
+}
+
+/// RGB channel order. Port of C++ `pixfmt_rgb48`.
+pub type PixfmtRgb48<'a, RB = RowAccessor> = PixfmtAlphaBlendRgb48<'a, OrderRgb, RB>;
+/// BGR channel order. Port of C++ `pixfmt_bgr48`.
+pub type PixfmtBgr48<'a, RB = RowAccessor> = PixfmtAlphaBlendRgb48<'a, OrderBgr, RB>;
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixfmtAlphaBlendRgb48<'a, O, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: Rgb48Raw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba16) {
+        self.raw.clear(c);
+    }
+
+    /// Blend a single pixel (internal helper, no bounds checking).
+    #[inline]
+    fn blend_pix(p: &mut [u8], c: &Rgba16, alpha: u16) {
+        set(p, O::R, Rgba16::lerp(get(p, O::R), c.r, alpha));
+        set(p, O::G, Rgba16::lerp(get(p, O::G), c.g, alpha));
+        set(p, O::B, Rgba16::lerp(get(p, O::B), c.b, alpha));
+    }
+
+    /// Copy the color if `alpha` is full, otherwise blend it.
+    #[inline]
+    fn copy_or_blend(p: &mut [u8], c: &Rgba16, alpha: u16) {
+        if alpha == FULL {
+            p.copy_from_slice(&pixel_bytes::<O>(c));
+        } else if alpha > 0 {
+            Self::blend_pix(p, c, alpha);
+        }
+    }
+}
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixelFormat for PixfmtAlphaBlendRgb48<'a, O, RB> {
+    type ColorType = Rgba16;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba16 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba16) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba16, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        let off = x as usize * BPP;
+        let alpha = Rgba16::mult_cover(c.a, cover);
+        Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        let alpha = Rgba16::mult_cover(c.a, cover);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba16, covers: &[CoverType]) {
+        let row = self.raw.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let alpha = Rgba16::mult_cover(c.a, cov);
+            Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba16],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.raw.row_mut(y);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            let alpha = Rgba16::mult_cover(c.a, cov);
+            Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtAlphaBlendRgb48Pre — premultiplied-source RGB, 16 bits per channel
+// ============================================================================
+
+/// Pixel format for 48-bit RGB that expects premultiplied source colors
+/// (6 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_rgb<blender_rgb_pre<rgba16, Order>, rendering_buf>`.
+/// Use the [`PixfmtRgb48Pre`] and [`PixfmtBgr48Pre`] aliases.
+pub struct PixfmtAlphaBlendRgb48Pre<'a, O, RB = RowAccessor> {
+    raw: Rgb48Raw<'a, O, RB>,
+}
+
+/// Premultiplied RGB channel order. Port of C++ `pixfmt_rgb48_pre`.
+pub type PixfmtRgb48Pre<'a, RB = RowAccessor> = PixfmtAlphaBlendRgb48Pre<'a, OrderRgb, RB>;
+/// Premultiplied BGR channel order. Port of C++ `pixfmt_bgr48_pre`.
+pub type PixfmtBgr48Pre<'a, RB = RowAccessor> = PixfmtAlphaBlendRgb48Pre<'a, OrderBgr, RB>;
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixfmtAlphaBlendRgb48Pre<'a, O, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: Rgb48Raw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba16) {
+        self.raw.clear(c);
+    }
+
+    /// Premultiplied blend folding a coverage value into the color.
+    /// Port of `blender_rgb_pre::blend_pix` (with cover).
+    #[inline]
+    fn blend_pix_cover(p: &mut [u8], c: &Rgba16, cover: CoverType) {
+        let alpha = Rgba16::mult_cover(c.a, cover);
+        let r = Rgba16::mult_cover(c.r, cover);
+        let g = Rgba16::mult_cover(c.g, cover);
+        let b = Rgba16::mult_cover(c.b, cover);
+        set(p, O::R, Rgba16::prelerp(get(p, O::R), r, alpha));
+        set(p, O::G, Rgba16::prelerp(get(p, O::G), g, alpha));
+        set(p, O::B, Rgba16::prelerp(get(p, O::B), b, alpha));
+    }
+
+    /// Port of `pixfmt_alpha_blend_rgb::copy_or_blend_pix` (with cover).
+    #[inline]
+    fn copy_or_blend_cover(p: &mut [u8], c: &Rgba16, cover: CoverType) {
+        if c.a != 0 {
+            if c.a == FULL && cover == 255 {
+                p.copy_from_slice(&pixel_bytes::<O>(c));
+            } else {
+                Self::blend_pix_cover(p, c, cover);
+            }
+        }
+    }
+}
+
+impl<'a, O: RgbOrder, RB: RenderingBufferMut> PixelFormat for PixfmtAlphaBlendRgb48Pre<'a, O, RB> {
+    type ColorType = Rgba16;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba16 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba16) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba16, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        let off = x as usize * BPP;
+        Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cover);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cover);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba16, covers: &[CoverType]) {
+        let row = self.raw.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cov);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba16],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.raw.row_mut(y);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cov);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_buffer(w: u32, h: u32) -> (Vec<u8>, RowAccessor) {
+        let stride = (w * BPP as u32) as i32;
+        let buf = vec![0u8; (h * w * BPP as u32) as usize];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, w, h, stride);
+        }
+        (buf, ra)
+    }
+
+    #[test]
+    fn test_copy_pixel_roundtrip() {
+        let (_buf, mut ra) = make_buffer(4, 2);
+        let mut pf = PixfmtRgb48::new(&mut ra);
+        let c = Rgba16::new(0x1234, 0xABCD, 0x0001, 0xFFFF);
+        pf.copy_pixel(2, 1, &c);
+        assert_eq!(pf.pixel(2, 1), c);
+        assert_eq!(pf.pixel(1, 1), Rgba16::new(0, 0, 0, 0xFFFF));
+    }
+
+    #[test]
+    fn test_bgr48_channel_layout() {
+        let (buf, mut ra) = make_buffer(1, 1);
+        let mut pf = PixfmtBgr48::new(&mut ra);
+        pf.copy_pixel(0, 0, &Rgba16::new(0x1111, 0x2222, 0x3333, 0xFFFF));
+        assert_eq!(get(&buf, 0), 0x3333);
+        assert_eq!(get(&buf, 1), 0x2222);
+        assert_eq!(get(&buf, 2), 0x1111);
+    }
+
+    #[test]
+    fn test_blend_half() {
+        let (_buf, mut ra) = make_buffer(3, 1);
+        let mut pf = PixfmtRgb48::new(&mut ra);
+        pf.clear(&Rgba16::new(0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF));
+        pf.blend_hline(0, 0, 3, &Rgba16::new(0, 0, 0, 0x8000), 255);
+        let p = pf.pixel(1, 0);
+        assert_eq!(p.r, 0x7FFF);
+        assert_eq!(p.a, 0xFFFF);
+
+        // Zero cover leaves the pixel unchanged.
+        pf.blend_solid_hspan(0, 0, 1, &Rgba16::new(0, 0, 0, 0xFFFF), &[0]);
+        assert_eq!(pf.pixel(0, 0).r, 0x7FFF);
+    }
+
+    #[test]
+    fn test_pre_matches_straight_for_opaque_source() {
+        let (_b1, mut ra1) = make_buffer(4, 1);
+        let (_b2, mut ra2) = make_buffer(4, 1);
+        let mut straight = PixfmtBgr48::new(&mut ra1);
+        let mut pre = PixfmtBgr48Pre::new(&mut ra2);
+        let bg = Rgba16::new(0x1000, 0x8000, 0xF000, 0xFFFF);
+        straight.clear(&bg);
+        pre.clear(&bg);
+
+        let c = Rgba16::new(0xFFFF, 0x4000, 0x0000, 0xFFFF);
+        let covers = [255u8, 200, 100, 0];
+        straight.blend_solid_hspan(0, 0, 4, &c, &covers);
+        pre.blend_solid_hspan(0, 0, 4, &c, &covers);
+        for x in 0..4 {
+            let (s, p) = (straight.pixel(x, 0), pre.pixel(x, 0));
+            for (a, b) in [(s.r, p.r), (s.g, p.g), (s.b, p.b)] {
+                assert!((a as i32 - b as i32).abs() <= 2, "x={x}: {s:?} vs {p:?}");
+            }
+        }
+        assert_eq!(pre.pixel(3, 0), bg);
+    }
+}
@@ -0,0 +1,465 @@
+//! Packed 16-bit RGB pixel formats (RGB565, RGB555, RGB444).
+//!
+//! Port of `agg_pixfmt_rgb_packed.h` — pixel formats that store each pixel
+//! as one native-endian `u16` with the channels packed into bit fields, as
+//! used by many embedded framebuffers.
+//!
+//! | Format | Bit layout (MSB → LSB)  |
+//! |--------|-------------------------|
+//! | RGB565 | `RRRRRGGG GGGBBBBB`     |
+//! | RGB555 | `1RRRRRGG GGGBBBBB`     |
+//! | RGB444 | `1111RRRR GGGGBBBB`     |
+//!
+//! Unused high bits are written as ones, matching C++ `blender_rgb555`
+//! (which sets `0x8000`), so the pixels read as opaque on hardware that
+//! interprets them as alpha. As with RGB24 the buffer stores no alpha and
+//! `pixel()` always returns `a=255`.
+//!
+//! Unlike the C++ blenders, which blend the truncated 8-bit values
+//! (`0xF8` for a full 5-bit channel), channels are widened by bit
+//! replication before blending so that a full channel reads back as 255
+//! and blending a pixel with its own color leaves it unchanged.
+
+use std::marker::PhantomData;
+
+use crate::basics::CoverType;
+use crate::color::Rgba8;
+use crate::pixfmt_rgba::PixelFormat;
+use crate::rendering_buffer::{assert_row_len, RenderingBufferMut, RowAccessor};
+
+/// Bytes per pixel for the packed formats.
+const BPP: usize = 2;
+
+/// Widen an `n`-bit channel value to 8 bits by replicating its high bits.
+#[inline]
+fn expand(v: u16, n: u32) -> u8 {
+    let x = v << (8 - n);
+    (x | (x >> n)) as u8
+}
+
+// ============================================================================
+// Blenders
+// ============================================================================
+
+/// Packs, unpacks and blends one packed RGB pixel.
+///
+/// Port of the C++ `blender_rgb555` / `blender_rgb565` family. `blend_pix`
+/// receives the pixel value and returns the blended one; `alpha` is the
+/// source alpha already scaled by `cover`.
+pub trait BlenderPacked {
+    fn make_pix(r: u8, g: u8, b: u8) -> u16;
+    fn make_color(p: u16) -> Rgba8;
+    fn blend_pix(p: u16, c: &Rgba8, alpha: u8, cover: CoverType) -> u16;
+}
+
+macro_rules! impl_packed_blenders {
+    (
+        $(#[$meta:meta])* $straight:ident,
+        $(#[$meta_pre:meta])* $pre:ident,
+        r: ($rs:expr, $rn:expr), g: ($gs:expr, $gn:expr), b: ($bs:expr, $bn:expr),
+        fill: $fill:expr
+    ) => {
+        $(#[$meta])*
+        pub struct $straight;
+
+        $(#[$meta_pre])*
+        pub struct $pre;
+
+        impl $straight {
+            #[inline]
+            fn pack(r: u8, g: u8, b: u8) -> u16 {
+                ((r as u16 >> (8 - $rn)) << $rs)
+                    | ((g as u16 >> (8 - $gn)) << $gs)
+                    | ((b as u16 >> (8 - $bn)) << $bs)
+                    | $fill
+            }
+
+            #[inline]
+            fn unpack(p: u16) -> [u8; 3] {
+                [
+                    expand((p >> $rs) & ((1 << $rn) - 1), $rn),
+                    expand((p >> $gs) & ((1 << $gn) - 1), $gn),
+                    expand((p >> $bs) & ((1 << $bn) - 1), $bn),
+                ]
+            }
+        }
+
+        impl BlenderPacked for $straight {
+            #[inline]
+            fn make_pix(r: u8, g: u8, b: u8) -> u16 {
+                Self::pack(r, g, b)
+            }
+
+            #[inline]
+            fn make_color(p: u16) -> Rgba8 {
+                let [r, g, b] = Self::unpack(p);
+                Rgba8::new(r as u32, g as u32, b as u32, 255)
+            }
+
+            #[inline]
+            fn blend_pix(p: u16, c: &Rgba8, alpha: u8, _cover: CoverType) -> u16 {
+                let [r, g, b] = Self::unpack(p);
+                Self::pack(
+                    Rgba8::lerp(r, c.r, alpha),
+                    Rgba8::lerp(g, c.g, alpha),
+                    Rgba8::lerp(b, c.b, alpha),
+                )
+            }
+        }
+
+        impl BlenderPacked for $pre {
+            #[inline]
+            fn make_pix(r: u8, g: u8, b: u8) -> u16 {
+                $straight::pack(r, g, b)
+            }
+
+            #[inline]
+            fn make_color(p: u16) -> Rgba8 {
+                $straight::make_color(p)
+            }
+
+            #[inline]
+            fn blend_pix(p: u16, c: &Rgba8, alpha: u8, cover: CoverType) -> u16 {
+                let [r, g, b] = $straight::unpack(p);
+                $straight::pack(
+                    Rgba8::prelerp(r, Rgba8::mult_cover(c.r, cover), alpha),
+                    Rgba8::prelerp(g, Rgba8::mult_cover(c.g, cover), alpha),
+                    Rgba8::prelerp(b, Rgba8::mult_cover(c.b, cover), alpha),
+                )
+            }
+        }
+    };
+}
+
+impl_packed_blenders!(
+    /// Non-premultiplied RGB565 blender. Port of C++ `blender_rgb565`.
+    BlenderRgb565,
+    /// Premultiplied RGB565 blender. Port of C++ `blender_rgb565_pre`.
+    BlenderRgb565Pre,
+    r: (11, 5), g: (5, 6), b: (0, 5),
+    fill: 0
+);
+
+impl_packed_blenders!(
+    /// Non-premultiplied RGB555 blender. Port of C++ `blender_rgb555`.
+    BlenderRgb555,
+    /// Premultiplied RGB555 blender. Port of C++ `blender_rgb555_pre`.
+    BlenderRgb555Pre,
+    r: (10, 5), g: (5, 5), b: (0, 5),
+    fill: 0x8000
+);
+
+impl_packed_blenders!(
+    /// Non-premultiplied RGB444 blender (no C++ counterpart; laid out like
+    /// `blender_rgb555` with 4-bit channels).
+    BlenderRgb444,
+    /// Premultiplied RGB444 blender.
+    BlenderRgb444Pre,
+    r: (8, 4), g: (4, 4), b: (0, 4),
+    fill: 0xF000
+);
+
+// ============================================================================
+// PixfmtAlphaBlendRgbPacked
+// ============================================================================
+
+/// Pixel format for packed 16-bit RGB (2 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_rgb_packed<Blender, rendering_buf>`.
+/// Use one of the aliases such as [`PixfmtRgb565`].
+pub struct PixfmtAlphaBlendRgbPacked<'a, B, RB = RowAccessor> {
+    rbuf: &'a mut RB,
+    blender: PhantomData<B>,
+}
+
+/// Non-premultiplied RGB565. Port of C++ `pixfmt_rgb565`.
+pub type PixfmtRgb565<'a, RB = RowAccessor> = PixfmtAlphaBlendRgbPacked<'a, BlenderRgb565, RB>;
+/// Premultiplied RGB565. Port of C++ `pixfmt_rgb565_pre`.
+pub type PixfmtRgb565Pre<'a, RB = RowAccessor> =
+    PixfmtAlphaBlendRgbPacked<'a, BlenderRgb565Pre, RB>;
+/// Non-premultiplied RGB555. Port of C++ `pixfmt_rgb555`.
+pub type PixfmtRgb555<'a, RB = RowAccessor> = PixfmtAlphaBlendRgbPacked<'a, BlenderRgb555, RB>;
+/// Premultiplied RGB555. Port of C++ `pixfmt_rgb555_pre`.
+pub type PixfmtRgb555Pre<'a, RB = RowAccessor> =
+    PixfmtAlphaBlendRgbPacked<'a, BlenderRgb555Pre, RB>;
+/// Non-premultiplied RGB444.
+pub type PixfmtRgb444<'a, RB = RowAccessor> = PixfmtAlphaBlendRgbPacked<'a, BlenderRgb444, RB>;
+/// Premultiplied RGB444.
+pub type PixfmtRgb444Pre<'a, RB = RowAccessor> =
+    PixfmtAlphaBlendRgbPacked<'a, BlenderRgb444Pre, RB>;
+
+impl<'a, B: BlenderPacked, RB: RenderingBufferMut> PixfmtAlphaBlendRgbPacked<'a, B, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        assert_row_len(rbuf, BPP);
+        Self {
+            rbuf,
+            blender: PhantomData,
+        }
+    }
+
+    /// Row of pixels at `y` as a byte slice (shared, read-only view).
+    #[inline]
+    fn row(&self, y: i32) -> &[u8] {
+        unsafe {
+            let ptr = self.rbuf.row_ptr_const(y);
+            std::slice::from_raw_parts(ptr, self.rbuf.width() as usize * BPP)
+        }
+    }
+
+    /// Row of pixels at `y` as a mutable byte slice.
+    #[inline]
+    fn row_mut(&mut self, y: i32) -> &mut [u8] {
+        unsafe {
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * BPP)
+        }
+    }
+
+    #[inline]
+    fn get(row: &[u8], x: usize) -> u16 {
+        u16::from_ne_bytes([row[x * BPP], row[x * BPP + 1]])
+    }
+
+    #[inline]
+    fn set(row: &mut [u8], x: usize, v: u16) {
+        row[x * BPP..x * BPP + BPP].copy_from_slice(&v.to_ne_bytes());
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba8) {
+        let v = B::make_pix(c.r, c.g, c.b).to_ne_bytes();
+        let h = self.rbuf.height();
+        for y in 0..h {
+            let row = self.row_mut(y as i32);
+            for px in row.chunks_exact_mut(BPP) {
+                px.copy_from_slice(&v);
+            }
+        }
+    }
+
+    /// Copy or blend a single pixel (internal helper, no bounds checking).
+    /// Port of C++ `copy_or_blend_pix`.
+    #[inline]
+    fn copy_or_blend(row: &mut [u8], x: usize, c: &Rgba8, cover: CoverType) {
+        if c.a > 0 {
+            let alpha = Rgba8::mult_cover(c.a, cover);
+            if alpha == 255 {
+                Self::set(row, x, B::make_pix(c.r, c.g, c.b));
+            } else if alpha > 0 {
+                Self::set(row, x, B::blend_pix(Self::get(row, x), c, alpha, cover));
+            }
+        }
+    }
+}
+
+impl<'a, B: BlenderPacked, RB: RenderingBufferMut> PixelFormat
+    for PixfmtAlphaBlendRgbPacked<'a, B, RB>
+{
+    type ColorType = Rgba8;
+
+    fn width(&self) -> u32 {
+        self.rbuf.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.rbuf.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba8 {
+        B::make_color(Self::get(self.row(y), x as usize))
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba8) {
+        let row = self.row_mut(y);
+        Self::set(row, x as usize, B::make_pix(c.r, c.g, c.b));
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8) {
+        let v = B::make_pix(c.r, c.g, c.b);
+        let row = self.row_mut(y);
+        for i in 0..len as usize {
+            Self::set(row, x as usize + i, v);
+        }
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba8, cover: CoverType) {
+        let row = self.row_mut(y);
+        Self::copy_or_blend(row, x as usize, c, cover);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, cover: CoverType) {
+        let row = self.row_mut(y);
+        for i in 0..len as usize {
+            Self::copy_or_blend(row, x as usize + i, c, cover);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, covers: &[CoverType]) {
+        let row = self.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            Self::copy_or_blend(row, x as usize + i, c, cov);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba8],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.row_mut(y);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            Self::copy_or_blend(row, x as usize + i, c, cov);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_buffer(w: u32, h: u32) -> (Vec<u8>, RowAccessor) {
+        let stride = (w * BPP as u32) as i32;
+        let buf = vec![0u8; (h * w * BPP as u32) as usize];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, w, h, stride);
+        }
+        (buf, ra)
+    }
+
+    fn raw(buf: &[u8], x: usize) -> u16 {
+        u16::from_ne_bytes([buf[x * BPP], buf[x * BPP + 1]])
+    }
+
+    #[test]
+    fn test_bit_layouts() {
+        let red = Rgba8::new(255, 0, 0, 255);
+        let green = Rgba8::new(0, 255, 0, 255);
+        let blue = Rgba8::new(0, 0, 255, 255);
+
+        let (buf, mut ra) = make_buffer(3, 1);
+        let mut pf = PixfmtRgb565::new(&mut ra);
+        pf.copy_pixel(0, 0, &red);
+        pf.copy_pixel(1, 0, &green);
+        pf.copy_pixel(2, 0, &blue);
+        assert_eq!(
+            [raw(&buf, 0), raw(&buf, 1), raw(&buf, 2)],
+            [0xF800, 0x07E0, 0x001F]
+        );
+
+        let (buf, mut ra) = make_buffer(3, 1);
+        let mut pf = PixfmtRgb555::new(&mut ra);
+        pf.copy_pixel(0, 0, &red);
+        pf.copy_pixel(1, 0, &green);
+        pf.copy_pixel(2, 0, &blue);
+        assert_eq!(
+            [raw(&buf, 0), raw(&buf, 1), raw(&buf, 2)],
+            [0xFC00, 0x83E0, 0x801F]
+        );
+
+        let (buf, mut ra) = make_buffer(3, 1);
+        let mut pf = PixfmtRgb444::new(&mut ra);
+        pf.copy_pixel(0, 0, &red);
+        pf.copy_pixel(1, 0, &green);
+        pf.copy_pixel(2, 0, &blue);
+        assert_eq!(
+            [raw(&buf, 0), raw(&buf, 1), raw(&buf, 2)],
+            [0xFF00, 0xF0F0, 0xF00F]
+        );
+    }
+
+    #[test]
+    fn test_pixel_expands_channels() {
+        let (_buf, mut ra) = make_buffer(2, 1);
+        let mut pf = PixfmtRgb565::new(&mut ra);
+        pf.copy_pixel(0, 0, &Rgba8::new(255, 255, 255, 255));
+        pf.copy_pixel(1, 0, &Rgba8::new(0x84, 0x82, 0x84, 255));
+        let white = pf.pixel(0, 0);
+        assert_eq!((white.r, white.g, white.b, white.a), (255, 255, 255, 255));
+        let p = pf.pixel(1, 0);
+        assert_eq!((p.r, p.g, p.b), (0x84, 0x82, 0x84));
+    }
+
+    #[test]
+    fn test_blend_half_black_over_white() {
+        let (_buf, mut ra) = make_buffer(4, 1);
+        let mut pf = PixfmtRgb565::new(&mut ra);
+        pf.clear(&Rgba8::new(255, 255, 255, 255));
+        pf.blend_hline(0, 0, 4, &Rgba8::new(0, 0, 0, 128), 255);
+        let p = pf.pixel(2, 0);
+        // 127 quantized to 5/6/5 bits and widened back.
+        assert_eq!((p.r, p.g, p.b), (0x7B, 0x7D, 0x7B));
+    }
+
+    #[test]
+    fn test_blend_same_color_is_stable() {
+        let (_buf, mut ra) = make_buffer(1, 1);
+        let mut pf = PixfmtRgb555::new(&mut ra);
+        let c = Rgba8::new(200, 90, 30, 255);
+        pf.copy_pixel(0, 0, &c);
+        let stored = pf.pixel(0, 0);
+        for a in [1u8, 64, 128, 254] {
+            pf.blend_pixel(
+                0,
+                0,
+                &Rgba8::new(stored.r as u32, stored.g as u32, stored.b as u32, a as u32),
+                255,
+            );
+            assert_eq!(pf.pixel(0, 0), stored, "alpha {a}");
+        }
+    }
+
+    #[test]
+    fn test_zero_cover_and_alpha_leave_pixel() {
+        let (buf, mut ra) = make_buffer(3, 1);
+        let mut pf = PixfmtRgb444::new(&mut ra);
+        pf.clear(&Rgba8::new(0x33, 0x66, 0x99, 255));
+        let before = raw(&buf, 1);
+        pf.blend_solid_hspan(0, 0, 3, &Rgba8::new(255, 0, 0, 255), &[0, 0, 0]);
+        pf.blend_pixel(1, 0, &Rgba8::new(255, 0, 0, 0), 255);
+        assert_eq!(raw(&buf, 1), before);
+    }
+
+    #[test]
+    fn test_pre_matches_straight_for_premultiplied_source() {
+        let (_b1, mut ra1) = make_buffer(4, 1);
+        let (_b2, mut ra2) = make_buffer(4, 1);
+        let mut straight = PixfmtRgb565::new(&mut ra1);
+        let mut pre = PixfmtRgb565Pre::new(&mut ra2);
+        let bg = Rgba8::new(40, 180, 220, 255);
+        straight.clear(&bg);
+        pre.clear(&bg);
+
+        let c = Rgba8::new(255, 128, 0, 255);
+        let covers = [255u8, 192, 64, 0];
+        straight.blend_solid_hspan(0, 0, 4, &c, &covers);
+        pre.blend_solid_hspan(0, 0, 4, &c, &covers);
+        for x in 0..4 {
+            let (s, p) = (straight.pixel(x, 0), pre.pixel(x, 0));
+            for (a, b) in [(s.r, p.r), (s.g, p.g), (s.b, p.b)] {
+                assert!((a as i32 - b as i32).abs() <= 8, "x={x}: {s:?} vs {p:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_blend_color_hspan_uniform_cover() {
+        let (_buf, mut ra) = make_buffer(3, 1);
+        let mut pf = PixfmtRgb565::new(&mut ra);
+        let colors = [
+            Rgba8::new(255, 0, 0, 255),
+            Rgba8::new(0, 255, 0, 255),
+            Rgba8::new(0, 0, 255, 255),
+        ];
+        pf.blend_color_hspan(0, 0, 3, &colors, &[], 255);
+        assert_eq!(pf.pixel(0, 0), Rgba8::new(255, 0, 0, 255));
+        assert_eq!(pf.pixel(1, 0), Rgba8::new(0, 255, 0, 255));
+        assert_eq!(pf.pixel(2, 0), Rgba8::new(0, 0, 255, 255));
+    }
+}
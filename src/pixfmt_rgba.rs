@@ -1,13 +1,20 @@
 //! RGBA pixel format with alpha blending.
 //!
-//! Port of `agg_pixfmt_rgba.h` — pixel format that reads and writes RGBA32
-//! pixels (4 bytes per pixel, non-premultiplied alpha) with alpha blending.
+//! Port of `agg_pixfmt_rgba.h` — pixel formats that read and write 32-bit
+//! RGBA pixels (4 bytes per pixel) with alpha blending.
 //!
-//! Provides the `PixelFormat` trait and `PixfmtRgba32` concrete implementation.
+//! Provides the `PixelFormat` trait and the straight and premultiplied
+//! RGBA32 formats, generic over the channel order (`PixfmtRgba32`,
+//! `PixfmtBgra32`, `PixfmtArgb32`, `PixfmtAbgr32` and their `Pre` variants),
+//! plus sRGB variants (`PixfmtSrgba32` etc.) that blend in linear light.
+//! Each renders into any `RenderingBufferMut`, `RowAccessor` by default.
 
 use crate::basics::CoverType;
-use crate::color::Rgba8;
-use crate::rendering_buffer::RowAccessor;
+use crate::color::{OrderAbgr, OrderArgb, OrderBgra, OrderRgba, Rgba16, Rgba8, RgbaOrder};
+use crate::gamma::SrgbLut;
+use crate::pixfmt_rgba_simd::{blend_colors, blend_span, BlendOp, SpanCover, SpanSrc};
+use crate::rendering_buffer::{assert_row_len, RenderingBufferMut, RowAccessor};
+use std::marker::PhantomData;
 
 // ============================================================================
 // PixelFormat trait
@@ -66,6 +73,17 @@ pub trait PixelFormat {
 
 const BPP: usize = 4; // bytes per pixel
 
+/// The four bytes of `c` laid out in channel order `O`.
+#[inline]
+pub(crate) fn pixel_bytes<O: RgbaOrder>(c: &Rgba8) -> [u8; 4] {
+    let mut p = [0u8; 4];
+    p[O::R] = c.r;
+    p[O::G] = c.g;
+    p[O::B] = c.b;
+    p[O::A] = c.a;
+    p
+}
+
 // ============================================================================
 // RgbaRaw — shared non-blend plumbing for the RGBA32 pixel formats
 // ============================================================================
@@ -73,13 +91,18 @@ const BPP: usize = 4; // bytes per pixel
 /// Shared non-blend plumbing for the RGBA32 pixel formats. Owns the row
 /// accessor and centralizes the unsafe row-slice construction; the wrapping
 /// formats differ only in their blend arithmetic (lerp vs prelerp).
-struct RgbaRaw<'a> {
-    rbuf: &'a mut RowAccessor,
+struct RgbaRaw<'a, O, RB> {
+    rbuf: &'a mut RB,
+    order: PhantomData<O>,
 }
 
-impl<'a> RgbaRaw<'a> {
-    fn new(rbuf: &'a mut RowAccessor) -> Self {
-        Self { rbuf }
+impl<'a, O: RgbaOrder, RB: RenderingBufferMut> RgbaRaw<'a, O, RB> {
+    fn new(rbuf: &'a mut RB) -> Self {
+        assert_row_len(rbuf, BPP);
+        Self {
+            rbuf,
+            order: PhantomData,
+        }
     }
 
     fn width(&self) -> u32 {
@@ -93,15 +116,15 @@ impl<'a> RgbaRaw<'a> {
     #[inline]
     fn row(&self, y: i32) -> &[u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
+            let ptr = self.rbuf.row_ptr_const(y);
             std::slice::from_raw_parts(ptr, (self.rbuf.width() as usize) * BPP)
         }
     }
 
     #[inline]
-    fn row_mut(&mut self, y: i32) -> &mut [u8] {
+    fn row_mut(&mut self, x: i32, y: i32, len: u32) -> &mut [u8] {
         unsafe {
-            let ptr = self.rbuf.row_ptr(y);
+            let ptr = self.rbuf.row_ptr_mut(x, y, len);
             std::slice::from_raw_parts_mut(ptr, (self.rbuf.width() as usize) * BPP)
         }
     }
@@ -110,14 +133,11 @@ impl<'a> RgbaRaw<'a> {
     fn clear(&mut self, c: &Rgba8) {
         let w = self.width();
         let h = self.height();
+        let pat = pixel_bytes::<O>(c);
         for y in 0..h {
-            let row = self.row_mut(y as i32);
-            for x in 0..w as usize {
-                let off = x * BPP;
-                row[off] = c.r;
-                row[off + 1] = c.g;
-                row[off + 2] = c.b;
-                row[off + 3] = c.a;
+            let row = self.row_mut(0, y as i32, w);
+            for px in row.chunks_exact_mut(BPP) {
+                px.copy_from_slice(&pat);
             }
         }
     }
@@ -126,30 +146,27 @@ impl<'a> RgbaRaw<'a> {
         let row = self.row(y);
         let off = x as usize * BPP;
         Rgba8::new(
-            row[off] as u32,
-            row[off + 1] as u32,
-            row[off + 2] as u32,
-            row[off + 3] as u32,
+            row[off + O::R] as u32,
+            row[off + O::G] as u32,
+            row[off + O::B] as u32,
+            row[off + O::A] as u32,
         )
     }
 
     fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba8) {
-        let row = self.row_mut(y);
+        let row = self.row_mut(x, y, 1);
         let off = x as usize * BPP;
-        row[off] = c.r;
-        row[off + 1] = c.g;
-        row[off + 2] = c.b;
-        row[off + 3] = c.a;
+        row[off..off + BPP].copy_from_slice(&pixel_bytes::<O>(c));
     }
 
     fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8) {
-        let row = self.row_mut(y);
+        let row = self.row_mut(x, y, len);
         let start = x as usize * BPP;
         // Fill a whole pixel at a time via a chunked pattern copy. The C++ equivalent
         // stores one pixel_type per pixel; writing the 4-byte pattern over
         // chunks_exact_mut lets the optimizer vectorize the fill instead of emitting
         // four individually-indexed byte stores per pixel.
-        let pat = [c.r, c.g, c.b, c.a];
+        let pat = pixel_bytes::<O>(c);
         for px in row[start..start + len as usize * BPP].chunks_exact_mut(BPP) {
             px.copy_from_slice(&pat);
         }
@@ -157,22 +174,34 @@ impl<'a> RgbaRaw<'a> {
 }
 
 // ============================================================================
-// PixfmtRgba32 — non-premultiplied RGBA, 8 bits per channel
+// PixfmtAlphaBlendRgba — non-premultiplied RGBA, 8 bits per channel
 // ============================================================================
 
-/// Pixel format for non-premultiplied RGBA32 (4 bytes per pixel).
+/// Pixel format for non-premultiplied 32-bit RGBA in any channel order
+/// (4 bytes per pixel).
 ///
-/// Port of C++ `pixfmt_alpha_blend_rgba<blender_rgba32, rendering_buf>`.
-/// Component order: R=0, G=1, B=2, A=3 (standard RGBA).
+/// Port of C++ `pixfmt_alpha_blend_rgba<blender_rgba<rgba8, Order>, rendering_buf>`.
+/// Use the [`PixfmtRgba32`], [`PixfmtBgra32`], [`PixfmtArgb32`] and
+/// [`PixfmtAbgr32`] aliases rather than naming the order directly.
 ///
 /// Blending uses the `Rgba8` utility methods (`lerp`, `mult_cover`, etc.)
 /// which match the C++ blender functions.
-pub struct PixfmtRgba32<'a> {
-    raw: RgbaRaw<'a>,
+pub struct PixfmtAlphaBlendRgba<'a, O, RB = RowAccessor> {
+    raw: RgbaRaw<'a, O, RB>,
 }
 
-impl<'a> PixfmtRgba32<'a> {
-    pub fn new(rbuf: &'a mut RowAccessor) -> Self {
+/// RGBA byte order (R=0, G=1, B=2, A=3). Port of C++ `pixfmt_rgba32`.
+pub type PixfmtRgba32<'a, RB = RowAccessor> = PixfmtAlphaBlendRgba<'a, OrderRgba, RB>;
+/// BGRA byte order, as used by Cairo image surfaces and Windows DIBs on
+/// little-endian machines. Port of C++ `pixfmt_bgra32`.
+pub type PixfmtBgra32<'a, RB = RowAccessor> = PixfmtAlphaBlendRgba<'a, OrderBgra, RB>;
+/// ARGB byte order. Port of C++ `pixfmt_argb32`.
+pub type PixfmtArgb32<'a, RB = RowAccessor> = PixfmtAlphaBlendRgba<'a, OrderArgb, RB>;
+/// ABGR byte order. Port of C++ `pixfmt_abgr32`.
+pub type PixfmtAbgr32<'a, RB = RowAccessor> = PixfmtAlphaBlendRgba<'a, OrderAbgr, RB>;
+
+impl<'a, O: RgbaOrder, RB: RenderingBufferMut> PixfmtAlphaBlendRgba<'a, O, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
         Self {
             raw: RgbaRaw::new(rbuf),
         }
@@ -186,10 +215,19 @@ impl<'a> PixfmtRgba32<'a> {
     /// Blend a single pixel (internal helper, no bounds checking).
     #[inline]
     fn blend_pix(p: &mut [u8], cr: u8, cg: u8, cb: u8, alpha: u8) {
-        p[0] = Rgba8::lerp(p[0], cr, alpha);
-        p[1] = Rgba8::lerp(p[1], cg, alpha);
-        p[2] = Rgba8::lerp(p[2], cb, alpha);
-        p[3] = Rgba8::lerp(p[3], 255, alpha);
+        p[O::R] = Rgba8::lerp(p[O::R], cr, alpha);
+        p[O::G] = Rgba8::lerp(p[O::G], cg, alpha);
+        p[O::B] = Rgba8::lerp(p[O::B], cb, alpha);
+        p[O::A] = Rgba8::lerp(p[O::A], 255, alpha);
+    }
+
+    /// Store an opaque copy of `c` (alpha forced to 255).
+    #[inline]
+    fn copy_opaque(p: &mut [u8], c: &Rgba8) {
+        p[O::R] = c.r;
+        p[O::G] = c.g;
+        p[O::B] = c.b;
+        p[O::A] = 255;
     }
 
     /// Apply inverse gamma correction to every pixel in the buffer.
@@ -201,13 +239,13 @@ impl<'a> PixfmtRgba32<'a> {
         let w = self.raw.width();
         let h = self.raw.height();
         for y in 0..h {
-            let row = self.raw.row_mut(y as i32);
+            let row = self.raw.row_mut(0, y as i32, w);
             for x in 0..w as usize {
                 let off = x * BPP;
-                row[off] = gamma.inv(row[off]);
-                row[off + 1] = gamma.inv(row[off + 1]);
-                row[off + 2] = gamma.inv(row[off + 2]);
-                // row[off + 3] (alpha) is left unchanged
+                row[off + O::R] = gamma.inv(row[off + O::R]);
+                row[off + O::G] = gamma.inv(row[off + O::G]);
+                row[off + O::B] = gamma.inv(row[off + O::B]);
+                // alpha is left unchanged
             }
         }
     }
@@ -220,18 +258,18 @@ impl<'a> PixfmtRgba32<'a> {
         let w = self.raw.width();
         let h = self.raw.height();
         for y in 0..h {
-            let row = self.raw.row_mut(y as i32);
+            let row = self.raw.row_mut(0, y as i32, w);
             for x in 0..w as usize {
                 let off = x * BPP;
-                row[off] = gamma.dir(row[off]);
-                row[off + 1] = gamma.dir(row[off + 1]);
-                row[off + 2] = gamma.dir(row[off + 2]);
+                row[off + O::R] = gamma.dir(row[off + O::R]);
+                row[off + O::G] = gamma.dir(row[off + O::G]);
+                row[off + O::B] = gamma.dir(row[off + O::B]);
             }
         }
     }
 }
 
-impl<'a> PixelFormat for PixfmtRgba32<'a> {
+impl<'a, O: RgbaOrder, RB: RenderingBufferMut> PixelFormat for PixfmtAlphaBlendRgba<'a, O, RB> {
     type ColorType = Rgba8;
 
     fn width(&self) -> u32 {
@@ -255,52 +293,36 @@ impl<'a> PixelFormat for PixfmtRgba32<'a> {
     }
 
     fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba8, cover: CoverType) {
-        let row = self.raw.row_mut(y);
+        let row = self.raw.row_mut(x, y, 1);
         let off = x as usize * BPP;
         let alpha = Rgba8::mult_cover(c.a, cover);
         if alpha == 255 {
-            row[off] = c.r;
-            row[off + 1] = c.g;
-            row[off + 2] = c.b;
-            row[off + 3] = 255;
+            Self::copy_opaque(&mut row[off..off + BPP], c);
         } else {
             Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
         }
     }
 
     fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, cover: CoverType) {
-        let row = self.raw.row_mut(y);
+        let row = self.raw.row_mut(x, y, len);
+        let span = &mut row[x as usize * BPP..(x as usize + len as usize) * BPP];
         let alpha = Rgba8::mult_cover(c.a, cover);
         if alpha == 255 {
-            for i in 0..len as usize {
-                let off = (x as usize + i) * BPP;
-                row[off] = c.r;
-                row[off + 1] = c.g;
-                row[off + 2] = c.b;
-                row[off + 3] = 255;
-            }
-        } else {
-            for i in 0..len as usize {
-                let off = (x as usize + i) * BPP;
-                Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
+            for px in span.chunks_exact_mut(BPP) {
+                Self::copy_opaque(px, c);
             }
+        } else if alpha > 0 {
+            let src = SpanSrc::Solid(pixel_bytes::<O>(c));
+            blend_span(BlendOp::Lerp, span, src, SpanCover::Uniform(cover), O::A);
         }
     }
 
     fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, covers: &[CoverType]) {
-        let row = self.raw.row_mut(y);
-        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
-            let off = (x as usize + i) * BPP;
-            let alpha = Rgba8::mult_cover(c.a, cov);
-            if alpha == 255 {
-                row[off] = c.r;
-                row[off + 1] = c.g;
-                row[off + 2] = c.b;
-                row[off + 3] = 255;
-            } else if alpha > 0 {
-                Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
-            }
-        }
+        let row = self.raw.row_mut(x, y, len);
+        let n = (len as usize).min(covers.len());
+        let span = &mut row[x as usize * BPP..(x as usize + n) * BPP];
+        let src = SpanSrc::Solid(pixel_bytes::<O>(c));
+        blend_span(BlendOp::Lerp, span, src, SpanCover::Covers(covers), O::A);
     }
 
     fn blend_color_hspan(
@@ -312,61 +334,30 @@ impl<'a> PixelFormat for PixfmtRgba32<'a> {
         covers: &[CoverType],
         cover: CoverType,
     ) {
-        let row = self.raw.row_mut(y);
-        if !covers.is_empty() {
-            // Per-pixel coverage from covers array
-            for i in 0..len as usize {
-                let off = (x as usize + i) * BPP;
-                let c = &colors[i];
-                let alpha = Rgba8::mult_cover(c.a, covers[i]);
-                if alpha == 255 {
-                    row[off] = c.r;
-                    row[off + 1] = c.g;
-                    row[off + 2] = c.b;
-                    row[off + 3] = 255;
-                } else if alpha > 0 {
-                    Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
-                }
-            }
-        } else if cover == 255 {
-            // Full coverage, direct copy/blend
-            for (i, c) in colors.iter().enumerate().take(len as usize) {
-                let off = (x as usize + i) * BPP;
-                if c.a == 255 {
-                    row[off] = c.r;
-                    row[off + 1] = c.g;
-                    row[off + 2] = c.b;
-                    row[off + 3] = 255;
-                } else if c.a > 0 {
-                    Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, c.a);
-                }
-            }
+        let row = self.raw.row_mut(x, y, len);
+        let n = (len as usize).min(colors.len());
+        let span = &mut row[x as usize * BPP..(x as usize + n) * BPP];
+        // A cover of 255 leaves each color's alpha as is, so the full-coverage
+        // case is the uniform one.
+        let cover = if covers.is_empty() {
+            SpanCover::Uniform(cover)
         } else {
-            // Uniform coverage for all pixels
-            for (i, c) in colors.iter().enumerate().take(len as usize) {
-                let off = (x as usize + i) * BPP;
-                let alpha = Rgba8::mult_cover(c.a, cover);
-                if alpha == 255 {
-                    row[off] = c.r;
-                    row[off + 1] = c.g;
-                    row[off + 2] = c.b;
-                    row[off + 3] = 255;
-                } else if alpha > 0 {
-                    Self::blend_pix(&mut row[off..off + BPP], c.r, c.g, c.b, alpha);
-                }
-            }
-        }
+            SpanCover::Covers(&covers[..n])
+        };
+        let colors = colors.iter().map(pixel_bytes::<O>);
+        blend_colors(BlendOp::Lerp, span, colors, cover, O::A);
     }
 }
 
 // ============================================================================
-// PixfmtRgba32Pre — premultiplied RGBA, 8 bits per channel
+// PixfmtAlphaBlendRgbaPre — premultiplied RGBA, 8 bits per channel
 // ============================================================================
 
-/// Pixel format for premultiplied RGBA32 (4 bytes per pixel).
+/// Pixel format for premultiplied 32-bit RGBA in any channel order
+/// (4 bytes per pixel).
 ///
-/// Port of C++ `pixfmt_alpha_blend_rgba<blender_rgba_pre, rendering_buf>`
-/// (a.k.a. `pixfmt_rgba32_pre`). Component order: R=0, G=1, B=2, A=3.
+/// Port of C++ `pixfmt_alpha_blend_rgba<blender_rgba_pre<rgba8, Order>, rendering_buf>`
+/// (a.k.a. `pixfmt_rgba32_pre` and friends, see the aliases below).
 ///
 /// Blending uses the premultiplied form of Alvy-Ray Smith's compositing
 /// function (`prelerp`) rather than the straight-alpha `lerp` used by
@@ -379,12 +370,21 @@ impl<'a> PixelFormat for PixfmtRgba32<'a> {
 /// Note that for an opaque backdrop the alpha channel is preserved at 255:
 /// `prelerp(255, a, a) == 255` for all `a`, so this format keeps a fully
 /// opaque buffer opaque, matching the RGB (`bgr24_pre`) reference output.
-pub struct PixfmtRgba32Pre<'a> {
-    raw: RgbaRaw<'a>,
+pub struct PixfmtAlphaBlendRgbaPre<'a, O, RB = RowAccessor> {
+    raw: RgbaRaw<'a, O, RB>,
 }
 
-impl<'a> PixfmtRgba32Pre<'a> {
-    pub fn new(rbuf: &'a mut RowAccessor) -> Self {
+/// Premultiplied RGBA byte order. Port of C++ `pixfmt_rgba32_pre`.
+pub type PixfmtRgba32Pre<'a, RB = RowAccessor> = PixfmtAlphaBlendRgbaPre<'a, OrderRgba, RB>;
+/// Premultiplied BGRA byte order. Port of C++ `pixfmt_bgra32_pre`.
+pub type PixfmtBgra32Pre<'a, RB = RowAccessor> = PixfmtAlphaBlendRgbaPre<'a, OrderBgra, RB>;
+/// Premultiplied ARGB byte order. Port of C++ `pixfmt_argb32_pre`.
+pub type PixfmtArgb32Pre<'a, RB = RowAccessor> = PixfmtAlphaBlendRgbaPre<'a, OrderArgb, RB>;
+/// Premultiplied ABGR byte order. Port of C++ `pixfmt_abgr32_pre`.
+pub type PixfmtAbgr32Pre<'a, RB = RowAccessor> = PixfmtAlphaBlendRgbaPre<'a, OrderAbgr, RB>;
+
+impl<'a, O: RgbaOrder, RB: RenderingBufferMut> PixfmtAlphaBlendRgbaPre<'a, O, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
         Self {
             raw: RgbaRaw::new(rbuf),
         }
@@ -399,10 +399,10 @@ impl<'a> PixfmtRgba32Pre<'a> {
     /// color components. Port of `blender_rgba_pre::blend_pix` (no cover).
     #[inline]
     fn blend_pix_pre(p: &mut [u8], cr: u8, cg: u8, cb: u8, alpha: u8) {
-        p[0] = Rgba8::prelerp(p[0], cr, alpha);
-        p[1] = Rgba8::prelerp(p[1], cg, alpha);
-        p[2] = Rgba8::prelerp(p[2], cb, alpha);
-        p[3] = Rgba8::prelerp(p[3], alpha, alpha);
+        p[O::R] = Rgba8::prelerp(p[O::R], cr, alpha);
+        p[O::G] = Rgba8::prelerp(p[O::G], cg, alpha);
+        p[O::B] = Rgba8::prelerp(p[O::B], cb, alpha);
+        p[O::A] = Rgba8::prelerp(p[O::A], alpha, alpha);
     }
 
     /// Premultiplied blend folding a coverage value into the color.
@@ -423,33 +423,15 @@ impl<'a> PixfmtRgba32Pre<'a> {
     fn copy_or_blend_cover(p: &mut [u8], c: &Rgba8, cover: CoverType) {
         if c.a != 0 {
             if c.a == 255 && cover == 255 {
-                p[0] = c.r;
-                p[1] = c.g;
-                p[2] = c.b;
-                p[3] = c.a;
+                p.copy_from_slice(&pixel_bytes::<O>(c));
             } else {
                 Self::blend_pix_cover(p, c, cover);
             }
         }
     }
-
-    /// Port of `pixfmt_alpha_blend_rgba::copy_or_blend_pix` (no cover).
-    #[inline]
-    fn copy_or_blend(p: &mut [u8], c: &Rgba8) {
-        if c.a != 0 {
-            if c.a == 255 {
-                p[0] = c.r;
-                p[1] = c.g;
-                p[2] = c.b;
-                p[3] = c.a;
-            } else {
-                Self::blend_pix_pre(p, c.r, c.g, c.b, c.a);
-            }
-        }
-    }
 }
 
-impl<'a> PixelFormat for PixfmtRgba32Pre<'a> {
+impl<'a, O: RgbaOrder, RB: RenderingBufferMut> PixelFormat for PixfmtAlphaBlendRgbaPre<'a, O, RB> {
     type ColorType = Rgba8;
 
     fn width(&self) -> u32 {
@@ -473,7 +455,7 @@ impl<'a> PixelFormat for PixfmtRgba32Pre<'a> {
     }
 
     fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba8, cover: CoverType) {
-        let row = self.raw.row_mut(y);
+        let row = self.raw.row_mut(x, y, 1);
         let off = x as usize * BPP;
         Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cover);
     }
@@ -482,20 +464,16 @@ impl<'a> PixelFormat for PixfmtRgba32Pre<'a> {
         if c.a == 0 {
             return;
         }
-        let row = self.raw.row_mut(y);
+        let row = self.raw.row_mut(x, y, len);
+        let span = &mut row[x as usize * BPP..(x as usize + len as usize) * BPP];
         if c.a == 255 && cover == 255 {
-            for i in 0..len as usize {
-                let off = (x as usize + i) * BPP;
-                row[off] = c.r;
-                row[off + 1] = c.g;
-                row[off + 2] = c.b;
-                row[off + 3] = c.a;
+            let pat = pixel_bytes::<O>(c);
+            for px in span.chunks_exact_mut(BPP) {
+                px.copy_from_slice(&pat);
             }
         } else {
-            for i in 0..len as usize {
-                let off = (x as usize + i) * BPP;
-                Self::blend_pix_cover(&mut row[off..off + BPP], c, cover);
-            }
+            let src = SpanSrc::Solid(pixel_bytes::<O>(c));
+            blend_span(BlendOp::Prelerp, span, src, SpanCover::Uniform(cover), O::A);
         }
     }
 
@@ -503,17 +481,154 @@ impl<'a> PixelFormat for PixfmtRgba32Pre<'a> {
         if c.a == 0 {
             return;
         }
-        let row = self.raw.row_mut(y);
+        let row = self.raw.row_mut(x, y, len);
+        let n = (len as usize).min(covers.len());
+        let span = &mut row[x as usize * BPP..(x as usize + n) * BPP];
+        let src = SpanSrc::Solid(pixel_bytes::<O>(c));
+        blend_span(BlendOp::Prelerp, span, src, SpanCover::Covers(covers), O::A);
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba8],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.raw.row_mut(x, y, len);
+        let n = (len as usize).min(colors.len());
+        let span = &mut row[x as usize * BPP..(x as usize + n) * BPP];
+        let cover = if covers.is_empty() {
+            SpanCover::Uniform(cover)
+        } else {
+            SpanCover::Covers(&covers[..n])
+        };
+        let colors = colors.iter().map(pixel_bytes::<O>);
+        blend_colors(BlendOp::Prelerp, span, colors, cover, O::A);
+    }
+}
+
+// ============================================================================
+// PixfmtAlphaBlendSrgba — sRGB-encoded RGBA, blended in linear light
+// ============================================================================
+
+/// Effective 16-bit alpha of an 8-bit alpha scaled by an 8-bit cover.
+///
+/// Alpha and coverage are both linear quantities, so they are combined
+/// directly (never through the sRGB curve) and kept at 16 bits, so small
+/// coverage on a faint color does not round to zero.
+#[inline]
+pub(crate) fn srgb_alpha(a: u8, cover: CoverType) -> u16 {
+    Rgba16::mult_cover((a as u16) << 8 | a as u16, cover)
+}
+
+/// Blend sRGB channel `c` over `p` in linear light by 16-bit `alpha`.
+#[inline]
+pub(crate) fn srgb_lerp(lut: &SrgbLut, p: u8, c: u8, alpha: u16) -> u8 {
+    lut.inv(Rgba16::lerp(lut.dir(p), lut.dir(c), alpha))
+}
+
+/// Pixel format for non-premultiplied, sRGB-encoded 32-bit RGBA in any
+/// channel order (4 bytes per pixel).
+///
+/// Counterpart of the C++ `pixfmt_srgba32` family. The buffer and the
+/// source colors hold sRGB bytes, but the color channels are decoded to
+/// 16-bit linear light through [`SrgbLut`] before blending and encoded
+/// again on store; this avoids the dark fringes and muddy midpoints of
+/// blending encoded values. Alpha is linear and blends as in
+/// [`PixfmtRgba32`]. Copies of opaque colors are stored unchanged.
+pub struct PixfmtAlphaBlendSrgba<'a, O, RB = RowAccessor> {
+    raw: RgbaRaw<'a, O, RB>,
+}
+
+/// sRGB RGBA byte order. Port of C++ `pixfmt_srgba32`.
+pub type PixfmtSrgba32<'a, RB = RowAccessor> = PixfmtAlphaBlendSrgba<'a, OrderRgba, RB>;
+/// sRGB BGRA byte order. Port of C++ `pixfmt_sbgra32`.
+pub type PixfmtSbgra32<'a, RB = RowAccessor> = PixfmtAlphaBlendSrgba<'a, OrderBgra, RB>;
+/// sRGB ARGB byte order. Port of C++ `pixfmt_sargb32`.
+pub type PixfmtSargb32<'a, RB = RowAccessor> = PixfmtAlphaBlendSrgba<'a, OrderArgb, RB>;
+/// sRGB ABGR byte order. Port of C++ `pixfmt_sabgr32`.
+pub type PixfmtSabgr32<'a, RB = RowAccessor> = PixfmtAlphaBlendSrgba<'a, OrderAbgr, RB>;
+
+impl<'a, O: RgbaOrder, RB: RenderingBufferMut> PixfmtAlphaBlendSrgba<'a, O, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: RgbaRaw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba8) {
+        self.raw.clear(c);
+    }
+
+    /// Copy `c` (alpha forced to 255) when `alpha` is full, otherwise blend
+    /// it in linear light.
+    #[inline]
+    fn copy_or_blend(lut: &SrgbLut, p: &mut [u8], c: &Rgba8, alpha: u16) {
+        if alpha == 0xFFFF {
+            p[O::R] = c.r;
+            p[O::G] = c.g;
+            p[O::B] = c.b;
+            p[O::A] = 255;
+        } else if alpha > 0 {
+            p[O::R] = srgb_lerp(lut, p[O::R], c.r, alpha);
+            p[O::G] = srgb_lerp(lut, p[O::G], c.g, alpha);
+            p[O::B] = srgb_lerp(lut, p[O::B], c.b, alpha);
+            let a = Rgba16::lerp((p[O::A] as u16) << 8 | p[O::A] as u16, 0xFFFF, alpha);
+            p[O::A] = ((a as u32 + 128) / 257) as u8;
+        }
+    }
+}
+
+impl<'a, O: RgbaOrder, RB: RenderingBufferMut> PixelFormat for PixfmtAlphaBlendSrgba<'a, O, RB> {
+    type ColorType = Rgba8;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba8 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba8) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba8, cover: CoverType) {
+        let lut = SrgbLut::get();
+        let row = self.raw.row_mut(x, y, 1);
+        let off = x as usize * BPP;
+        Self::copy_or_blend(lut, &mut row[off..off + BPP], c, srgb_alpha(c.a, cover));
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, cover: CoverType) {
+        let lut = SrgbLut::get();
+        let row = self.raw.row_mut(x, y, len);
+        let alpha = srgb_alpha(c.a, cover);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(lut, &mut row[off..off + BPP], c, alpha);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba8, covers: &[CoverType]) {
+        let lut = SrgbLut::get();
+        let row = self.raw.row_mut(x, y, len);
         for (i, &cov) in covers.iter().enumerate().take(len as usize) {
             let off = (x as usize + i) * BPP;
-            if c.a == 255 && cov == 255 {
-                row[off] = c.r;
-                row[off + 1] = c.g;
-                row[off + 2] = c.b;
-                row[off + 3] = c.a;
-            } else {
-                Self::blend_pix_cover(&mut row[off..off + BPP], c, cov);
-            }
+            Self::copy_or_blend(lut, &mut row[off..off + BPP], c, srgb_alpha(c.a, cov));
         }
     }
 
@@ -526,22 +641,12 @@ impl<'a> PixelFormat for PixfmtRgba32Pre<'a> {
         covers: &[CoverType],
         cover: CoverType,
     ) {
-        let row = self.raw.row_mut(y);
-        if !covers.is_empty() {
-            for i in 0..len as usize {
-                let off = (x as usize + i) * BPP;
-                Self::copy_or_blend_cover(&mut row[off..off + BPP], &colors[i], covers[i]);
-            }
-        } else if cover == 255 {
-            for (i, c) in colors.iter().enumerate().take(len as usize) {
-                let off = (x as usize + i) * BPP;
-                Self::copy_or_blend(&mut row[off..off + BPP], c);
-            }
-        } else {
-            for (i, c) in colors.iter().enumerate().take(len as usize) {
-                let off = (x as usize + i) * BPP;
-                Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cover);
-            }
+        let lut = SrgbLut::get();
+        let row = self.raw.row_mut(x, y, len);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            Self::copy_or_blend(lut, &mut row[off..off + BPP], c, srgb_alpha(c.a, cov));
         }
     }
 }
@@ -880,4 +985,80 @@ mod tests {
         let p = pf.pixel(0, 0);
         assert_eq!((p.r, p.g, p.b, p.a), (140, 70, 35, 255));
     }
+
+    #[test]
+    fn test_bgra_byte_layout() {
+        let (buf, mut ra) = make_buffer(2, 1);
+        let mut pf = PixfmtBgra32::new(&mut ra);
+        pf.copy_pixel(0, 0, &Rgba8::new(10, 20, 30, 40));
+        pf.blend_pixel(1, 0, &Rgba8::new(200, 100, 50, 255), 255);
+        assert_eq!(&buf[0..4], &[30, 20, 10, 40]);
+        assert_eq!(&buf[4..8], &[50, 100, 200, 255]);
+        assert_eq!(pf.pixel(0, 0), Rgba8::new(10, 20, 30, 40));
+    }
+
+    #[test]
+    fn test_orders_blend_identically() {
+        // Blending must not depend on where the channels live in memory.
+        let bg = Rgba8::new(40, 80, 120, 200);
+        let c = Rgba8::new(250, 5, 100, 150);
+        let covers = [255, 128, 7, 0];
+
+        let (_b1, mut ra1) = make_buffer(4, 1);
+        let mut rgba = PixfmtRgba32::new(&mut ra1);
+        rgba.clear(&bg);
+        rgba.blend_solid_hspan(0, 0, 4, &c, &covers);
+
+        let (_b2, mut ra2) = make_buffer(4, 1);
+        let mut argb = PixfmtArgb32::new(&mut ra2);
+        argb.clear(&bg);
+        argb.blend_solid_hspan(0, 0, 4, &c, &covers);
+
+        let (_b3, mut ra3) = make_buffer(4, 1);
+        let mut abgr = PixfmtAbgr32Pre::new(&mut ra3);
+        abgr.clear(&bg);
+        abgr.blend_solid_hspan(0, 0, 4, &c, &covers);
+
+        let (_b4, mut ra4) = make_buffer(4, 1);
+        let mut rgba_pre = PixfmtRgba32Pre::new(&mut ra4);
+        rgba_pre.clear(&bg);
+        rgba_pre.blend_solid_hspan(0, 0, 4, &c, &covers);
+
+        for x in 0..4 {
+            assert_eq!(argb.pixel(x, 0), rgba.pixel(x, 0));
+            assert_eq!(abgr.pixel(x, 0), rgba_pre.pixel(x, 0));
+        }
+    }
+
+    #[test]
+    fn test_srgb_blend_is_linear() {
+        let (_buf, mut ra) = make_buffer(4, 1);
+        let mut pf = PixfmtSrgba32::new(&mut ra);
+        pf.clear(&Rgba8::new(0, 0, 0, 255));
+        // Half-covered white over black is 50% linear light: sRGB 188, not 128.
+        pf.blend_pixel(0, 0, &Rgba8::new(255, 255, 255, 255), 128);
+        assert_eq!(pf.pixel(0, 0), Rgba8::new(188, 188, 188, 255));
+        // Same for alpha instead of coverage.
+        pf.blend_pixel(1, 0, &Rgba8::new(255, 255, 255, 128), 255);
+        assert_eq!(pf.pixel(1, 0), Rgba8::new(188, 188, 188, 255));
+        // Opaque full-cover colors are stored unchanged.
+        pf.blend_pixel(2, 0, &Rgba8::new(12, 34, 56, 255), 255);
+        assert_eq!(pf.pixel(2, 0), Rgba8::new(12, 34, 56, 255));
+    }
+
+    #[test]
+    fn test_srgb_small_alpha_times_cover_not_lost() {
+        let (_b1, mut ra1) = make_buffer(1, 1);
+        let (_b2, mut ra2) = make_buffer(1, 1);
+        let mut srgb = PixfmtSrgba32::new(&mut ra1);
+        let mut plain = PixfmtRgba32::new(&mut ra2);
+        srgb.clear(&Rgba8::new(0, 0, 0, 255));
+        plain.clear(&Rgba8::new(0, 0, 0, 255));
+        // alpha 1 at cover 100 rounds to zero in 8-bit but not in 16-bit.
+        let c = Rgba8::new(255, 255, 255, 1);
+        srgb.blend_pixel(0, 0, &c, 100);
+        plain.blend_pixel(0, 0, &c, 100);
+        assert_eq!(plain.pixel(0, 0).r, 0);
+        assert!(srgb.pixel(0, 0).r > 0);
+    }
 }
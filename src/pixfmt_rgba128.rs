@@ -0,0 +1,656 @@
+//! RGBA128 pixel formats (32-bit float per channel).
+//!
+//! Port of the `rgba32` instantiations in `agg_pixfmt_rgba.h`
+//! (`pixfmt_rgba128`, `pixfmt_rgba128_pre` and the `comp_op_adaptor_rgba`
+//! variant) — pixel formats that read and write RGBA128 pixels (16 bytes per
+//! pixel) in linear light.
+//!
+//! Each channel is a native-endian `f32`. Blending never clamps, so values
+//! above 1.0 accumulate without loss; convert with [`Rgba32::to_rgba8`] when
+//! the image is finished. The rendering buffer is still addressed in bytes,
+//! so the stride must be at least `width * 16`.
+
+use crate::basics::CoverType;
+use crate::color::Rgba32;
+use crate::comp_op::{comp_op_blend_float, CompOp};
+use crate::pixfmt_rgba::PixelFormat;
+use crate::rendering_buffer::{assert_row_len, RenderingBufferMut, RowAccessor};
+
+/// Bytes per pixel for RGBA128.
+const BPP: usize = 16;
+
+/// Read the four channels of the pixel starting at `p[0]`.
+#[inline]
+pub(crate) fn get_pix(p: &[u8]) -> [f32; 4] {
+    let ch = |i: usize| f32::from_ne_bytes([p[i], p[i + 1], p[i + 2], p[i + 3]]);
+    [ch(0), ch(4), ch(8), ch(12)]
+}
+
+/// Write four channels to the pixel starting at `p[0]`.
+#[inline]
+fn set_pix(p: &mut [u8], v: [f32; 4]) {
+    p[0..4].copy_from_slice(&v[0].to_ne_bytes());
+    p[4..8].copy_from_slice(&v[1].to_ne_bytes());
+    p[8..12].copy_from_slice(&v[2].to_ne_bytes());
+    p[12..16].copy_from_slice(&v[3].to_ne_bytes());
+}
+
+// ============================================================================
+// Rgba128Raw — shared non-blend plumbing for the RGBA128 pixel formats
+// ============================================================================
+
+/// Shared non-blend plumbing for the RGBA128 pixel formats.
+struct Rgba128Raw<'a, RB> {
+    rbuf: &'a mut RB,
+}
+
+impl<'a, RB: RenderingBufferMut> Rgba128Raw<'a, RB> {
+    fn new(rbuf: &'a mut RB) -> Self {
+        assert_row_len(rbuf, BPP);
+        Self { rbuf }
+    }
+
+    fn width(&self) -> u32 {
+        self.rbuf.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.rbuf.height()
+    }
+
+    #[inline]
+    fn row(&self, y: i32) -> &[u8] {
+        unsafe {
+            let ptr = self.rbuf.row_ptr_const(y);
+            std::slice::from_raw_parts(ptr, (self.rbuf.width() as usize) * BPP)
+        }
+    }
+
+    #[inline]
+    fn row_mut(&mut self, y: i32) -> &mut [u8] {
+        unsafe {
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * BPP)
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    fn clear(&mut self, c: &Rgba32) {
+        let h = self.height();
+        for y in 0..h {
+            let row = self.row_mut(y as i32);
+            for px in row.chunks_exact_mut(BPP) {
+                set_pix(px, [c.r, c.g, c.b, c.a]);
+            }
+        }
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba32 {
+        let off = x as usize * BPP;
+        let [r, g, b, a] = get_pix(&self.row(y)[off..off + BPP]);
+        Rgba32 { r, g, b, a }
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba32) {
+        let off = x as usize * BPP;
+        set_pix(&mut self.row_mut(y)[off..off + BPP], [c.r, c.g, c.b, c.a]);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba32) {
+        let row = self.row_mut(y);
+        let start = x as usize * BPP;
+        for px in row[start..start + len as usize * BPP].chunks_exact_mut(BPP) {
+            set_pix(px, [c.r, c.g, c.b, c.a]);
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtRgba128 — non-premultiplied RGBA, f32 per channel
+// ============================================================================
+
+/// Pixel format for non-premultiplied RGBA128 (16 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_rgba<blender_rgba<rgba32, order_rgba>, rendering_buf>`
+/// (a.k.a. `pixfmt_rgba128`). Component order: R=0, G=1, B=2, A=3.
+pub struct PixfmtRgba128<'a, RB = RowAccessor> {
+    raw: Rgba128Raw<'a, RB>,
+}
+
+impl<'a, RB: RenderingBufferMut> PixfmtRgba128<'a, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: Rgba128Raw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba32) {
+        self.raw.clear(c);
+    }
+
+    /// Blend a single pixel. Port of `blender_rgba::blend_pix` (no cover).
+    #[inline]
+    fn blend_pix(p: &mut [u8], c: &Rgba32, alpha: f32) {
+        let [r, g, b, a] = get_pix(p);
+        set_pix(
+            p,
+            [
+                Rgba32::lerp(r, c.r, alpha),
+                Rgba32::lerp(g, c.g, alpha),
+                Rgba32::lerp(b, c.b, alpha),
+                Rgba32::prelerp(a, alpha, alpha),
+            ],
+        );
+    }
+
+    /// Port of `pixfmt_alpha_blend_rgba::copy_or_blend_pix`.
+    #[inline]
+    fn copy_or_blend(p: &mut [u8], c: &Rgba32, cover: CoverType) {
+        if !c.is_transparent() {
+            if c.is_opaque() && cover == 255 {
+                set_pix(p, [c.r, c.g, c.b, c.a]);
+            } else {
+                Self::blend_pix(p, c, Rgba32::mult_cover(c.a, cover));
+            }
+        }
+    }
+}
+
+impl<'a, RB: RenderingBufferMut> PixelFormat for PixfmtRgba128<'a, RB> {
+    type ColorType = Rgba32;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba32 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba32) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba32) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba32, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        let off = x as usize * BPP;
+        Self::copy_or_blend(&mut row[off..off + BPP], c, cover);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba32, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(&mut row[off..off + BPP], c, cover);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba32, covers: &[CoverType]) {
+        let row = self.raw.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(&mut row[off..off + BPP], c, cov);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba32],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.raw.row_mut(y);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            Self::copy_or_blend(&mut row[off..off + BPP], c, cov);
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtRgba128Pre — premultiplied RGBA, f32 per channel
+// ============================================================================
+
+/// Pixel format for premultiplied RGBA128 (16 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_rgba<blender_rgba_pre<rgba32, order_rgba>, rendering_buf>`
+/// (a.k.a. `pixfmt_rgba128_pre`). Component order: R=0, G=1, B=2, A=3.
+///
+/// Source colors are expected to be premultiplied. This is the natural
+/// format for HDR accumulation: src-over is `d = s + d * (1 - sa)`, with no
+/// rounding and no upper bound on the color channels.
+pub struct PixfmtRgba128Pre<'a, RB = RowAccessor> {
+    raw: Rgba128Raw<'a, RB>,
+}
+
+impl<'a, RB: RenderingBufferMut> PixfmtRgba128Pre<'a, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: Rgba128Raw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba32) {
+        self.raw.clear(c);
+    }
+
+    /// Premultiplied blend folding a coverage value into the color.
+    /// Port of `blender_rgba_pre::blend_pix` (with cover).
+    #[inline]
+    fn blend_pix(p: &mut [u8], c: &Rgba32, cover: CoverType) {
+        let [r, g, b, a] = get_pix(p);
+        let alpha = Rgba32::mult_cover(c.a, cover);
+        set_pix(
+            p,
+            [
+                Rgba32::prelerp(r, Rgba32::mult_cover(c.r, cover), alpha),
+                Rgba32::prelerp(g, Rgba32::mult_cover(c.g, cover), alpha),
+                Rgba32::prelerp(b, Rgba32::mult_cover(c.b, cover), alpha),
+                Rgba32::prelerp(a, alpha, alpha),
+            ],
+        );
+    }
+
+    /// Port of `pixfmt_alpha_blend_rgba::copy_or_blend_pix`.
+    #[inline]
+    fn copy_or_blend(p: &mut [u8], c: &Rgba32, cover: CoverType) {
+        if !c.is_transparent() {
+            if c.is_opaque() && cover == 255 {
+                set_pix(p, [c.r, c.g, c.b, c.a]);
+            } else {
+                Self::blend_pix(p, c, cover);
+            }
+        }
+    }
+}
+
+impl<'a, RB: RenderingBufferMut> PixelFormat for PixfmtRgba128Pre<'a, RB> {
+    type ColorType = Rgba32;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba32 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba32) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba32) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba32, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        let off = x as usize * BPP;
+        Self::copy_or_blend(&mut row[off..off + BPP], c, cover);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba32, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(&mut row[off..off + BPP], c, cover);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba32, covers: &[CoverType]) {
+        let row = self.raw.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(&mut row[off..off + BPP], c, cov);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba32],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.raw.row_mut(y);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            Self::copy_or_blend(&mut row[off..off + BPP], c, cov);
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtRgba128CompOp — premultiplied RGBA128 with SVG compositing
+// ============================================================================
+
+/// Premultiplied RGBA128 pixel format with runtime-selectable SVG
+/// compositing operations.
+///
+/// Port of C++ `pixfmt_custom_blend_rgba<comp_op_adaptor_rgba<rgba32, order_rgba>, rendering_buffer>`.
+/// Like [`PixfmtRgba32CompOp`](crate::comp_op::PixfmtRgba32CompOp), source
+/// colors are non-premultiplied and the buffer is premultiplied. Results are
+/// not clipped, so `Plus` and friends can push channels above 1.0.
+pub struct PixfmtRgba128CompOp<'a, RB = RowAccessor> {
+    raw: Rgba128Raw<'a, RB>,
+    comp_op: CompOp,
+}
+
+impl<'a, RB: RenderingBufferMut> PixfmtRgba128CompOp<'a, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self::new_with_op(rbuf, CompOp::SrcOver)
+    }
+
+    pub fn new_with_op(rbuf: &'a mut RB, op: CompOp) -> Self {
+        Self {
+            raw: Rgba128Raw::new(rbuf),
+            comp_op: op,
+        }
+    }
+
+    pub fn comp_op(&self) -> CompOp {
+        self.comp_op
+    }
+
+    pub fn set_comp_op(&mut self, op: CompOp) {
+        self.comp_op = op;
+    }
+
+    /// Clear the entire buffer to a solid (premultiplied) color.
+    pub fn clear(&mut self, c: &Rgba32) {
+        self.raw.clear(c);
+    }
+
+    /// Premultiply the source (as `comp_op_adaptor_rgba` does) and composite
+    /// it onto the pixel at `p[0]`.
+    #[inline]
+    fn blend_pix(op: CompOp, p: &mut [u8], c: &Rgba32, cover: CoverType) {
+        let s = [
+            (c.r * c.a) as f64,
+            (c.g * c.a) as f64,
+            (c.b * c.a) as f64,
+            c.a as f64,
+        ];
+        let [r, g, b, a] = get_pix(p);
+        let mut d = [r as f64, g as f64, b as f64, a as f64];
+        comp_op_blend_float(op, &mut d, &s, cover as f64 / 255.0);
+        set_pix(p, [d[0] as f32, d[1] as f32, d[2] as f32, d[3] as f32]);
+    }
+}
+
+impl<'a, RB: RenderingBufferMut> PixelFormat for PixfmtRgba128CompOp<'a, RB> {
+    type ColorType = Rgba32;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba32 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba32) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba32) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba32, cover: CoverType) {
+        let op = self.comp_op;
+        let row = self.raw.row_mut(y);
+        let off = x as usize * BPP;
+        Self::blend_pix(op, &mut row[off..off + BPP], c, cover);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba32, cover: CoverType) {
+        let op = self.comp_op;
+        let row = self.raw.row_mut(y);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::blend_pix(op, &mut row[off..off + BPP], c, cover);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba32, covers: &[CoverType]) {
+        let op = self.comp_op;
+        let row = self.raw.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            Self::blend_pix(op, &mut row[off..off + BPP], c, cov);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba32],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let op = self.comp_op;
+        let row = self.raw.row_mut(y);
+        for (i, c) in colors.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let cov = if covers.is_empty() { cover } else { covers[i] };
+            Self::blend_pix(op, &mut row[off..off + BPP], c, cov);
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgba8;
+    use crate::gradient_lut::GradientLut;
+    use crate::image_accessors::ImageAccessorClone;
+    use crate::rasterizer_scanline_aa::RasterizerScanlineAa;
+    use crate::renderer_base::RendererBase;
+    use crate::renderer_scanline::{render_scanlines_aa, render_scanlines_aa_solid};
+    use crate::scanline_u::ScanlineU8;
+    use crate::span_allocator::SpanAllocator;
+    use crate::span_gradient::{GradientX, SpanGradient};
+    use crate::span_image_filter_rgba::SpanImageFilterRgba128Nn;
+    use crate::span_interpolator_linear::SpanInterpolatorLinear;
+    use crate::trans_affine::TransAffine;
+
+    fn make_buffer(w: u32, h: u32) -> (Vec<u8>, RowAccessor) {
+        let stride = (w * BPP as u32) as i32;
+        let buf = vec![0u8; (h * w * BPP as u32) as usize];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, w, h, stride);
+        }
+        (buf, ra)
+    }
+
+    fn fill_rect(ras: &mut RasterizerScanlineAa, x1: f64, y1: f64, x2: f64, y2: f64) {
+        ras.reset();
+        ras.move_to_d(x1, y1);
+        ras.line_to_d(x2, y1);
+        ras.line_to_d(x2, y2);
+        ras.line_to_d(x1, y2);
+    }
+
+    #[test]
+    fn test_copy_pixel_roundtrip() {
+        let (buf, mut ra) = make_buffer(4, 2);
+        let mut pf = PixfmtRgba128::new(&mut ra);
+        assert_eq!(pf.width(), 4);
+        let c = Rgba32::new(0.25, 3.5, -0.125, 1.0);
+        pf.copy_pixel(1, 1, &c);
+        assert_eq!(pf.pixel(1, 1), c);
+        assert_eq!(pf.pixel(0, 1), Rgba32::no_color());
+        let off = (4 + 1) * BPP;
+        assert_eq!(&buf[off + 4..off + 8], &3.5f32.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_blend_pixel_half_cover() {
+        let (_buf, mut ra) = make_buffer(2, 1);
+        let mut pf = PixfmtRgba128::new(&mut ra);
+        pf.clear(&Rgba32::new(0.0, 0.0, 0.0, 1.0));
+        pf.blend_pixel(0, 0, &Rgba32::new(1.0, 0.5, 0.0, 1.0), 51);
+        let p = pf.pixel(0, 0);
+        assert!((p.r - 0.2).abs() < 1e-6);
+        assert!((p.g - 0.1).abs() < 1e-6);
+        assert_eq!(p.a, 1.0);
+    }
+
+    #[test]
+    fn test_pre_accumulates_hdr_without_clamping() {
+        let (_buf, mut ra) = make_buffer(1, 1);
+        let mut pf = PixfmtRgba128Pre::new(&mut ra);
+        pf.clear(&Rgba32::new(0.8, 0.8, 0.8, 1.0));
+        // A bright half-transparent light source
+        let light = Rgba32::new(4.0, 2.0, 1.0, 0.5);
+        pf.blend_pixel(0, 0, &light, 255);
+        let p = pf.pixel(0, 0);
+        assert!((p.r - 4.4).abs() < 1e-6);
+        assert!((p.g - 2.4).abs() < 1e-6);
+        assert!((p.b - 1.4).abs() < 1e-6);
+        assert!((p.a - 1.0).abs() < 1e-6);
+        assert_eq!(p.to_rgba8(), Rgba8::new(255, 255, 255, 255));
+    }
+
+    #[test]
+    fn test_comp_op_plus_exceeds_one() {
+        let (_buf, mut ra) = make_buffer(3, 1);
+        let mut pf = PixfmtRgba128CompOp::new_with_op(&mut ra, CompOp::Plus);
+        assert_eq!(pf.comp_op(), CompOp::Plus);
+        pf.clear(&Rgba32::new(0.75, 0.5, 0.0, 1.0));
+        let c = [Rgba32::new(0.75, 1.0, 0.25, 1.0); 3];
+        pf.blend_color_hspan(0, 0, 3, &c, &[], 255);
+        let p = pf.pixel(2, 0);
+        assert_eq!(p, Rgba32::new(1.5, 1.5, 0.25, 2.0));
+    }
+
+    #[test]
+    fn test_comp_op_multiply_and_src_over() {
+        let (_buf, mut ra) = make_buffer(2, 1);
+        let mut pf = PixfmtRgba128CompOp::new(&mut ra);
+        pf.clear(&Rgba32::new(0.5, 0.5, 0.5, 1.0));
+        pf.blend_pixel(0, 0, &Rgba32::new(1.0, 0.0, 0.0, 0.5), 255);
+        let p = pf.pixel(0, 0);
+        assert!((p.r - 0.75).abs() < 1e-6 && (p.g - 0.25).abs() < 1e-6);
+        pf.set_comp_op(CompOp::Multiply);
+        pf.blend_pixel(1, 0, &Rgba32::new(2.0, 0.5, 0.0, 1.0), 255);
+        assert_eq!(pf.pixel(1, 0), Rgba32::new(1.0, 0.25, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_comp_op_transparent_source_is_noop() {
+        let (_buf, mut ra) = make_buffer(1, 1);
+        let mut pf = PixfmtRgba128CompOp::new_with_op(&mut ra, CompOp::ColorDodge);
+        let bg = Rgba32::new(0.0, 0.0, 0.0, 0.0);
+        pf.clear(&bg);
+        pf.blend_pixel(0, 0, &Rgba32::new(1.0, 1.0, 1.0, 0.0), 255);
+        assert_eq!(pf.pixel(0, 0), bg);
+    }
+
+    #[test]
+    fn test_render_solid_triangle() {
+        let (_buf, mut ra) = make_buffer(100, 100);
+        let mut pf = PixfmtRgba128::new(&mut ra);
+        pf.clear(&Rgba32::new(1.0, 1.0, 1.0, 1.0));
+        let mut ren = RendererBase::new(pf);
+        let mut ras = RasterizerScanlineAa::new();
+        let mut sl = ScanlineU8::new();
+        ras.move_to_d(20.0, 20.0);
+        ras.line_to_d(80.0, 20.0);
+        ras.line_to_d(50.0, 80.0);
+
+        let red = Rgba32::new(1.0, 0.0, 0.0, 1.0);
+        render_scanlines_aa_solid(&mut ras, &mut sl, &mut ren, &red);
+
+        assert_eq!(ren.ren().pixel(50, 40), red);
+        assert_eq!(ren.ren().pixel(0, 0), Rgba32::new(1.0, 1.0, 1.0, 1.0));
+        let edge = ren.ren().pixel(20, 21);
+        assert!(edge.g > 0.0 && edge.g < 1.0);
+    }
+
+    #[test]
+    fn test_render_hdr_gradient() {
+        let (_buf, mut ra) = make_buffer(64, 1);
+        let pf = PixfmtRgba128Pre::new(&mut ra);
+        let mut ren = RendererBase::new(pf);
+        let mut ras = RasterizerScanlineAa::new();
+        let mut sl = ScanlineU8::new();
+        fill_rect(&mut ras, 0.0, 0.0, 64.0, 1.0);
+
+        let mut lut: GradientLut<Rgba32> = GradientLut::new_for(64);
+        lut.add_color(0.0, Rgba32::new(0.0, 0.0, 0.0, 1.0));
+        lut.add_color(1.0, Rgba32::new(8.0, 8.0, 8.0, 1.0));
+        lut.build_lut();
+
+        let interp = SpanInterpolatorLinear::new(TransAffine::new());
+        let mut sg = SpanGradient::new(interp, GradientX, &lut, 0.0, 64.0);
+        let mut alloc = SpanAllocator::<Rgba32>::new();
+        render_scanlines_aa(&mut ras, &mut sl, &mut ren, &mut alloc, &mut sg);
+
+        let levels: Vec<f32> = (0..64).map(|x| ren.ren().pixel(x, 0).r).collect();
+        assert!(levels.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(levels[0], 0.0);
+        assert!((levels[63] - 8.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_render_float_image_span() {
+        // 2x1 source image holding HDR values
+        let (_src_buf, mut src_ra) = make_buffer(2, 1);
+        {
+            let mut src = PixfmtRgba128::new(&mut src_ra);
+            src.copy_pixel(0, 0, &Rgba32::new(5.0, 0.0, 0.0, 1.0));
+            src.copy_pixel(1, 0, &Rgba32::new(0.0, 0.0, 10.0, 1.0));
+        }
+
+        let (_buf, mut ra) = make_buffer(4, 1);
+        let pf = PixfmtRgba128::new(&mut ra);
+        let mut ren = RendererBase::new(pf);
+        let mut ras = RasterizerScanlineAa::new();
+        let mut sl = ScanlineU8::new();
+        fill_rect(&mut ras, 0.0, 0.0, 4.0, 1.0);
+
+        let mut source = ImageAccessorClone::<BPP>::new(&src_ra);
+        let mut interp = SpanInterpolatorLinear::new(TransAffine::new_scaling(0.5, 1.0));
+        let mut sg = SpanImageFilterRgba128Nn::new(&mut source, &mut interp);
+        let mut alloc = SpanAllocator::<Rgba32>::new();
+        render_scanlines_aa(&mut ras, &mut sl, &mut ren, &mut alloc, &mut sg);
+
+        assert_eq!(ren.ren().pixel(0, 0), Rgba32::new(5.0, 0.0, 0.0, 1.0));
+        assert_eq!(ren.ren().pixel(3, 0), Rgba32::new(0.0, 0.0, 10.0, 1.0));
+    }
+}
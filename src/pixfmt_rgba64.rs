@@ -0,0 +1,719 @@
+//! RGBA64 pixel formats (16 bits per channel).
+//!
+//! Port of the `rgba16` instantiations in `agg_pixfmt_rgba.h`
+//! (`pixfmt_rgba64` and `pixfmt_rgba64_pre`) — pixel formats that read and
+//! write RGBA64 pixels (8 bytes per pixel) with alpha blending.
+//!
+//! Each channel is a native-endian `u16`, matching the C++ layout where a
+//! pixel is four `int16u` values. The rendering buffer is still addressed in
+//! bytes, so the stride must be at least `width * 8`.
+
+use crate::basics::CoverType;
+use crate::color::Rgba16;
+use crate::pixfmt_rgba::PixelFormat;
+use crate::rendering_buffer::{assert_row_len, RenderingBufferMut, RowAccessor};
+
+/// Bytes per pixel for RGBA64.
+const BPP: usize = 8;
+
+/// Full channel value (`0xFFFF`).
+const FULL: u16 = Rgba16::BASE_MASK as u16;
+
+/// Read the four channels of the pixel starting at `p[0]`.
+#[inline]
+fn get_pix(p: &[u8]) -> [u16; 4] {
+    [
+        u16::from_ne_bytes([p[0], p[1]]),
+        u16::from_ne_bytes([p[2], p[3]]),
+        u16::from_ne_bytes([p[4], p[5]]),
+        u16::from_ne_bytes([p[6], p[7]]),
+    ]
+}
+
+/// Write four channels to the pixel starting at `p[0]`.
+#[inline]
+fn set_pix(p: &mut [u8], v: [u16; 4]) {
+    p[0..2].copy_from_slice(&v[0].to_ne_bytes());
+    p[2..4].copy_from_slice(&v[1].to_ne_bytes());
+    p[4..6].copy_from_slice(&v[2].to_ne_bytes());
+    p[6..8].copy_from_slice(&v[3].to_ne_bytes());
+}
+
+// ============================================================================
+// Rgba64Raw — shared non-blend plumbing for the RGBA64 pixel formats
+// ============================================================================
+
+/// Shared non-blend plumbing for the RGBA64 pixel formats, the 16-bit
+/// counterpart of the RGBA32 `RgbaRaw` helper.
+struct Rgba64Raw<'a, RB> {
+    rbuf: &'a mut RB,
+}
+
+impl<'a, RB: RenderingBufferMut> Rgba64Raw<'a, RB> {
+    fn new(rbuf: &'a mut RB) -> Self {
+        assert_row_len(rbuf, BPP);
+        Self { rbuf }
+    }
+
+    fn width(&self) -> u32 {
+        self.rbuf.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.rbuf.height()
+    }
+
+    #[inline]
+    fn row(&self, y: i32) -> &[u8] {
+        unsafe {
+            let ptr = self.rbuf.row_ptr_const(y);
+            std::slice::from_raw_parts(ptr, (self.rbuf.width() as usize) * BPP)
+        }
+    }
+
+    #[inline]
+    fn row_mut(&mut self, y: i32) -> &mut [u8] {
+        unsafe {
+            let w = self.rbuf.width();
+            let ptr = self.rbuf.row_ptr_mut(0, y, w);
+            std::slice::from_raw_parts_mut(ptr, w as usize * BPP)
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    fn clear(&mut self, c: &Rgba16) {
+        let h = self.height();
+        for y in 0..h {
+            let row = self.row_mut(y as i32);
+            for px in row.chunks_exact_mut(BPP) {
+                set_pix(px, [c.r, c.g, c.b, c.a]);
+            }
+        }
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba16 {
+        let off = x as usize * BPP;
+        let [r, g, b, a] = get_pix(&self.row(y)[off..off + BPP]);
+        Rgba16 { r, g, b, a }
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba16) {
+        let off = x as usize * BPP;
+        set_pix(&mut self.row_mut(y)[off..off + BPP], [c.r, c.g, c.b, c.a]);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16) {
+        let row = self.row_mut(y);
+        let start = x as usize * BPP;
+        for px in row[start..start + len as usize * BPP].chunks_exact_mut(BPP) {
+            set_pix(px, [c.r, c.g, c.b, c.a]);
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtRgba64 — non-premultiplied RGBA, 16 bits per channel
+// ============================================================================
+
+/// Pixel format for non-premultiplied RGBA64 (8 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_rgba<blender_rgba<rgba16, order_rgba>, rendering_buf>`
+/// (a.k.a. `pixfmt_rgba64`). Component order: R=0, G=1, B=2, A=3.
+///
+/// Blending mirrors [`PixfmtRgba32`](crate::pixfmt_rgba::PixfmtRgba32) with
+/// the `Rgba16` arithmetic; coverage values stay 8-bit and are expanded to
+/// 16 bits by `Rgba16::mult_cover`.
+pub struct PixfmtRgba64<'a, RB = RowAccessor> {
+    raw: Rgba64Raw<'a, RB>,
+}
+
+impl<'a, RB: RenderingBufferMut> PixfmtRgba64<'a, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: Rgba64Raw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba16) {
+        self.raw.clear(c);
+    }
+
+    /// Blend a single pixel (internal helper, no bounds checking).
+    #[inline]
+    fn blend_pix(p: &mut [u8], c: &Rgba16, alpha: u16) {
+        let [r, g, b, a] = get_pix(p);
+        set_pix(
+            p,
+            [
+                Rgba16::lerp(r, c.r, alpha),
+                Rgba16::lerp(g, c.g, alpha),
+                Rgba16::lerp(b, c.b, alpha),
+                Rgba16::lerp(a, FULL, alpha),
+            ],
+        );
+    }
+
+    /// Copy the color if `alpha` is full, otherwise blend it.
+    #[inline]
+    fn copy_or_blend(p: &mut [u8], c: &Rgba16, alpha: u16) {
+        if alpha == FULL {
+            set_pix(p, [c.r, c.g, c.b, FULL]);
+        } else if alpha > 0 {
+            Self::blend_pix(p, c, alpha);
+        }
+    }
+}
+
+impl<'a, RB: RenderingBufferMut> PixelFormat for PixfmtRgba64<'a, RB> {
+    type ColorType = Rgba16;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba16 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba16) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba16, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        let off = x as usize * BPP;
+        let alpha = Rgba16::mult_cover(c.a, cover);
+        Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        let alpha = Rgba16::mult_cover(c.a, cover);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba16, covers: &[CoverType]) {
+        let row = self.raw.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            let alpha = Rgba16::mult_cover(c.a, cov);
+            Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba16],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.raw.row_mut(y);
+        if !covers.is_empty() {
+            for i in 0..len as usize {
+                let off = (x as usize + i) * BPP;
+                let c = &colors[i];
+                let alpha = Rgba16::mult_cover(c.a, covers[i]);
+                Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+            }
+        } else if cover == 255 {
+            for (i, c) in colors.iter().enumerate().take(len as usize) {
+                let off = (x as usize + i) * BPP;
+                Self::copy_or_blend(&mut row[off..off + BPP], c, c.a);
+            }
+        } else {
+            for (i, c) in colors.iter().enumerate().take(len as usize) {
+                let off = (x as usize + i) * BPP;
+                let alpha = Rgba16::mult_cover(c.a, cover);
+                Self::copy_or_blend(&mut row[off..off + BPP], c, alpha);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// PixfmtRgba64Pre — premultiplied RGBA, 16 bits per channel
+// ============================================================================
+
+/// Pixel format for premultiplied RGBA64 (8 bytes per pixel).
+///
+/// Port of C++ `pixfmt_alpha_blend_rgba<blender_rgba_pre<rgba16, order_rgba>, rendering_buf>`
+/// (a.k.a. `pixfmt_rgba64_pre`). Component order: R=0, G=1, B=2, A=3.
+///
+/// Source colors are expected to be premultiplied. Repeated compositing into
+/// this format keeps 16 bits of precision per channel, which is the main
+/// reason to prefer it over [`PixfmtRgba32Pre`](crate::pixfmt_rgba::PixfmtRgba32Pre)
+/// for intermediate buffers.
+pub struct PixfmtRgba64Pre<'a, RB = RowAccessor> {
+    raw: Rgba64Raw<'a, RB>,
+}
+
+impl<'a, RB: RenderingBufferMut> PixfmtRgba64Pre<'a, RB> {
+    pub fn new(rbuf: &'a mut RB) -> Self {
+        Self {
+            raw: Rgba64Raw::new(rbuf),
+        }
+    }
+
+    /// Clear the entire buffer to a solid color.
+    pub fn clear(&mut self, c: &Rgba16) {
+        self.raw.clear(c);
+    }
+
+    /// Premultiplied blend of a pixel with already-covered (premultiplied)
+    /// color components. Port of `blender_rgba_pre::blend_pix` (no cover).
+    #[inline]
+    fn blend_pix_pre(p: &mut [u8], cr: u16, cg: u16, cb: u16, alpha: u16) {
+        let [r, g, b, a] = get_pix(p);
+        set_pix(
+            p,
+            [
+                Rgba16::prelerp(r, cr, alpha),
+                Rgba16::prelerp(g, cg, alpha),
+                Rgba16::prelerp(b, cb, alpha),
+                Rgba16::prelerp(a, alpha, alpha),
+            ],
+        );
+    }
+
+    /// Premultiplied blend folding a coverage value into the color.
+    /// Port of `blender_rgba_pre::blend_pix` (with cover).
+    #[inline]
+    fn blend_pix_cover(p: &mut [u8], c: &Rgba16, cover: CoverType) {
+        Self::blend_pix_pre(
+            p,
+            Rgba16::mult_cover(c.r, cover),
+            Rgba16::mult_cover(c.g, cover),
+            Rgba16::mult_cover(c.b, cover),
+            Rgba16::mult_cover(c.a, cover),
+        );
+    }
+
+    /// Port of `pixfmt_alpha_blend_rgba::copy_or_blend_pix` (with cover).
+    #[inline]
+    fn copy_or_blend_cover(p: &mut [u8], c: &Rgba16, cover: CoverType) {
+        if c.a != 0 {
+            if c.a == FULL && cover == 255 {
+                set_pix(p, [c.r, c.g, c.b, c.a]);
+            } else {
+                Self::blend_pix_cover(p, c, cover);
+            }
+        }
+    }
+
+    /// Port of `pixfmt_alpha_blend_rgba::copy_or_blend_pix` (no cover).
+    #[inline]
+    fn copy_or_blend(p: &mut [u8], c: &Rgba16) {
+        if c.a != 0 {
+            if c.a == FULL {
+                set_pix(p, [c.r, c.g, c.b, c.a]);
+            } else {
+                Self::blend_pix_pre(p, c.r, c.g, c.b, c.a);
+            }
+        }
+    }
+}
+
+impl<'a, RB: RenderingBufferMut> PixelFormat for PixfmtRgba64Pre<'a, RB> {
+    type ColorType = Rgba16;
+
+    fn width(&self) -> u32 {
+        self.raw.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.raw.height()
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Rgba16 {
+        self.raw.pixel(x, y)
+    }
+
+    fn copy_pixel(&mut self, x: i32, y: i32, c: &Rgba16) {
+        self.raw.copy_pixel(x, y, c);
+    }
+
+    fn copy_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16) {
+        self.raw.copy_hline(x, y, len, c);
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, c: &Rgba16, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        let off = x as usize * BPP;
+        Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cover);
+    }
+
+    fn blend_hline(&mut self, x: i32, y: i32, len: u32, c: &Rgba16, cover: CoverType) {
+        let row = self.raw.row_mut(y);
+        for i in 0..len as usize {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cover);
+        }
+    }
+
+    fn blend_solid_hspan(&mut self, x: i32, y: i32, len: u32, c: &Rgba16, covers: &[CoverType]) {
+        let row = self.raw.row_mut(y);
+        for (i, &cov) in covers.iter().enumerate().take(len as usize) {
+            let off = (x as usize + i) * BPP;
+            Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cov);
+        }
+    }
+
+    fn blend_color_hspan(
+        &mut self,
+        x: i32,
+        y: i32,
+        len: u32,
+        colors: &[Rgba16],
+        covers: &[CoverType],
+        cover: CoverType,
+    ) {
+        let row = self.raw.row_mut(y);
+        if !covers.is_empty() {
+            for i in 0..len as usize {
+                let off = (x as usize + i) * BPP;
+                Self::copy_or_blend_cover(&mut row[off..off + BPP], &colors[i], covers[i]);
+            }
+        } else if cover == 255 {
+            for (i, c) in colors.iter().enumerate().take(len as usize) {
+                let off = (x as usize + i) * BPP;
+                Self::copy_or_blend(&mut row[off..off + BPP], c);
+            }
+        } else {
+            for (i, c) in colors.iter().enumerate().take(len as usize) {
+                let off = (x as usize + i) * BPP;
+                Self::copy_or_blend_cover(&mut row[off..off + BPP], c, cover);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgba8;
+    use crate::gradient_lut::GradientLut;
+    use crate::rasterizer_scanline_aa::RasterizerScanlineAa;
+    use crate::renderer_base::RendererBase;
+    use crate::renderer_scanline::{render_scanlines_aa, render_scanlines_aa_solid, SpanGenerator};
+    use crate::scanline_u::ScanlineU8;
+    use crate::span_allocator::SpanAllocator;
+    use crate::span_gouraud_rgba::SpanGouraudRgba;
+    use crate::span_gradient::{GradientX, SpanGradient};
+    use crate::span_interpolator_linear::SpanInterpolatorLinear;
+    use crate::trans_affine::TransAffine;
+
+    fn make_buffer(w: u32, h: u32) -> (Vec<u8>, RowAccessor) {
+        let stride = (w * BPP as u32) as i32;
+        let buf = vec![0u8; (h * w * BPP as u32) as usize];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, w, h, stride);
+        }
+        (buf, ra)
+    }
+
+    #[test]
+    fn test_new() {
+        let (_buf, mut ra) = make_buffer(100, 50);
+        let pf = PixfmtRgba64::new(&mut ra);
+        assert_eq!(pf.width(), 100);
+        assert_eq!(pf.height(), 50);
+    }
+
+    #[test]
+    fn test_copy_pixel_roundtrip() {
+        let (_buf, mut ra) = make_buffer(10, 10);
+        let mut pf = PixfmtRgba64::new(&mut ra);
+        let c = Rgba16::new(0x1234, 0xABCD, 0x0001, 0xFFFF);
+        pf.copy_pixel(3, 4, &c);
+        assert_eq!(pf.pixel(3, 4), c);
+        assert_eq!(pf.pixel(2, 4), Rgba16::new(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_native_endian_layout() {
+        let (buf, mut ra) = make_buffer(2, 1);
+        let mut pf = PixfmtRgba64::new(&mut ra);
+        pf.copy_pixel(1, 0, &Rgba16::new(1, 2, 3, 4));
+        assert_eq!(&buf[8..10], &1u16.to_ne_bytes());
+        assert_eq!(&buf[14..16], &4u16.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_clear_and_copy_hline() {
+        let (_buf, mut ra) = make_buffer(20, 5);
+        let mut pf = PixfmtRgba64::new(&mut ra);
+        pf.clear(&Rgba16::new(100, 200, 300, 0xFFFF));
+        let c = Rgba16::new(0xFFFF, 0, 0, 0xFFFF);
+        pf.copy_hline(5, 2, 10, &c);
+        assert_eq!(pf.pixel(4, 2), Rgba16::new(100, 200, 300, 0xFFFF));
+        for x in 5..15 {
+            assert_eq!(pf.pixel(x, 2), c);
+        }
+        assert_eq!(pf.pixel(15, 2), Rgba16::new(100, 200, 300, 0xFFFF));
+    }
+
+    #[test]
+    fn test_blend_pixel_opaque() {
+        let (_buf, mut ra) = make_buffer(10, 10);
+        let mut pf = PixfmtRgba64::new(&mut ra);
+        let c = Rgba16::new(1000, 2000, 3000, 0xFFFF);
+        pf.blend_pixel(1, 1, &c, 255);
+        assert_eq!(pf.pixel(1, 1), c);
+    }
+
+    #[test]
+    fn test_blend_pixel_half_keeps_16bit_precision() {
+        let (_buf, mut ra) = make_buffer(10, 10);
+        let mut pf = PixfmtRgba64::new(&mut ra);
+        pf.clear(&Rgba16::new(0, 0, 0, 0xFFFF));
+        // 50% of 1001 over black lands between two 8-bit steps
+        let c = Rgba16::new(1001, 0, 0, 0x8000);
+        pf.blend_pixel(0, 0, &c, 255);
+        let p = pf.pixel(0, 0);
+        assert_eq!(p.r, 501);
+        assert_eq!(p.a, 0xFFFF);
+    }
+
+    #[test]
+    fn test_blend_zero_cover_is_noop() {
+        let (_buf, mut ra) = make_buffer(10, 1);
+        let mut pf = PixfmtRgba64::new(&mut ra);
+        let bg = Rgba16::new(10, 20, 30, 40);
+        pf.clear(&bg);
+        pf.blend_solid_hspan(0, 0, 3, &Rgba16::new(0xFFFF, 0, 0, 0xFFFF), &[0, 0, 0]);
+        assert_eq!(pf.pixel(1, 0), bg);
+    }
+
+    #[test]
+    fn test_blend_color_hspan_uniform_cover() {
+        let (_buf, mut ra) = make_buffer(10, 1);
+        let mut pf = PixfmtRgba64::new(&mut ra);
+        let colors = [Rgba16::new(0xFFFF, 0, 0, 0xFFFF); 3];
+        pf.blend_color_hspan(2, 0, 3, &colors, &[], 255);
+        assert_eq!(pf.pixel(3, 0), colors[0]);
+        pf.clear(&Rgba16::new(0, 0, 0, 0xFFFF));
+        pf.blend_color_hspan(2, 0, 3, &colors, &[], 128);
+        let p = pf.pixel(3, 0);
+        assert_eq!(p.r, Rgba16::mult_cover(0xFFFF, 128));
+    }
+
+    #[test]
+    fn test_pre_blend_matches_prelerp() {
+        let (_buf, mut ra) = make_buffer(4, 1);
+        let mut pf = PixfmtRgba64Pre::new(&mut ra);
+        pf.clear(&Rgba16::new(0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF));
+        // Premultiplied 50% black
+        let c = Rgba16::new(0, 0, 0, 0x8000);
+        pf.blend_pixel(1, 0, &c, 255);
+        let p = pf.pixel(1, 0);
+        assert_eq!(p.r, Rgba16::prelerp(0xFFFF, 0, 0x8000));
+        assert_eq!(p.a, 0xFFFF);
+    }
+
+    #[test]
+    fn test_pre_copy_when_opaque() {
+        let (_buf, mut ra) = make_buffer(4, 1);
+        let mut pf = PixfmtRgba64Pre::new(&mut ra);
+        let c = Rgba16::new(7, 8, 9, 0xFFFF);
+        pf.blend_hline(0, 0, 4, &c, 255);
+        assert_eq!(pf.pixel(3, 0), c);
+        // Transparent source leaves the pixel alone
+        pf.blend_pixel(0, 0, &Rgba16::new(0, 0, 0, 0), 255);
+        assert_eq!(pf.pixel(0, 0), c);
+    }
+
+    #[test]
+    fn test_render_solid_triangle() {
+        let (_buf, mut ra) = make_buffer(100, 100);
+        let mut pf = PixfmtRgba64::new(&mut ra);
+        pf.clear(&Rgba16::new(0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF));
+        let mut ren = RendererBase::new(pf);
+        let mut ras = RasterizerScanlineAa::new();
+        let mut sl = ScanlineU8::new();
+        ras.move_to_d(20.0, 20.0);
+        ras.line_to_d(80.0, 20.0);
+        ras.line_to_d(50.0, 80.0);
+
+        let red = Rgba16::new(0xFFFF, 0, 0, 0xFFFF);
+        render_scanlines_aa_solid(&mut ras, &mut sl, &mut ren, &red);
+
+        assert_eq!(ren.ren().pixel(50, 40), red);
+        assert_eq!(
+            ren.ren().pixel(0, 0),
+            Rgba16::new(0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF)
+        );
+    }
+
+    #[test]
+    fn test_render_gradient_has_more_than_256_levels() {
+        let (_buf, mut ra) = make_buffer(1024, 1);
+        let pf = PixfmtRgba64::new(&mut ra);
+        let mut ren = RendererBase::new(pf);
+        let mut ras = RasterizerScanlineAa::new();
+        let mut sl = ScanlineU8::new();
+        ras.move_to_d(0.0, 0.0);
+        ras.line_to_d(1024.0, 0.0);
+        ras.line_to_d(1024.0, 1.0);
+        ras.line_to_d(0.0, 1.0);
+
+        let mut lut: GradientLut<Rgba16> = GradientLut::new_for(1024);
+        lut.add_color(0.0, Rgba16::new(0, 0, 0, 0xFFFF));
+        lut.add_color(1.0, Rgba16::new(0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF));
+        lut.build_lut();
+
+        let interp = SpanInterpolatorLinear::new(TransAffine::new());
+        let mut sg = SpanGradient::new(interp, GradientX, &lut, 0.0, 1024.0);
+        let mut alloc = SpanAllocator::<Rgba16>::new();
+        render_scanlines_aa(&mut ras, &mut sl, &mut ren, &mut alloc, &mut sg);
+
+        let mut levels: Vec<u16> = (0..1024).map(|x| ren.ren().pixel(x, 0).r).collect();
+        assert!(levels.windows(2).all(|w| w[0] <= w[1]));
+        levels.dedup();
+        assert!(levels.len() > 256, "only {} distinct levels", levels.len());
+        assert!(ren.ren().pixel(1023, 0).r >= 0xFFFE);
+    }
+
+    #[test]
+    fn test_render_gouraud_triangle() {
+        let (_buf, mut ra) = make_buffer(100, 100);
+        let pf = PixfmtRgba64::new(&mut ra);
+        let mut ren = RendererBase::new(pf);
+        let mut ras = RasterizerScanlineAa::new();
+        let mut sl = ScanlineU8::new();
+
+        let mut gouraud = SpanGouraudRgba::new_with_triangle(
+            Rgba16::new(0xFFFF, 0, 0, 0xFFFF),
+            Rgba16::new(0, 0xFFFF, 0, 0xFFFF),
+            Rgba16::new(0, 0, 0xFFFF, 0xFFFF),
+            10.0,
+            10.0,
+            90.0,
+            10.0,
+            50.0,
+            90.0,
+            0.5,
+        );
+        ras.add_path(&mut gouraud, 0);
+        let mut alloc = SpanAllocator::<Rgba16>::new();
+        render_scanlines_aa(&mut ras, &mut sl, &mut ren, &mut alloc, &mut gouraud);
+
+        let center = ren.ren().pixel(50, 40);
+        assert_eq!(center.a, 0xFFFF);
+        assert!(center.r > 0 && center.g > 0 && center.b > 0, "{center:?}");
+        assert_eq!(ren.ren().pixel(0, 0).a, 0);
+    }
+
+    #[test]
+    fn test_gouraud_16bit_matches_8bit() {
+        // A thin triangle: near its sharp vertices the edge-to-edge run is a
+        // subpixel or two, so the spans extrapolate far past both edges. The
+        // 16-bit generator must not overflow there, and wherever the triangle
+        // fully covers a pixel it must agree with the 8-bit one up to rounding.
+        use crate::pixfmt_rgba::PixfmtRgba32;
+
+        let c8 = [
+            Rgba8::new(255, 0, 0, 255),
+            Rgba8::new(0, 255, 0, 255),
+            Rgba8::new(0, 0, 255, 255),
+        ];
+        let (x1, y1, x2, y2, x3, y3) = (10.0, 10.0, 30.0, 11.0, 12.0, 60.0);
+
+        let mut buf8 = vec![0u8; 40 * 64 * 4];
+        let mut ra8 = RowAccessor::new();
+        unsafe { ra8.attach(buf8.as_mut_ptr(), 40, 64, 40 * 4) };
+        let mut ren8 = RendererBase::new(PixfmtRgba32::new(&mut ra8));
+        let mut g8 =
+            SpanGouraudRgba::new_with_triangle(c8[0], c8[1], c8[2], x1, y1, x2, y2, x3, y3, 0.5);
+        let mut ras = RasterizerScanlineAa::new();
+        let mut sl = ScanlineU8::new();
+        ras.add_path(&mut g8, 0);
+        let mut alloc8 = SpanAllocator::<Rgba8>::new();
+        render_scanlines_aa(&mut ras, &mut sl, &mut ren8, &mut alloc8, &mut g8);
+
+        let (_buf16, mut ra16) = make_buffer(40, 64);
+        let mut ren16 = RendererBase::new(PixfmtRgba64::new(&mut ra16));
+        let mut g16 = SpanGouraudRgba::new_with_triangle(
+            Rgba16::from_rgba8(&c8[0]),
+            Rgba16::from_rgba8(&c8[1]),
+            Rgba16::from_rgba8(&c8[2]),
+            x1,
+            y1,
+            x2,
+            y2,
+            x3,
+            y3,
+            0.5,
+        );
+        ras.reset();
+        ras.add_path(&mut g16, 0);
+        let mut alloc16 = SpanAllocator::<Rgba16>::new();
+        render_scanlines_aa(&mut ras, &mut sl, &mut ren16, &mut alloc16, &mut g16);
+
+        let mut covered = 0;
+        for y in 0..64 {
+            for x in 0..40 {
+                let a = ren8.ren().pixel(x, y);
+                let b = ren16.ren().pixel(x, y).to_rgba8();
+                if a.a != 255 {
+                    continue;
+                }
+                covered += 1;
+                assert_eq!(b.a, 255);
+                assert!(
+                    (a.r as i32 - b.r as i32).abs() <= 2,
+                    "({x},{y}): {a:?} vs {b:?}"
+                );
+                assert!(
+                    (a.g as i32 - b.g as i32).abs() <= 2,
+                    "({x},{y}): {a:?} vs {b:?}"
+                );
+                assert!(
+                    (a.b as i32 - b.b as i32).abs() <= 2,
+                    "({x},{y}): {a:?} vs {b:?}"
+                );
+            }
+        }
+        assert!(covered > 100);
+
+        // A one-pixel-wide edge with a full-range color change, sampled over
+        // a wide span: the DDA extrapolates tens of times past 0xFFFF.
+        let mut g = SpanGouraudRgba::new_with_triangle(
+            Rgba16::new(0xFFFF, 0, 0, 0xFFFF),
+            Rgba16::new(0, 0xFFFF, 0, 0xFFFF),
+            Rgba16::new(0, 0, 0xFFFF, 0xFFFF),
+            10.0,
+            10.0,
+            11.0,
+            10.0,
+            10.0,
+            60.0,
+            0.0,
+        );
+        g.prepare();
+        let mut span = vec![Rgba16::default(); 40];
+        for y in 8..62 {
+            g.generate(&mut span, 0, y, 40);
+        }
+        assert_eq!(span[0].a, 0xFFFF);
+    }
+}
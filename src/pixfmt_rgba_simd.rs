@@ -0,0 +1,943 @@
+//! SIMD span blending for the 8-bit RGBA pixel formats.
+//!
+//! The span loops of `PixfmtAlphaBlendRgba` (straight alpha, `lerp`),
+//! `PixfmtAlphaBlendRgbaPre` (premultiplied, `prelerp`) and the `SrcOver`
+//! path of `PixfmtCustomBlendRgba` run through [`blend_span`], which picks
+//! the widest kernel available: AVX2 or SSE2 on x86, detected at runtime;
+//! NEON on AArch64 and SIMD128 on wasm32, both fixed at compile time since
+//! neither target can probe for them. Other targets, spans shorter than one
+//! vector, and the pixels left over after the last full vector use the
+//! scalar `Rgba8` arithmetic.
+//!
+//! The kernels are bit-exact with that arithmetic, which pixel-compare
+//! relies on to match C++ byte for byte. Every intermediate fits a 16-bit
+//! lane: a product of two bytes is at most 65025, and `multiply`'s rounding
+//! `t = x + 128; (t + (t >> 8)) >> 8` stays below 65536. `lerp`'s signed
+//! difference is split into two saturating ones,
+//! `p + multiply(q -| p, a) - multiply(p -| q, a)`, which equals the scalar
+//! formula, including its `p > q` rounding bias, for every `p`, `q` and `a`.
+
+use crate::color::Rgba8;
+
+/// Pixels blended per chunk when colors have to be staged in channel order.
+const STAGE_LEN: usize = 64;
+
+/// Blend arithmetic applied by [`blend_span`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BlendOp {
+    /// Straight alpha, as `blender_rgba::blend_pix`: the color channels move
+    /// toward the source and alpha toward 255, by `mult_cover(alpha, cover)`.
+    Lerp,
+    /// Premultiplied, as `blender_rgba_pre::blend_pix` with a cover: the
+    /// source is scaled by the cover and composited with `prelerp`. Sources
+    /// with zero alpha leave the pixel untouched, as in `copy_or_blend_pix`.
+    Prelerp,
+}
+
+/// Source colors of a span, laid out in the destination's channel order.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SpanSrc<'a> {
+    /// One color for every pixel.
+    Solid([u8; 4]),
+    /// Four bytes per pixel.
+    Colors(&'a [u8]),
+}
+
+/// Coverage of a span.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SpanCover<'a> {
+    /// One cover for every pixel.
+    Uniform(u8),
+    /// One cover per pixel.
+    Covers(&'a [u8]),
+}
+
+/// Blend `src` into the pixels of `dst` (four bytes each, alpha at byte
+/// `ai`) with `op`, scaled by `cover`.
+///
+/// `Colors` and `Covers` must hold at least one entry per pixel of `dst`.
+pub(crate) fn blend_span(op: BlendOp, dst: &mut [u8], src: SpanSrc, cover: SpanCover, ai: usize) {
+    let done = if dst.len() >= 16 {
+        Backend::detect().blend(op, dst, src, cover, ai)
+    } else {
+        0
+    };
+    blend_scalar(op, dst, src, cover, ai, done);
+}
+
+/// Blend `colors` into `dst` with [`blend_span`], staging them a chunk at a
+/// time as four bytes per pixel in the destination's channel order.
+pub(crate) fn blend_colors(
+    op: BlendOp,
+    dst: &mut [u8],
+    mut colors: impl Iterator<Item = [u8; 4]>,
+    cover: SpanCover,
+    ai: usize,
+) {
+    let mut stage = [0u8; STAGE_LEN * 4];
+    for (chunk, dst) in dst.chunks_mut(STAGE_LEN * 4).enumerate() {
+        let n = dst.len() / 4;
+        for (px, c) in stage.chunks_exact_mut(4).zip(colors.by_ref().take(n)) {
+            px.copy_from_slice(&c);
+        }
+        let cover = match cover {
+            SpanCover::Uniform(k) => SpanCover::Uniform(k),
+            SpanCover::Covers(k) => SpanCover::Covers(&k[chunk * STAGE_LEN..]),
+        };
+        blend_span(op, dst, SpanSrc::Colors(&stage[..n * 4]), cover, ai);
+    }
+}
+
+// ============================================================================
+// Scalar reference
+// ============================================================================
+
+/// Straight-alpha blend of one pixel; port of `blender_rgba::blend_pix`
+/// with the copy shortcut of `blend_solid_hspan`.
+#[inline]
+fn lerp_pix(p: &mut [u8], s: [u8; 4], cover: u8, ai: usize) {
+    let alpha = Rgba8::mult_cover(s[ai], cover);
+    if alpha == 255 {
+        p.copy_from_slice(&s);
+        p[ai] = 255;
+    } else if alpha > 0 {
+        for (ch, (d, &q)) in p.iter_mut().zip(s.iter()).enumerate() {
+            let q = if ch == ai { 255 } else { q };
+            *d = Rgba8::lerp(*d, q, alpha);
+        }
+    }
+}
+
+/// Premultiplied blend of one pixel; port of `copy_or_blend_pix` with a
+/// cover.
+#[inline]
+fn prelerp_pix(p: &mut [u8], s: [u8; 4], cover: u8, ai: usize) {
+    if s[ai] == 0 {
+        return;
+    }
+    if s[ai] == 255 && cover == 255 {
+        p.copy_from_slice(&s);
+        return;
+    }
+    let alpha = Rgba8::mult_cover(s[ai], cover);
+    for (d, &q) in p.iter_mut().zip(s.iter()) {
+        *d = Rgba8::prelerp(*d, Rgba8::mult_cover(q, cover), alpha);
+    }
+}
+
+/// Blend the pixels of `dst` from index `from` on with the scalar
+/// arithmetic.
+fn blend_scalar(
+    op: BlendOp,
+    dst: &mut [u8],
+    src: SpanSrc,
+    cover: SpanCover,
+    ai: usize,
+    from: usize,
+) {
+    for (i, p) in dst.chunks_exact_mut(4).enumerate().skip(from) {
+        let s = match src {
+            SpanSrc::Solid(s) => s,
+            SpanSrc::Colors(c) => [c[i * 4], c[i * 4 + 1], c[i * 4 + 2], c[i * 4 + 3]],
+        };
+        let k = match cover {
+            SpanCover::Uniform(k) => k,
+            SpanCover::Covers(k) => k[i],
+        };
+        match op {
+            BlendOp::Lerp => lerp_pix(p, s, k, ai),
+            BlendOp::Prelerp => prelerp_pix(p, s, k, ai),
+        }
+    }
+}
+
+// ============================================================================
+// Backend selection
+// ============================================================================
+
+/// A span blending kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    Scalar,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Sse2,
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    Avx2,
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    Neon,
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    Simd128,
+}
+
+impl Backend {
+    /// The widest kernel this CPU supports.
+    #[allow(unreachable_code)]
+    fn detect() -> Backend {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return Backend::Avx2;
+            }
+            if is_x86_feature_detected!("sse2") {
+                return Backend::Sse2;
+            }
+        }
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            return Backend::Neon;
+        }
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            return Backend::Simd128;
+        }
+        Backend::Scalar
+    }
+
+    /// Blend the leading pixels of `dst` that fill whole vectors and return
+    /// how many were blended.
+    #[cfg_attr(
+        not(any(
+            target_arch = "x86",
+            target_arch = "x86_64",
+            all(target_arch = "aarch64", target_feature = "neon"),
+            all(target_arch = "wasm32", target_feature = "simd128"),
+        )),
+        allow(unused_variables)
+    )]
+    fn blend(
+        self,
+        op: BlendOp,
+        dst: &mut [u8],
+        src: SpanSrc,
+        cover: SpanCover,
+        ai: usize,
+    ) -> usize {
+        // The kernels load sources and covers through raw pointers.
+        assert!(ai < 4);
+        if let SpanSrc::Colors(c) = src {
+            assert!(c.len() >= dst.len());
+        }
+        if let SpanCover::Covers(k) = cover {
+            assert!(k.len() >= dst.len() / 4);
+        }
+        match self {
+            Backend::Scalar => 0,
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Sse2 => unsafe { x86::blend_sse2(op, dst, src, cover, ai) },
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Avx2 => unsafe { x86::blend_avx2(op, dst, src, cover, ai) },
+            #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+            Backend::Neon => unsafe { neon::blend_neon(op, dst, src, cover, ai) },
+            #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+            Backend::Simd128 => unsafe { wasm::blend_simd128(op, dst, src, cover, ai) },
+        }
+    }
+}
+
+// ============================================================================
+// x86: SSE2 and AVX2
+// ============================================================================
+
+// Pixels are widened to 16-bit lanes, two pixels per 128 bits. The 8-bit
+// unpack and pack instructions work within 128-bit lanes, so under AVX2 the
+// low half holds pixels 0, 1, 4, 5 and the high half 2, 3, 6, 7; covers are
+// spread to match and the final pack restores the memory order.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    use super::{BlendOp, SpanCover, SpanSrc};
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    /// `Rgba8::multiply` of the 16-bit lanes of `a` and `b`.
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn mul_sse2(a: __m128i, b: __m128i) -> __m128i {
+        let t = _mm_add_epi16(_mm_mullo_epi16(a, b), _mm_set1_epi16(128));
+        _mm_srli_epi16(_mm_add_epi16(t, _mm_srli_epi16(t, 8)), 8)
+    }
+
+    /// Copy channel `ai` of each pixel to its other three channels.
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn splat_channel_sse2(v: __m128i, ai: usize) -> __m128i {
+        match ai {
+            0 => _mm_shufflehi_epi16(_mm_shufflelo_epi16(v, 0x00), 0x00),
+            1 => _mm_shufflehi_epi16(_mm_shufflelo_epi16(v, 0x55), 0x55),
+            2 => _mm_shufflehi_epi16(_mm_shufflelo_epi16(v, 0xAA), 0xAA),
+            _ => _mm_shufflehi_epi16(_mm_shufflelo_epi16(v, 0xFF), 0xFF),
+        }
+    }
+
+    /// Blend two widened pixels `s` into `d` with per-channel covers `k`.
+    /// `amask` is 255 in the alpha channels and 0 elsewhere.
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    unsafe fn blend2_sse2(
+        op: BlendOp,
+        d: __m128i,
+        s: __m128i,
+        k: __m128i,
+        amask: __m128i,
+        ai: usize,
+    ) -> __m128i {
+        let sa = splat_channel_sse2(s, ai);
+        match op {
+            BlendOp::Lerp => {
+                let a = mul_sse2(sa, k);
+                let q = _mm_or_si128(s, amask);
+                let up = mul_sse2(_mm_subs_epu16(q, d), a);
+                let down = mul_sse2(_mm_subs_epu16(d, q), a);
+                _mm_sub_epi16(_mm_add_epi16(d, up), down)
+            }
+            BlendOp::Prelerp => {
+                let sc = mul_sse2(s, k);
+                let a = splat_channel_sse2(sc, ai);
+                let r = _mm_sub_epi16(_mm_add_epi16(d, sc), mul_sse2(d, a));
+                let r = _mm_and_si128(r, _mm_set1_epi16(0xFF));
+                let skip = _mm_cmpeq_epi16(sa, _mm_setzero_si128());
+                _mm_or_si128(_mm_and_si128(skip, d), _mm_andnot_si128(skip, r))
+            }
+        }
+    }
+
+    /// Four pixels per step.
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn blend_sse2(
+        op: BlendOp,
+        dst: &mut [u8],
+        src: SpanSrc,
+        cover: SpanCover,
+        ai: usize,
+    ) -> usize {
+        let n = dst.len() / 16 * 4;
+        let zero = _mm_setzero_si128();
+        let mut mask = [0i16; 8];
+        mask[ai] = 255;
+        mask[ai + 4] = 255;
+        let amask = _mm_loadu_si128(mask.as_ptr() as *const __m128i);
+        let solid = match src {
+            SpanSrc::Solid(s) => _mm_set1_epi32(i32::from_ne_bytes(s)),
+            SpanSrc::Colors(_) => zero,
+        };
+        let uniform = match cover {
+            SpanCover::Uniform(k) => _mm_set1_epi16(k as i16),
+            SpanCover::Covers(_) => zero,
+        };
+        let mut i = 0;
+        while i < n {
+            let p = dst.as_mut_ptr().add(i * 4) as *mut __m128i;
+            let d = _mm_loadu_si128(p);
+            let s = match src {
+                SpanSrc::Solid(_) => solid,
+                SpanSrc::Colors(c) => _mm_loadu_si128(c.as_ptr().add(i * 4) as *const __m128i),
+            };
+            let (klo, khi) = match cover {
+                SpanCover::Uniform(_) => (uniform, uniform),
+                SpanCover::Covers(k) => {
+                    let k4 = std::ptr::read_unaligned(k.as_ptr().add(i) as *const [u8; 4]);
+                    let k16 = _mm_unpacklo_epi8(_mm_cvtsi32_si128(i32::from_ne_bytes(k4)), zero);
+                    let k32 = _mm_unpacklo_epi16(k16, k16);
+                    (_mm_unpacklo_epi32(k32, k32), _mm_unpackhi_epi32(k32, k32))
+                }
+            };
+            let lo = blend2_sse2(
+                op,
+                _mm_unpacklo_epi8(d, zero),
+                _mm_unpacklo_epi8(s, zero),
+                klo,
+                amask,
+                ai,
+            );
+            let hi = blend2_sse2(
+                op,
+                _mm_unpackhi_epi8(d, zero),
+                _mm_unpackhi_epi8(s, zero),
+                khi,
+                amask,
+                ai,
+            );
+            _mm_storeu_si128(p, _mm_packus_epi16(lo, hi));
+            i += 4;
+        }
+        n
+    }
+
+    /// `Rgba8::multiply` of the 16-bit lanes of `a` and `b`.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn mul_avx2(a: __m256i, b: __m256i) -> __m256i {
+        let t = _mm256_add_epi16(_mm256_mullo_epi16(a, b), _mm256_set1_epi16(128));
+        _mm256_srli_epi16(_mm256_add_epi16(t, _mm256_srli_epi16(t, 8)), 8)
+    }
+
+    /// Copy channel `ai` of each pixel to its other three channels.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn splat_channel_avx2(v: __m256i, ai: usize) -> __m256i {
+        match ai {
+            0 => _mm256_shufflehi_epi16(_mm256_shufflelo_epi16(v, 0x00), 0x00),
+            1 => _mm256_shufflehi_epi16(_mm256_shufflelo_epi16(v, 0x55), 0x55),
+            2 => _mm256_shufflehi_epi16(_mm256_shufflelo_epi16(v, 0xAA), 0xAA),
+            _ => _mm256_shufflehi_epi16(_mm256_shufflelo_epi16(v, 0xFF), 0xFF),
+        }
+    }
+
+    /// Blend four widened pixels; see [`blend2_sse2`].
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn blend4_avx2(
+        op: BlendOp,
+        d: __m256i,
+        s: __m256i,
+        k: __m256i,
+        amask: __m256i,
+        ai: usize,
+    ) -> __m256i {
+        let sa = splat_channel_avx2(s, ai);
+        match op {
+            BlendOp::Lerp => {
+                let a = mul_avx2(sa, k);
+                let q = _mm256_or_si256(s, amask);
+                let up = mul_avx2(_mm256_subs_epu16(q, d), a);
+                let down = mul_avx2(_mm256_subs_epu16(d, q), a);
+                _mm256_sub_epi16(_mm256_add_epi16(d, up), down)
+            }
+            BlendOp::Prelerp => {
+                let sc = mul_avx2(s, k);
+                let a = splat_channel_avx2(sc, ai);
+                let r = _mm256_sub_epi16(_mm256_add_epi16(d, sc), mul_avx2(d, a));
+                let r = _mm256_and_si256(r, _mm256_set1_epi16(0xFF));
+                let skip = _mm256_cmpeq_epi16(sa, _mm256_setzero_si256());
+                _mm256_blendv_epi8(r, d, skip)
+            }
+        }
+    }
+
+    /// Eight pixels per step.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn blend_avx2(
+        op: BlendOp,
+        dst: &mut [u8],
+        src: SpanSrc,
+        cover: SpanCover,
+        ai: usize,
+    ) -> usize {
+        let n = dst.len() / 32 * 8;
+        let zero = _mm256_setzero_si256();
+        let mut mask = [0i16; 16];
+        for px in 0..4 {
+            mask[px * 4 + ai] = 255;
+        }
+        let amask = _mm256_loadu_si256(mask.as_ptr() as *const __m256i);
+        let solid = match src {
+            SpanSrc::Solid(s) => _mm256_set1_epi32(i32::from_ne_bytes(s)),
+            SpanSrc::Colors(_) => zero,
+        };
+        let uniform = match cover {
+            SpanCover::Uniform(k) => _mm256_set1_epi16(k as i16),
+            SpanCover::Covers(_) => zero,
+        };
+        let mut i = 0;
+        while i < n {
+            let p = dst.as_mut_ptr().add(i * 4) as *mut __m256i;
+            let d = _mm256_loadu_si256(p);
+            let s = match src {
+                SpanSrc::Solid(_) => solid,
+                SpanSrc::Colors(c) => _mm256_loadu_si256(c.as_ptr().add(i * 4) as *const __m256i),
+            };
+            let (klo, khi) = match cover {
+                SpanCover::Uniform(_) => (uniform, uniform),
+                SpanCover::Covers(k) => {
+                    // Covers 0-3 and 4-7 to the two 128-bit halves, each
+                    // doubled into a 32-bit lane, then doubled again.
+                    let k8 = _mm_loadl_epi64(k.as_ptr().add(i) as *const __m128i);
+                    let k32 = _mm256_cvtepu8_epi32(k8);
+                    let k32 = _mm256_or_si256(k32, _mm256_slli_epi32(k32, 16));
+                    (
+                        _mm256_unpacklo_epi32(k32, k32),
+                        _mm256_unpackhi_epi32(k32, k32),
+                    )
+                }
+            };
+            let lo = blend4_avx2(
+                op,
+                _mm256_unpacklo_epi8(d, zero),
+                _mm256_unpacklo_epi8(s, zero),
+                klo,
+                amask,
+                ai,
+            );
+            let hi = blend4_avx2(
+                op,
+                _mm256_unpackhi_epi8(d, zero),
+                _mm256_unpackhi_epi8(s, zero),
+                khi,
+                amask,
+                ai,
+            );
+            _mm256_storeu_si256(p, _mm256_packus_epi16(lo, hi));
+            i += 8;
+        }
+        n
+    }
+}
+
+// ============================================================================
+// AArch64: NEON
+// ============================================================================
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon {
+    use super::{BlendOp, SpanCover, SpanSrc};
+    use std::arch::aarch64::*;
+
+    /// Table indices spreading covers 0-1 (`LO`) and 2-3 (`HI`) of a
+    /// register over the four 16-bit channels of each pixel; out-of-range
+    /// indices read as zero.
+    const COVER_LO: [u8; 16] = [0, 16, 0, 16, 0, 16, 0, 16, 1, 16, 1, 16, 1, 16, 1, 16];
+    const COVER_HI: [u8; 16] = [2, 16, 2, 16, 2, 16, 2, 16, 3, 16, 3, 16, 3, 16, 3, 16];
+
+    /// `Rgba8::multiply` of the 16-bit lanes of `a` and `b`.
+    #[inline]
+    unsafe fn mul(a: uint16x8_t, b: uint16x8_t) -> uint16x8_t {
+        let t = vaddq_u16(vmulq_u16(a, b), vdupq_n_u16(128));
+        vshrq_n_u16(vaddq_u16(t, vshrq_n_u16(t, 8)), 8)
+    }
+
+    /// Copy the channel selected by `splat` (see `blend_neon`) of each pixel
+    /// to its other three channels.
+    #[inline]
+    unsafe fn splat_channel(v: uint16x8_t, splat: uint8x16_t) -> uint16x8_t {
+        vreinterpretq_u16_u8(vqtbl1q_u8(vreinterpretq_u8_u16(v), splat))
+    }
+
+    /// Blend two widened pixels; see the x86 `blend2_sse2`.
+    #[inline]
+    unsafe fn blend2(
+        op: BlendOp,
+        d: uint16x8_t,
+        s: uint16x8_t,
+        k: uint16x8_t,
+        amask: uint16x8_t,
+        splat: uint8x16_t,
+    ) -> uint16x8_t {
+        let sa = splat_channel(s, splat);
+        match op {
+            BlendOp::Lerp => {
+                let a = mul(sa, k);
+                let q = vorrq_u16(s, amask);
+                let up = mul(vqsubq_u16(q, d), a);
+                let down = mul(vqsubq_u16(d, q), a);
+                vsubq_u16(vaddq_u16(d, up), down)
+            }
+            BlendOp::Prelerp => {
+                let sc = mul(s, k);
+                let a = splat_channel(sc, splat);
+                // The narrowing store keeps the low byte, as `prelerp` wraps.
+                let r = vsubq_u16(vaddq_u16(d, sc), mul(d, a));
+                let skip = vceqq_u16(sa, vdupq_n_u16(0));
+                vbslq_u16(skip, d, r)
+            }
+        }
+    }
+
+    /// Four pixels per step.
+    pub(super) unsafe fn blend_neon(
+        op: BlendOp,
+        dst: &mut [u8],
+        src: SpanSrc,
+        cover: SpanCover,
+        ai: usize,
+    ) -> usize {
+        let n = dst.len() / 16 * 4;
+        let mut mask = [0u16; 8];
+        mask[ai] = 255;
+        mask[ai + 4] = 255;
+        let amask = vld1q_u16(mask.as_ptr());
+        let mut idx = [0u8; 16];
+        for (lane, pair) in idx.chunks_exact_mut(2).enumerate() {
+            let from = (lane & !3 | ai) as u8 * 2;
+            pair.copy_from_slice(&[from, from + 1]);
+        }
+        let splat = vld1q_u8(idx.as_ptr());
+        let (cover_lo, cover_hi) = (vld1q_u8(COVER_LO.as_ptr()), vld1q_u8(COVER_HI.as_ptr()));
+        let solid = match src {
+            SpanSrc::Solid(s) => vreinterpretq_u8_u32(vdupq_n_u32(u32::from_ne_bytes(s))),
+            SpanSrc::Colors(_) => vdupq_n_u8(0),
+        };
+        let uniform = match cover {
+            SpanCover::Uniform(k) => vdupq_n_u16(k as u16),
+            SpanCover::Covers(_) => vdupq_n_u16(0),
+        };
+        let mut i = 0;
+        while i < n {
+            let p = dst.as_mut_ptr().add(i * 4);
+            let d = vld1q_u8(p);
+            let s = match src {
+                SpanSrc::Solid(_) => solid,
+                SpanSrc::Colors(c) => vld1q_u8(c.as_ptr().add(i * 4)),
+            };
+            let (klo, khi) = match cover {
+                SpanCover::Uniform(_) => (uniform, uniform),
+                SpanCover::Covers(k) => {
+                    let k4 = std::ptr::read_unaligned(k.as_ptr().add(i) as *const [u8; 4]);
+                    let k4 = vreinterpretq_u8_u32(vdupq_n_u32(u32::from_ne_bytes(k4)));
+                    (
+                        vreinterpretq_u16_u8(vqtbl1q_u8(k4, cover_lo)),
+                        vreinterpretq_u16_u8(vqtbl1q_u8(k4, cover_hi)),
+                    )
+                }
+            };
+            let lo = blend2(
+                op,
+                vmovl_u8(vget_low_u8(d)),
+                vmovl_u8(vget_low_u8(s)),
+                klo,
+                amask,
+                splat,
+            );
+            let hi = blend2(op, vmovl_high_u8(d), vmovl_high_u8(s), khi, amask, splat);
+            vst1q_u8(p, vcombine_u8(vmovn_u16(lo), vmovn_u16(hi)));
+            i += 4;
+        }
+        n
+    }
+}
+
+// ============================================================================
+// wasm32: SIMD128
+// ============================================================================
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm {
+    use super::{BlendOp, SpanCover, SpanSrc};
+    use std::arch::wasm32::*;
+
+    /// Swizzle indices spreading covers 0-1 (`LO`) and 2-3 (`HI`) of a
+    /// register over the four 16-bit channels of each pixel; out-of-range
+    /// indices read as zero.
+    const COVER_LO: [u8; 16] = [0, 16, 0, 16, 0, 16, 0, 16, 1, 16, 1, 16, 1, 16, 1, 16];
+    const COVER_HI: [u8; 16] = [2, 16, 2, 16, 2, 16, 2, 16, 3, 16, 3, 16, 3, 16, 3, 16];
+
+    /// `Rgba8::multiply` of the 16-bit lanes of `a` and `b`.
+    #[inline]
+    fn mul(a: v128, b: v128) -> v128 {
+        let t = i16x8_add(i16x8_mul(a, b), u16x8_splat(128));
+        u16x8_shr(i16x8_add(t, u16x8_shr(t, 8)), 8)
+    }
+
+    /// Blend two widened pixels; see the x86 `blend2_sse2`.
+    #[inline]
+    fn blend2(op: BlendOp, d: v128, s: v128, k: v128, amask: v128, splat: v128) -> v128 {
+        let sa = u8x16_swizzle(s, splat);
+        match op {
+            BlendOp::Lerp => {
+                let a = mul(sa, k);
+                let q = v128_or(s, amask);
+                let up = mul(u16x8_sub_sat(q, d), a);
+                let down = mul(u16x8_sub_sat(d, q), a);
+                i16x8_sub(i16x8_add(d, up), down)
+            }
+            BlendOp::Prelerp => {
+                let sc = mul(s, k);
+                let a = u8x16_swizzle(sc, splat);
+                let r = i16x8_sub(i16x8_add(d, sc), mul(d, a));
+                let r = v128_and(r, u16x8_splat(0xFF));
+                let skip = i16x8_eq(sa, u16x8_splat(0));
+                v128_bitselect(d, r, skip)
+            }
+        }
+    }
+
+    /// Four pixels per step.
+    pub(super) unsafe fn blend_simd128(
+        op: BlendOp,
+        dst: &mut [u8],
+        src: SpanSrc,
+        cover: SpanCover,
+        ai: usize,
+    ) -> usize {
+        let n = dst.len() / 16 * 4;
+        let mut mask = [0u16; 8];
+        mask[ai] = 255;
+        mask[ai + 4] = 255;
+        let amask = v128_load(mask.as_ptr() as *const v128);
+        let mut idx = [0u8; 16];
+        for (lane, pair) in idx.chunks_exact_mut(2).enumerate() {
+            let from = (lane & !3 | ai) as u8 * 2;
+            pair.copy_from_slice(&[from, from + 1]);
+        }
+        let splat = v128_load(idx.as_ptr() as *const v128);
+        let cover_lo = v128_load(COVER_LO.as_ptr() as *const v128);
+        let cover_hi = v128_load(COVER_HI.as_ptr() as *const v128);
+        let solid = match src {
+            SpanSrc::Solid(s) => u32x4_splat(u32::from_ne_bytes(s)),
+            SpanSrc::Colors(_) => u32x4_splat(0),
+        };
+        let uniform = match cover {
+            SpanCover::Uniform(k) => u16x8_splat(k as u16),
+            SpanCover::Covers(_) => u16x8_splat(0),
+        };
+        let mut i = 0;
+        while i < n {
+            let p = dst.as_mut_ptr().add(i * 4) as *mut v128;
+            let d = v128_load(p);
+            let s = match src {
+                SpanSrc::Solid(_) => solid,
+                SpanSrc::Colors(c) => v128_load(c.as_ptr().add(i * 4) as *const v128),
+            };
+            let (klo, khi) = match cover {
+                SpanCover::Uniform(_) => (uniform, uniform),
+                SpanCover::Covers(k) => {
+                    let k4 = std::ptr::read_unaligned(k.as_ptr().add(i) as *const [u8; 4]);
+                    let k4 = u32x4_splat(u32::from_ne_bytes(k4));
+                    (u8x16_swizzle(k4, cover_lo), u8x16_swizzle(k4, cover_hi))
+                }
+            };
+            let lo = blend2(
+                op,
+                u16x8_extend_low_u8x16(d),
+                u16x8_extend_low_u8x16(s),
+                klo,
+                amask,
+                splat,
+            );
+            let hi = blend2(
+                op,
+                u16x8_extend_high_u8x16(d),
+                u16x8_extend_high_u8x16(s),
+                khi,
+                amask,
+                splat,
+            );
+            v128_store(p, u8x16_narrow_i16x8(lo, hi));
+            i += 4;
+        }
+        n
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every backend this machine can run, besides `Scalar`.
+    fn simd_backends() -> Vec<Backend> {
+        let mut v = Vec::new();
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse2") {
+                v.push(Backend::Sse2);
+            }
+            if is_x86_feature_detected!("avx2") {
+                v.push(Backend::Avx2);
+            }
+        }
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            v.push(Backend::Neon);
+        }
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            v.push(Backend::Simd128);
+        }
+        v
+    }
+
+    /// `backend` over `dst`, with the scalar path finishing the remainder.
+    fn run(
+        backend: Backend,
+        op: BlendOp,
+        dst: &mut [u8],
+        src: SpanSrc,
+        cover: SpanCover,
+        ai: usize,
+    ) {
+        let done = backend.blend(op, dst, src, cover, ai);
+        blend_scalar(op, dst, src, cover, ai, done);
+    }
+
+    /// xorshift64* byte stream.
+    struct Bytes(u64);
+
+    impl Bytes {
+        fn next(&mut self) -> u8 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            (self.0.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 56) as u8
+        }
+
+        /// Mostly random bytes, with the 0 and 255 edge cases over-represented.
+        fn edgy(&mut self) -> u8 {
+            match self.next() & 7 {
+                0 => 0,
+                1 => 255,
+                _ => self.next(),
+            }
+        }
+
+        fn fill(&mut self, n: usize) -> Vec<u8> {
+            (0..n).map(|_| self.edgy()).collect()
+        }
+    }
+
+    #[test]
+    fn test_lerp_exhaustive() {
+        // Every (p, q, a) through each kernel: 256 destination bytes per
+        // span, against a solid source whose alpha makes `a` the effective
+        // alpha for the color channels.
+        let dst: Vec<u8> = (0..=255u8).collect();
+        for backend in simd_backends() {
+            for q in 0..=255u8 {
+                for a in 0..=255u8 {
+                    let mut d = dst.clone();
+                    let src = SpanSrc::Solid([q, q, q, 255]);
+                    run(
+                        backend,
+                        BlendOp::Lerp,
+                        &mut d,
+                        src,
+                        SpanCover::Uniform(a),
+                        3,
+                    );
+                    for (i, &v) in d.iter().enumerate() {
+                        let expect = if i % 4 == 3 {
+                            Rgba8::lerp(dst[i], 255, a)
+                        } else {
+                            Rgba8::lerp(dst[i], q, a)
+                        };
+                        assert_eq!(v, expect, "{backend:?} p={} q={q} a={a}", dst[i]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_prelerp_exhaustive() {
+        // Every (p, q, a) with cover 255, so the source is used unscaled.
+        let dst: Vec<u8> = (0..=255u8).collect();
+        for backend in simd_backends() {
+            for q in 0..=255u8 {
+                for a in 1..=255u8 {
+                    let mut d = dst.clone();
+                    let src = SpanSrc::Solid([q, q, q, a]);
+                    run(
+                        backend,
+                        BlendOp::Prelerp,
+                        &mut d,
+                        src,
+                        SpanCover::Uniform(255),
+                        3,
+                    );
+                    for (i, &v) in d.iter().enumerate() {
+                        let expect = if i % 4 == 3 {
+                            Rgba8::prelerp(dst[i], a, a)
+                        } else {
+                            Rgba8::prelerp(dst[i], q, a)
+                        };
+                        assert_eq!(v, expect, "{backend:?} p={} q={q} a={a}", dst[i]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_spans_match_scalar() {
+        let mut rng = Bytes(0x9E37_79B9_7F4A_7C15);
+        for backend in simd_backends() {
+            for op in [BlendOp::Lerp, BlendOp::Prelerp] {
+                for ai in [0, 3] {
+                    for len in [0, 1, 3, 4, 5, 7, 8, 9, 15, 16, 17, 33, 100] {
+                        let dst = rng.fill(len * 4);
+                        let colors = rng.fill(len * 4);
+                        let covers = rng.fill(len);
+                        let solid = [rng.next(), rng.next(), rng.next(), rng.edgy()];
+                        let uniform = rng.edgy();
+                        for src in [SpanSrc::Solid(solid), SpanSrc::Colors(&colors)] {
+                            for cover in [SpanCover::Uniform(uniform), SpanCover::Covers(&covers)] {
+                                let mut expect = dst.clone();
+                                blend_scalar(op, &mut expect, src, cover, ai, 0);
+                                let mut got = dst.clone();
+                                run(backend, op, &mut got, src, cover, ai);
+                                assert_eq!(got, expect, "{backend:?} {op:?} ai={ai} len={len}");
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_scalar_matches_blender() {
+        // The scalar path against the unshortcut blender arithmetic.
+        let mut rng = Bytes(0xD1B5_4A32_D192_ED03);
+        for _ in 0..10_000 {
+            let p = [rng.edgy(), rng.edgy(), rng.edgy(), rng.edgy()];
+            let s = [rng.edgy(), rng.edgy(), rng.edgy(), rng.edgy()];
+            let k = rng.edgy();
+
+            let mut got = p;
+            lerp_pix(&mut got, s, k, 3);
+            let alpha = Rgba8::mult_cover(s[3], k);
+            let expect = [
+                Rgba8::lerp(p[0], s[0], alpha),
+                Rgba8::lerp(p[1], s[1], alpha),
+                Rgba8::lerp(p[2], s[2], alpha),
+                Rgba8::lerp(p[3], 255, alpha),
+            ];
+            assert_eq!(got, expect, "lerp p={p:?} s={s:?} k={k}");
+
+            let mut got = p;
+            prelerp_pix(&mut got, s, k, 3);
+            let expect = if s[3] == 0 {
+                p
+            } else {
+                let sc = s.map(|c| Rgba8::mult_cover(c, k));
+                [0, 1, 2, 3].map(|i| Rgba8::prelerp(p[i], sc[i], sc[3]))
+            };
+            assert_eq!(got, expect, "prelerp p={p:?} s={s:?} k={k}");
+        }
+    }
+
+    #[test]
+    fn test_blend_colors_stages_in_chunks() {
+        let mut rng = Bytes(0x2545_F491_4F6C_DD1D);
+        let len = STAGE_LEN * 2 + 13;
+        let dst = rng.fill(len * 4);
+        let colors: Vec<[u8; 4]> = (0..len)
+            .map(|_| [rng.edgy(), rng.edgy(), rng.edgy(), rng.edgy()])
+            .collect();
+        let covers = rng.fill(len);
+        let flat: Vec<u8> = colors.iter().flatten().copied().collect();
+        for op in [BlendOp::Lerp, BlendOp::Prelerp] {
+            let mut expect = dst.clone();
+            blend_scalar(
+                op,
+                &mut expect,
+                SpanSrc::Colors(&flat),
+                SpanCover::Covers(&covers),
+                3,
+                0,
+            );
+            let mut got = dst.clone();
+            blend_colors(
+                op,
+                &mut got,
+                colors.iter().copied(),
+                SpanCover::Covers(&covers),
+                3,
+            );
+            assert_eq!(got, expect, "{op:?}");
+        }
+    }
+}
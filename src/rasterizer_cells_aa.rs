@@ -6,7 +6,9 @@
 //!
 //! Also ports the `cell_aa` struct from `agg_rasterizer_scanline_aa_nogamma.h`.
 
-use crate::basics::{POLY_SUBPIXEL_MASK, POLY_SUBPIXEL_SCALE, POLY_SUBPIXEL_SHIFT};
+use std::fmt;
+
+use crate::basics::{RectI, POLY_SUBPIXEL_MASK, POLY_SUBPIXEL_SCALE, POLY_SUBPIXEL_SHIFT};
 
 // ============================================================================
 // CellAa — a single pixel cell with coverage data
@@ -70,6 +72,27 @@ struct SortedY {
     num: u32,
 }
 
+// ============================================================================
+// CellLimitExceeded — the cell budget ran out
+// ============================================================================
+
+/// An outline needed more cells than the rasterizer's cell limit (see
+/// [`RasterizerCellsAa::set_cell_limit`]); the cells past the limit were
+/// dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellLimitExceeded {
+    /// The limit that was reached.
+    pub limit: usize,
+}
+
+impl fmt::Display for CellLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rasterizer cell limit of {} cells exceeded", self.limit)
+    }
+}
+
+impl std::error::Error for CellLimitExceeded {}
+
 // ============================================================================
 // RasterizerCellsAa — the edge-to-cell conversion engine
 // ============================================================================
@@ -92,6 +115,9 @@ pub struct RasterizerCellsAa {
     max_x: i32,
     max_y: i32,
     sorted: bool,
+    window: Option<RectI>,
+    cell_limit: usize,
+    overflow: bool,
 }
 
 /// Limit for dx magnitude before recursive subdivision in `line()`.
@@ -111,6 +137,9 @@ impl RasterizerCellsAa {
             max_x: i32::MIN,
             max_y: i32::MIN,
             sorted: false,
+            window: None,
+            cell_limit: usize::MAX,
+            overflow: false,
         }
     }
 
@@ -126,6 +155,55 @@ impl RasterizerCellsAa {
         self.max_x = i32::MIN;
         self.max_y = i32::MIN;
         self.sorted = false;
+        self.overflow = false;
+    }
+
+    /// Keep at most `limit` cells per outline (until the next `reset`).
+    ///
+    /// The C++ `cell_block_limit` counterpart: C++ defaults to 1024 blocks
+    /// of 4096 cells, this port to no limit. Cells past the limit are
+    /// dropped, as in C++, and [`overflowed`](Self::overflowed) reports it.
+    /// A sorted cell costs about 20 bytes.
+    pub fn set_cell_limit(&mut self, limit: usize) {
+        self.cell_limit = limit;
+    }
+
+    #[inline]
+    pub fn cell_limit(&self) -> usize {
+        self.cell_limit
+    }
+
+    /// Whether cells were dropped because of the cell limit since the last
+    /// `reset`.
+    #[inline]
+    pub fn overflowed(&self) -> bool {
+        self.overflow
+    }
+
+    /// `Err` if [`overflowed`](Self::overflowed).
+    pub fn check_cell_limit(&self) -> Result<(), CellLimitExceeded> {
+        if self.overflow {
+            Err(CellLimitExceeded {
+                limit: self.cell_limit,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Keep only the cells that affect the inclusive pixel rectangle
+    /// `window` (`None` keeps everything). Not part of AGG.
+    ///
+    /// Edges are rasterized whole, so coverage inside the window is exactly
+    /// what an unrestricted pass would produce. Cells in other rows are
+    /// dropped; cells left or right of the window collapse into a single
+    /// zero-area cell just outside it that carries their cover. The
+    /// rectangle is normalized, so its corners may come in any order.
+    pub fn set_window(&mut self, window: Option<RectI>) {
+        self.window = window.map(|mut w| {
+            w.normalize();
+            w
+        });
     }
 
     /// Set the current style cell (used by compound rasterizer; no-op for basic usage).
@@ -197,8 +275,45 @@ impl RasterizerCellsAa {
     #[inline]
     pub(crate) fn add_curr_cell(&mut self) {
         if self.curr_cell.area | self.curr_cell.cover != 0 {
-            self.cells.push(self.curr_cell);
+            match self.window {
+                None => self.push_cell(self.curr_cell),
+                Some(w) => self.add_window_cell(w),
+            }
+        }
+    }
+
+    /// Store `cell` unless the cell limit is reached.
+    #[inline]
+    fn push_cell(&mut self, cell: CellAa) {
+        if self.cells.len() < self.cell_limit {
+            self.cells.push(cell);
+        } else {
+            self.overflow = true;
+        }
+    }
+
+    /// `add_curr_cell` for a windowed rasterizer (see `set_window`).
+    fn add_window_cell(&mut self, w: RectI) {
+        let mut cell = self.curr_cell;
+        if cell.y < w.y1 || cell.y > w.y2 {
+            return;
         }
+        if cell.x < w.x1 || cell.x > w.x2 {
+            // Pixels inside the window only see this cell's cover, which
+            // a cell just outside the window carries just as well.
+            if cell.cover == 0 {
+                return;
+            }
+            cell.x = if cell.x < w.x1 { w.x1 - 1 } else { w.x2 + 1 };
+            cell.area = 0;
+            if let Some(last) = self.cells.last_mut() {
+                if last.x == cell.x && last.y == cell.y {
+                    last.cover += cell.cover;
+                    return;
+                }
+            }
+        }
+        self.push_cell(cell);
     }
 
     /// Import pre-computed cells with a pixel offset, bypassing path conversion.
@@ -215,7 +330,8 @@ impl RasterizerCellsAa {
     /// all cells have been inserted.
     pub fn add_cells_offset(&mut self, src: &[CellAa], dx: i32, dy: i32) {
         self.sorted = false;
-        self.cells.reserve(src.len());
+        let room = self.cell_limit.saturating_sub(self.cells.len());
+        self.cells.reserve(src.len().min(room));
         for c in src {
             let x = c.x + dx;
             let y = c.y + dy;
@@ -231,7 +347,7 @@ impl RasterizerCellsAa {
             if y > self.max_y {
                 self.max_y = y;
             }
-            self.cells.push(CellAa {
+            self.push_cell(CellAa {
                 x,
                 y,
                 cover: c.cover,
@@ -321,7 +437,7 @@ impl RasterizerCellsAa {
 
             modulo -= dx;
 
-            while ex1 != ex2 {
+            while ex1 != ex2 && !self.overflow {
                 delta = lift;
                 modulo += rem;
                 if modulo >= 0 {
@@ -346,6 +462,10 @@ impl RasterizerCellsAa {
     /// This is the primary entry point for the rasterizer. Large dx values
     /// are handled by recursive subdivision (matching the C++ implementation).
     pub fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        // Past the cell limit the outline is incomplete anyway; skip the work.
+        if self.overflow {
+            return;
+        }
         let dx = x2 as i64 - x1 as i64;
 
         if dx >= DX_LIMIT || dx <= -DX_LIMIT {
@@ -390,6 +510,12 @@ impl RasterizerCellsAa {
             self.max_y = ey2;
         }
 
+        if let Some(w) = self.window {
+            if (ey1_orig < w.y1 && ey2 < w.y1) || (ey1_orig > w.y2 && ey2 > w.y2) {
+                return;
+            }
+        }
+
         let mut ey1 = ey1_orig;
 
         self.set_curr_cell(ex1, ey1);
@@ -424,7 +550,8 @@ impl RasterizerCellsAa {
 
             delta = first + first - POLY_SUBPIXEL_SCALE as i32;
             let area = two_fx * delta;
-            while ey1 != ey2 {
+            // Rows can number in the millions; stop once cells are dropped.
+            while ey1 != ey2 && !self.overflow {
                 self.curr_cell.cover = delta;
                 self.curr_cell.area = area;
                 ey1 += incr;
@@ -473,7 +600,7 @@ impl RasterizerCellsAa {
             }
             modulo -= dy_abs;
 
-            while ey1 != ey2 {
+            while ey1 != ey2 && !self.overflow {
                 delta = lift;
                 modulo += rem;
                 if modulo >= 0 {
@@ -511,6 +638,15 @@ impl RasterizerCellsAa {
             return;
         }
 
+        // Every kept cell lies in the window's rows, at most one column
+        // outside it.
+        if let Some(w) = self.window {
+            self.min_x = self.min_x.clamp(w.x1 - 1, w.x2 + 1);
+            self.max_x = self.max_x.clamp(w.x1 - 1, w.x2 + 1);
+            self.min_y = self.min_y.max(w.y1);
+            self.max_y = self.max_y.min(w.y2);
+        }
+
         // Allocate sorted_cells (indices) and sorted_y (histogram)
         let num_cells = self.cells.len();
         self.sorted_cells.clear();
@@ -689,6 +825,27 @@ mod tests {
         assert!(!ras.sorted());
     }
 
+    #[test]
+    fn test_cell_limit() {
+        let s = POLY_SUBPIXEL_SCALE as i32;
+        let mut ras = RasterizerCellsAa::new();
+        ras.set_cell_limit(5);
+        ras.line(s / 2, 0, s / 2, 20 * s);
+        ras.sort_cells();
+        assert_eq!(ras.total_cells(), 5);
+        assert!(ras.overflowed());
+        assert_eq!(ras.check_cell_limit(), Err(CellLimitExceeded { limit: 5 }));
+
+        // Reset clears the overflow but keeps the limit.
+        ras.reset();
+        assert!(!ras.overflowed());
+        assert_eq!(ras.cell_limit(), 5);
+        ras.line(s / 2, 0, s / 2, 5 * s);
+        ras.sort_cells();
+        assert_eq!(ras.total_cells(), 5);
+        assert!(ras.check_cell_limit().is_ok());
+    }
+
     // ------------------------------------------------------------------
     // Horizontal line tests
     // ------------------------------------------------------------------
@@ -9,6 +9,7 @@ use crate::basics::{
     is_close, is_move_to, is_stop, is_vertex, iround, FillingRule, VertexSource,
     POLY_SUBPIXEL_MASK, POLY_SUBPIXEL_SCALE, POLY_SUBPIXEL_SHIFT,
 };
+use crate::gamma::{coverage_gamma_table, GammaFunction, GammaNone};
 use crate::rasterizer_scanline_aa::Scanline;
 
 // ============================================================================
@@ -625,6 +626,7 @@ pub struct RasterizerCompoundAa {
     clip_box: [i32; 4], // x1, y1, x2, y2 in subpixel coords
     clip_x1: i32,
     clip_y1: i32,
+    gamma: [u8; AA_SCALE as usize],
 }
 
 impl RasterizerCompoundAa {
@@ -649,6 +651,7 @@ impl RasterizerCompoundAa {
             clip_box: [0; 4],
             clip_x1: 0,
             clip_y1: 0,
+            gamma: coverage_gamma_table(&GammaNone),
         }
     }
 
@@ -676,6 +679,14 @@ impl RasterizerCompoundAa {
         self.filling_rule = rule;
     }
 
+    /// Map every coverage value through `gamma_function`.
+    ///
+    /// Not in C++ `rasterizer_compound_aa`; mirrors
+    /// `RasterizerScanlineAa::gamma()`. The default is the identity.
+    pub fn gamma<G: GammaFunction + ?Sized>(&mut self, gamma_function: &G) {
+        self.gamma = coverage_gamma_table(gamma_function);
+    }
+
     pub fn layer_order(&mut self, order: LayerOrder) {
         self.layer_order = order;
     }
@@ -873,7 +884,7 @@ impl RasterizerCompoundAa {
         true
     }
 
-    /// Calculate alpha from coverage area.
+    /// Calculate alpha from coverage area, through the gamma table.
     #[inline]
     pub fn calculate_alpha(&self, area: i32) -> u32 {
         let mut cover = area >> (POLY_SUBPIXEL_SHIFT * 2 + 1 - AA_SHIFT);
@@ -889,7 +900,7 @@ impl RasterizerCompoundAa {
         if cover > AA_MASK as i32 {
             cover = AA_MASK as i32;
         }
-        cover as u32
+        self.gamma[cover as usize] as u32
     }
 
     /// Internal: add a style to the active style table.
@@ -1312,6 +1323,19 @@ mod tests {
         assert_eq!(ras.calculate_alpha(0), 0);
     }
 
+    #[test]
+    fn test_gamma() {
+        use crate::gamma::{GammaMultiply, GammaNone};
+
+        let mut ras = RasterizerCompoundAa::new();
+        let half_area = (POLY_SUBPIXEL_SCALE * POLY_SUBPIXEL_SCALE) as i32;
+        assert_eq!(ras.calculate_alpha(half_area), 128);
+        ras.gamma(&GammaMultiply::new(1.5));
+        assert_eq!(ras.calculate_alpha(half_area), 192);
+        ras.gamma(&GammaNone);
+        assert_eq!(ras.calculate_alpha(half_area), 128);
+    }
+
     #[test]
     fn test_add_path() {
         use crate::path_storage::PathStorage;
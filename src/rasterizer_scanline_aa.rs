@@ -1,18 +1,24 @@
 //! High-level polygon scanline rasterizer with anti-aliasing.
 //!
-//! Port of `agg_rasterizer_scanline_aa_nogamma.h` — the heart of AGG's
+//! Port of `agg_rasterizer_scanline_aa.h` — the heart of AGG's
 //! rendering pipeline. Accepts polygon contours (move_to/line_to/close),
 //! rasterizes them into anti-aliased scanlines, and feeds the scanlines
 //! to a renderer.
 //!
-//! This is the "nogamma" variant that returns raw coverage values (0..255).
-//! Gamma correction can be applied in the renderer or pixel format layer.
+//! Coverage values (0..255) pass through a gamma table set with `gamma()`.
+//! The default table is the identity, which matches the C++ "nogamma"
+//! variant bit for bit.
+//!
+//! Vertices with a non-finite coordinate are skipped, as if absent from the
+//! path, so `NaN`s and infinities from upstream geometry cannot reach the
+//! fixed-point conversion.
 
 use crate::basics::{
-    is_close, is_move_to, is_stop, is_vertex, FillingRule, VertexSource, POLY_SUBPIXEL_SHIFT,
+    is_close, is_move_to, is_stop, is_vertex, FillingRule, RectI, VertexSource, POLY_SUBPIXEL_SHIFT,
 };
-use crate::rasterizer_cells_aa::{CellAa, RasterizerCellsAa, ScanlineHitTest};
-use crate::rasterizer_sl_clip::{poly_coord, RasterizerSlClipInt};
+use crate::gamma::{coverage_gamma_table, GammaFunction, GammaNone};
+use crate::rasterizer_cells_aa::{CellAa, CellLimitExceeded, RasterizerCellsAa, ScanlineHitTest};
+use crate::rasterizer_sl_clip::{RasConv, RasterizerClip, RasterizerSlClipInt};
 
 // ============================================================================
 // AA scale constants
@@ -66,34 +72,49 @@ enum Status {
 
 /// High-level polygon rasterizer with anti-aliased output.
 ///
-/// Port of C++ `rasterizer_scanline_aa_nogamma<rasterizer_sl_clip_int>`.
+/// Port of C++ `rasterizer_scanline_aa<Clip>`. The clipper defaults to
+/// `RasterizerSlClipInt`, as in C++; `RasterizerSlClipDbl` clips in doubles,
+/// which keeps geometry far outside the 24.8 range correct (see
+/// [`with_clipper`](Self::with_clipper)).
 ///
 /// Usage:
-/// 1. Optionally set `filling_rule()` and `clip_box()`
+/// 1. Optionally set `filling_rule()`, `clip_box()` and `gamma()`
 /// 2. Define contours with `move_to_d()` / `line_to_d()` or `add_path()`
 /// 3. Call `rewind_scanlines()` then repeatedly `sweep_scanline()` to extract AA data
-pub struct RasterizerScanlineAa {
+pub struct RasterizerScanlineAa<CL: RasterizerClip = RasterizerSlClipInt> {
     outline: RasterizerCellsAa,
-    clipper: RasterizerSlClipInt,
+    clipper: CL,
     filling_rule: FillingRule,
     auto_close: bool,
-    start_x: i32,
-    start_y: i32,
+    start_x: Coord<CL>,
+    start_y: Coord<CL>,
     status: Status,
     scan_y: i32,
+    gamma: [u8; AA_SCALE as usize],
 }
 
+/// Coordinate type of clipper `CL`.
+type Coord<CL> = <<CL as RasterizerClip>::Conv as RasConv>::Coord;
+
 impl RasterizerScanlineAa {
     pub fn new() -> Self {
+        Self::with_clipper(RasterizerSlClipInt::new())
+    }
+}
+
+impl<CL: RasterizerClip> RasterizerScanlineAa<CL> {
+    /// Create a rasterizer that clips with `clipper`.
+    pub fn with_clipper(clipper: CL) -> Self {
         Self {
             outline: RasterizerCellsAa::new(),
-            clipper: RasterizerSlClipInt::new(),
+            clipper,
             filling_rule: FillingRule::NonZero,
             auto_close: true,
-            start_x: 0,
-            start_y: 0,
+            start_x: CL::Conv::downscale(0),
+            start_y: CL::Conv::downscale(0),
             status: Status::Initial,
             scan_y: 0,
+            gamma: coverage_gamma_table(&GammaNone),
         }
     }
 
@@ -108,19 +129,34 @@ impl RasterizerScanlineAa {
         self.filling_rule = rule;
     }
 
+    /// Map every coverage value through `gamma_function`.
+    ///
+    /// Takes effect for subsequent sweeps, so it can be changed per draw
+    /// call. `GammaNone` restores raw coverage.
+    pub fn gamma<G: GammaFunction + ?Sized>(&mut self, gamma_function: &G) {
+        self.gamma = coverage_gamma_table(gamma_function);
+    }
+
     /// Enable or disable automatic polygon closing on move_to.
     pub fn auto_close(&mut self, flag: bool) {
         self.auto_close = flag;
     }
 
+    /// Cap the number of cells an outline may produce, bounding memory on
+    /// untrusted input. See [`RasterizerCellsAa::set_cell_limit`];
+    /// `try_add_path` and `try_sort` report when the cap was hit.
+    pub fn cell_limit(&mut self, limit: usize) {
+        self.outline.set_cell_limit(limit);
+    }
+
     /// Set the clipping rectangle in floating-point coordinates.
     pub fn clip_box(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
         self.reset();
         self.clipper.clip_box(
-            poly_coord(x1),
-            poly_coord(y1),
-            poly_coord(x2),
-            poly_coord(y2),
+            CL::Conv::upscale(x1),
+            CL::Conv::upscale(y1),
+            CL::Conv::upscale(x2),
+            CL::Conv::upscale(y2),
         );
     }
 
@@ -130,6 +166,26 @@ impl RasterizerScanlineAa {
         self.clipper.reset_clipping();
     }
 
+    /// Only produce coverage for the inclusive pixel rectangle `x1..=x2`,
+    /// `y1..=y2`. Not part of AGG.
+    ///
+    /// Unlike `clip_box`, which splits edges at the box (rounding the split
+    /// points), edges are rasterized whole and only the resulting cells are
+    /// restricted, so the pixels inside match an unrestricted pass exactly.
+    /// A span may start one pixel left of the window; the renderer's own
+    /// clipping drops that pixel. Rendering large images in tiles or bands
+    /// relies on this. The corners may be given in any order.
+    pub fn window(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+        self.reset();
+        self.outline.set_window(Some(RectI::new(x1, y1, x2, y2)));
+    }
+
+    /// Remove the window set by `window`.
+    pub fn reset_window(&mut self) {
+        self.reset();
+        self.outline.set_window(None);
+    }
+
     // ========================================================================
     // Path building
     // ========================================================================
@@ -151,39 +207,53 @@ impl RasterizerScanlineAa {
         if self.auto_close {
             self.close_polygon();
         }
-        // For ras_conv_int, downscale is identity
-        self.start_x = x;
-        self.start_y = y;
-        self.clipper.move_to(x, y);
+        self.start_x = CL::Conv::downscale(x);
+        self.start_y = CL::Conv::downscale(y);
+        self.clipper.move_to(self.start_x, self.start_y);
         self.status = Status::MoveTo;
     }
 
     /// Line to in 24.8 fixed-point coordinates.
     pub fn line_to(&mut self, x: i32, y: i32) {
-        self.clipper.line_to(&mut self.outline, x, y);
+        self.clipper.line_to(
+            &mut self.outline,
+            CL::Conv::downscale(x),
+            CL::Conv::downscale(y),
+        );
         self.status = Status::LineTo;
     }
 
-    /// Move to a new position in floating-point coordinates.
+    /// Move to a new position in floating-point coordinates. Ignored if
+    /// either coordinate is non-finite.
     pub fn move_to_d(&mut self, x: f64, y: f64) {
+        if !(x.is_finite() && y.is_finite()) {
+            return;
+        }
         if self.outline.sorted() {
             self.reset();
         }
         if self.auto_close {
             self.close_polygon();
         }
-        let sx = poly_coord(x);
-        let sy = poly_coord(y);
+        let sx = CL::Conv::upscale(x);
+        let sy = CL::Conv::upscale(y);
         self.start_x = sx;
         self.start_y = sy;
         self.clipper.move_to(sx, sy);
         self.status = Status::MoveTo;
     }
 
-    /// Line to in floating-point coordinates.
+    /// Line to in floating-point coordinates. Ignored if either coordinate
+    /// is non-finite.
     pub fn line_to_d(&mut self, x: f64, y: f64) {
-        self.clipper
-            .line_to(&mut self.outline, poly_coord(x), poly_coord(y));
+        if !(x.is_finite() && y.is_finite()) {
+            return;
+        }
+        self.clipper.line_to(
+            &mut self.outline,
+            CL::Conv::upscale(x),
+            CL::Conv::upscale(y),
+        );
         self.status = Status::LineTo;
     }
 
@@ -203,24 +273,41 @@ impl RasterizerScanlineAa {
         if self.outline.sorted() {
             self.reset();
         }
-        self.clipper.move_to(x1, y1);
-        self.clipper.line_to(&mut self.outline, x2, y2);
+        self.clipper
+            .move_to(CL::Conv::downscale(x1), CL::Conv::downscale(y1));
+        self.clipper.line_to(
+            &mut self.outline,
+            CL::Conv::downscale(x2),
+            CL::Conv::downscale(y2),
+        );
         self.status = Status::MoveTo;
     }
 
-    /// Add a single edge in floating-point coordinates.
+    /// Add a single edge in floating-point coordinates. Ignored if any
+    /// coordinate is non-finite.
     pub fn edge_d(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        if ![x1, y1, x2, y2].iter().all(|v| v.is_finite()) {
+            return;
+        }
         if self.outline.sorted() {
             self.reset();
         }
-        self.clipper.move_to(poly_coord(x1), poly_coord(y1));
         self.clipper
-            .line_to(&mut self.outline, poly_coord(x2), poly_coord(y2));
+            .move_to(CL::Conv::upscale(x1), CL::Conv::upscale(y1));
+        self.clipper.line_to(
+            &mut self.outline,
+            CL::Conv::upscale(x2),
+            CL::Conv::upscale(y2),
+        );
         self.status = Status::MoveTo;
     }
 
     /// Add all vertices from a vertex source.
-    pub fn add_path(&mut self, vs: &mut dyn VertexSource, path_id: u32) {
+    ///
+    /// Generic so a concrete converter chain is monomorphized and its
+    /// `vertex` calls inlined; `&mut dyn VertexSource` is still accepted.
+    #[inline]
+    pub fn add_path<VS: VertexSource + ?Sized>(&mut self, vs: &mut VS, path_id: u32) {
         let mut x = 0.0;
         let mut y = 0.0;
 
@@ -237,6 +324,33 @@ impl RasterizerScanlineAa {
         }
     }
 
+    /// `add_path` that fails once the outline exceeds the cell limit.
+    ///
+    /// Stops reading `vs` at that point. The rasterizer then holds a
+    /// truncated outline; `reset` it before reuse.
+    pub fn try_add_path<VS: VertexSource + ?Sized>(
+        &mut self,
+        vs: &mut VS,
+        path_id: u32,
+    ) -> Result<(), CellLimitExceeded> {
+        let mut x = 0.0;
+        let mut y = 0.0;
+
+        vs.rewind(path_id);
+        if self.outline.sorted() {
+            self.reset();
+        }
+        loop {
+            let cmd = vs.vertex(&mut x, &mut y);
+            if is_stop(cmd) {
+                break;
+            }
+            self.add_vertex(x, y, cmd);
+            self.outline.check_cell_limit()?;
+        }
+        Ok(())
+    }
+
     /// Snapshot the rasterizer's current cell array for external caching.
     ///
     /// Returns a `Vec<CellAa>` representing all cells accumulated so far. Call
@@ -331,9 +445,22 @@ impl RasterizerScanlineAa {
         self.outline.sort_cells();
     }
 
-    /// Calculate alpha (coverage) from accumulated area.
+    /// `sort` that fails if cells were dropped because of the cell limit,
+    /// including by the final closing edge and cell flushed here.
     ///
-    /// This is the "nogamma" variant — no gamma LUT, raw coverage.
+    /// An outline that already overflowed is left unsorted: its bounds still
+    /// span the dropped edges, so sorting could allocate far past the limit.
+    pub fn try_sort(&mut self) -> Result<(), CellLimitExceeded> {
+        if self.auto_close {
+            self.close_polygon();
+        }
+        self.outline.check_cell_limit()?;
+        self.outline.sort_cells();
+        self.outline.check_cell_limit()
+    }
+
+    /// Calculate alpha (coverage) from accumulated area, through the gamma
+    /// table.
     #[inline]
     pub fn calculate_alpha(&self, area: i32) -> u32 {
         let mut cover = area >> (POLY_SUBPIXEL_SHIFT * 2 + 1 - AA_SHIFT);
@@ -350,7 +477,7 @@ impl RasterizerScanlineAa {
         if cover > AA_MASK as i32 {
             cover = AA_MASK as i32;
         }
-        cover as u32
+        self.gamma[cover as usize] as u32
     }
 
     /// Extract the next scanline of anti-aliased coverage data.
@@ -507,9 +634,9 @@ impl RasterizerScanlineAa {
     }
 }
 
-impl Default for RasterizerScanlineAa {
+impl<CL: RasterizerClip> Default for RasterizerScanlineAa<CL> {
     fn default() -> Self {
-        Self::new()
+        Self::with_clipper(CL::default())
     }
 }
 
@@ -523,6 +650,7 @@ mod tests {
     use crate::basics::{PATH_FLAGS_NONE, POLY_SUBPIXEL_SCALE};
     use crate::ellipse::Ellipse;
     use crate::path_storage::PathStorage;
+    use crate::rasterizer_sl_clip::RasterizerSlClipDbl;
 
     /// Minimal scanline for testing: just tracks cells and spans.
     struct TestScanline {
@@ -614,6 +742,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gamma_maps_coverage() {
+        use crate::gamma::{GammaNone, GammaPower, GammaThreshold};
+
+        let mut ras = RasterizerScanlineAa::new();
+        // A quarter-covered cell: raw alpha 64.
+        let area = (POLY_SUBPIXEL_SCALE as i32) << (POLY_SUBPIXEL_SHIFT - 1);
+        assert_eq!(ras.calculate_alpha(area), 64);
+
+        ras.gamma(&GammaPower::new(0.5));
+        assert_eq!(ras.calculate_alpha(area), 128);
+        ras.gamma(&GammaThreshold::new(0.5));
+        assert_eq!(ras.calculate_alpha(area), 0);
+        assert_eq!(ras.calculate_alpha(area * 3), 255);
+        ras.gamma(&GammaNone);
+        assert_eq!(ras.calculate_alpha(area), 64);
+    }
+
+    #[test]
+    fn test_gamma_threshold_sweep_is_aliased() {
+        use crate::gamma::GammaThreshold;
+
+        let mut ras = RasterizerScanlineAa::new();
+        ras.gamma(&GammaThreshold::new(0.5));
+        ras.move_to_d(10.3, 10.7);
+        ras.line_to_d(40.1, 12.2);
+        ras.line_to_d(22.6, 35.4);
+        assert!(ras.rewind_scanlines());
+        let mut sl = TestScanline::new();
+        while ras.sweep_scanline(&mut sl) {
+            for &(_, _, cover) in &sl.spans {
+                assert!(cover == 0 || cover == 255, "cover {cover}");
+            }
+        }
+    }
+
     #[test]
     fn test_triangle_sweep() {
         let mut ras = RasterizerScanlineAa::new();
@@ -705,6 +869,23 @@ mod tests {
         assert!(ras.hit_test(50, 50));
     }
 
+    #[test]
+    fn test_add_path_dyn_matches_generic() {
+        let mut ellipse = Ellipse::new(50.0, 50.0, 20.0, 15.0, 32, false);
+        let mut generic = RasterizerScanlineAa::new();
+        generic.add_path(&mut ellipse, 0);
+
+        let vs: &mut dyn VertexSource = &mut ellipse;
+        let mut dynamic = RasterizerScanlineAa::new();
+        dynamic.add_path(vs, 0);
+
+        let cells = |ras: &mut RasterizerScanlineAa| -> Vec<(i32, i32, i32, i32)> {
+            let cells = ras.outline_cells();
+            cells.iter().map(|c| (c.x, c.y, c.cover, c.area)).collect()
+        };
+        assert_eq!(cells(&mut generic), cells(&mut dynamic));
+    }
+
     #[test]
     fn test_empty_rasterizer_no_scanlines() {
         let mut ras = RasterizerScanlineAa::new();
@@ -737,6 +918,58 @@ mod tests {
         assert!(ras.max_y() <= 50);
     }
 
+    #[test]
+    fn test_window_matches_unrestricted() {
+        // Per-pixel coverage inside (x1..=x2, y1..=y2) from a full sweep.
+        fn coverage(ras: &mut RasterizerScanlineAa, win: (i32, i32, i32, i32)) -> Vec<u32> {
+            let (x1, y1, x2, y2) = win;
+            let w = (x2 - x1 + 1) as usize;
+            let mut out = vec![0; w * (y2 - y1 + 1) as usize];
+            for (y, x, len, cover) in collect_scanlines(ras) {
+                for px in x..x + len as i32 {
+                    if (x1..=x2).contains(&px) && (y1..=y2).contains(&y) {
+                        out[(y - y1) as usize * w + (px - x1) as usize] = cover;
+                    }
+                }
+            }
+            out
+        }
+
+        let win = (17, 9, 42, 31);
+        let mut ras = RasterizerScanlineAa::new();
+        ras.add_path(&mut Ellipse::new(30.3, 25.6, 21.7, 18.2, 40, false), 0);
+        assert!(ras.rewind_scanlines());
+        let full_cells = ras.outline.total_cells();
+        let expected = coverage(&mut ras, win);
+
+        ras.window(win.0, win.1, win.2, win.3);
+        ras.add_path(&mut Ellipse::new(30.3, 25.6, 21.7, 18.2, 40, false), 0);
+        assert!(ras.rewind_scanlines());
+        assert!(ras.outline.total_cells() < full_cells);
+        assert!(ras.min_y() >= win.1 && ras.max_y() <= win.3);
+        assert_eq!(coverage(&mut ras, win), expected);
+    }
+
+    #[test]
+    fn test_window_corners_in_any_order() {
+        let rect = |ras: &mut RasterizerScanlineAa| {
+            ras.move_to_d(0.0, 0.0);
+            ras.line_to_d(20.0, 0.0);
+            ras.line_to_d(20.0, 20.0);
+            ras.line_to_d(0.0, 20.0);
+        };
+        let mut ras = RasterizerScanlineAa::new();
+        ras.window(5, 2, 10, 8);
+        rect(&mut ras);
+        assert!(ras.rewind_scanlines());
+        let expected = collect_scanlines(&mut ras);
+
+        ras.window(10, 8, 5, 2);
+        rect(&mut ras);
+        assert!(ras.rewind_scanlines());
+        assert_eq!(collect_scanlines(&mut ras), expected);
+    }
+
     #[test]
     fn test_navigate_scanline() {
         let mut ras = RasterizerScanlineAa::new();
@@ -767,7 +1000,9 @@ mod tests {
     }
 
     /// Sweep all scanlines from a rasterizer into a flat list of (y, x, len, cover) tuples.
-    fn collect_scanlines(ras: &mut RasterizerScanlineAa) -> Vec<(i32, i32, u32, u32)> {
+    fn collect_scanlines<CL: RasterizerClip>(
+        ras: &mut RasterizerScanlineAa<CL>,
+    ) -> Vec<(i32, i32, u32, u32)> {
         let mut sl = TestScanline::new();
         let mut result = Vec::new();
         while ras.sweep_scanline(&mut sl) {
@@ -778,6 +1013,72 @@ mod tests {
         result
     }
 
+    #[test]
+    fn test_dbl_clipper_matches_int() {
+        let mut int = RasterizerScanlineAa::new();
+        let mut dbl = RasterizerScanlineAa::with_clipper(RasterizerSlClipDbl::new());
+        int.clip_box(0.0, 0.0, 60.0, 50.0);
+        dbl.clip_box(0.0, 0.0, 60.0, 50.0);
+        let mut ellipse = Ellipse::new(40.0, 30.0, 35.0, 25.0, 64, false);
+        int.add_path(&mut ellipse, 0);
+        dbl.add_path(&mut ellipse, 0);
+
+        assert!(int.rewind_scanlines() && dbl.rewind_scanlines());
+        assert_eq!(collect_scanlines(&mut dbl), collect_scanlines(&mut int));
+    }
+
+    #[test]
+    fn test_dbl_clipper_far_geometry() {
+        // A band whose ends lie far outside the 24.8 fixed-point range.
+        let mut ras = RasterizerScanlineAa::<RasterizerSlClipDbl>::default();
+        ras.clip_box(0.0, 0.0, 20.0, 20.0);
+        ras.move_to_d(-1e9, 5.0);
+        ras.line_to_d(1e9, 5.0);
+        ras.line_to_d(1e9, 8.0);
+        ras.line_to_d(-1e9, 8.0);
+
+        assert!(ras.rewind_scanlines());
+        let spans = collect_scanlines(&mut ras);
+        let rows: Vec<i32> = spans.iter().map(|s| s.0).collect();
+        assert_eq!(rows, [5, 6, 7]);
+        assert!(spans.iter().all(|&(_, x, len, _)| x == 0 && len == 20));
+        assert!(spans.iter().all(|&(_, _, _, cover)| cover == 255));
+    }
+
+    #[test]
+    fn test_try_add_path_cell_limit() {
+        let mut ellipse = Ellipse::new(100.0, 100.0, 80.0, 60.0, 100, false);
+        let mut ras = RasterizerScanlineAa::new();
+        ras.cell_limit(50);
+        let err = ras.try_add_path(&mut ellipse, 0).unwrap_err();
+        assert_eq!(err.limit, 50);
+        assert!(err.to_string().contains("limit of 50 cells"));
+        assert!(ras.try_sort().is_err());
+
+        // Within the limit, the result matches plain `add_path`.
+        let mut plain = RasterizerScanlineAa::new();
+        plain.add_path(&mut ellipse, 0);
+        ras.reset();
+        ras.cell_limit(100_000);
+        ras.try_add_path(&mut ellipse, 0).unwrap();
+        ras.try_sort().unwrap();
+        assert!(ras.rewind_scanlines() && plain.rewind_scanlines());
+        assert_eq!(collect_scanlines(&mut ras), collect_scanlines(&mut plain));
+    }
+
+    #[test]
+    fn test_try_sort_skips_overflowed_outline() {
+        // The edge to y = 1e6 is dropped past the limit but still widens the
+        // outline's bounds; sorting would size the scanline index by them.
+        let mut ras = RasterizerScanlineAa::new();
+        ras.cell_limit(10);
+        ras.move_to_d(0.0, 0.0);
+        ras.line_to_d(100.0, 3.0);
+        ras.line_to_d(50.0, 1e6);
+        assert!(ras.try_sort().is_err());
+        assert!(!ras.outline.sorted());
+    }
+
     /// Verify that outline_cells() + add_cells_offset(dx, dy) produces identical
     /// scanline output to rasterizing the same path translated by (dx, dy) directly.
     ///
@@ -854,4 +1155,151 @@ mod tests {
             assert_eq!(direct, replayed, "mismatch at offset ({dx}, {dy})");
         }
     }
+
+    // ------------------------------------------------------------------
+    // Fuzz-style robustness: arbitrary f64 input must not panic or hang
+    // ------------------------------------------------------------------
+
+    /// xorshift64* generator, so the fuzz tests are deterministic.
+    struct Fuzz(u64);
+
+    impl Fuzz {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        }
+
+        fn ordinary(&mut self) -> f64 {
+            (self.next() % 20_000) as f64 / 100.0 - 50.0
+        }
+
+        /// A coordinate: non-finite, extreme, arbitrary bits or ordinary.
+        fn value(&mut self) -> f64 {
+            const SPECIAL: [f64; 9] = [
+                f64::NAN,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::MAX,
+                f64::MIN,
+                1e300,
+                -1e-300,
+                f64::MIN_POSITIVE,
+                -0.0,
+            ];
+            match self.next() % 5 {
+                0 => SPECIAL[(self.next() % SPECIAL.len() as u64) as usize],
+                1 => f64::from_bits(self.next()),
+                _ => self.ordinary(),
+            }
+        }
+
+        /// A non-finite or ordinary value. Enormous finite stroke widths and
+        /// dash lengths legitimately need enormous numbers of vertices.
+        fn param(&mut self) -> f64 {
+            const NON_FINITE: [f64; 3] = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+            match self.next() % 4 {
+                0 => NON_FINITE[(self.next() % 3) as usize],
+                _ => self.ordinary(),
+            }
+        }
+
+        fn path(&mut self, value: fn(&mut Self) -> f64) -> PathStorage {
+            let mut path = PathStorage::new();
+            path.move_to(value(self), value(self));
+            for _ in 0..self.next() % 12 {
+                let cmd = self.next() % 6;
+                let v: Vec<f64> = (0..6).map(|_| value(self)).collect();
+                match cmd {
+                    0 => path.move_to(v[0], v[1]),
+                    1 => path.curve3(v[0], v[1], v[2], v[3]),
+                    2 => path.curve4(v[0], v[1], v[2], v[3], v[4], v[5]),
+                    3 => {
+                        let (large_arc, sweep) = (self.next() % 2 == 0, self.next() % 2 == 0);
+                        path.arc_to(v[0], v[1], v[2], large_arc, sweep, v[3], v[4])
+                    }
+                    4 => path.close_polygon(PATH_FLAGS_NONE),
+                    _ => path.line_to(v[0], v[1]),
+                }
+            }
+            path
+        }
+    }
+
+    /// Rasterize `vs` with both clippers, sweeping every scanline, and
+    /// unclipped under a cell limit.
+    fn fuzz_rasterize(vs: &mut dyn VertexSource) {
+        let mut int = RasterizerScanlineAa::new();
+        int.clip_box(0.0, 0.0, 64.0, 64.0);
+        int.add_path(vs, 0);
+        if int.rewind_scanlines() {
+            collect_scanlines(&mut int);
+        }
+
+        let mut dbl = RasterizerScanlineAa::with_clipper(RasterizerSlClipDbl::new());
+        dbl.clip_box(0.0, 0.0, 64.0, 64.0);
+        dbl.add_path(vs, 0);
+        if dbl.rewind_scanlines() {
+            collect_scanlines(&mut dbl);
+        }
+
+        // Unclipped, huge finite coordinates legitimately span millions of
+        // rows, so only the cell generation is exercised.
+        let mut unclipped = RasterizerScanlineAa::new();
+        unclipped.cell_limit(10_000);
+        unclipped.add_path(vs, 0);
+    }
+
+    #[test]
+    fn test_fuzz_non_finite_pipeline() {
+        use crate::conv_curve::ConvCurve;
+        use crate::conv_dash::ConvDash;
+        use crate::conv_stroke::ConvStroke;
+
+        let mut fuzz = Fuzz(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..100 {
+            let path = fuzz.path(Fuzz::value);
+            fuzz_rasterize(&mut ConvCurve::new(path.clone()));
+
+            let mut stroke = ConvStroke::new(ConvCurve::new(path));
+            stroke.set_width(fuzz.param());
+            fuzz_rasterize(&mut stroke);
+
+            // Dashing emits a vertex per dash, so huge finite coordinates
+            // are left out here.
+            let path = fuzz.path(Fuzz::param);
+            let mut dash = ConvDash::new(ConvCurve::new(path));
+            dash.add_dash(fuzz.param(), fuzz.param());
+            dash.dash_start(fuzz.param());
+            let mut stroke = ConvStroke::new(dash);
+            stroke.set_width(4.0);
+            fuzz_rasterize(&mut stroke);
+        }
+    }
+
+    #[test]
+    fn test_fuzz_bezier_arc() {
+        use crate::bezier_arc::{BezierArc, BezierArcSvg};
+        use crate::conv_stroke::ConvStroke;
+
+        let mut fuzz = Fuzz(0xD1B5_4A32_D192_ED03);
+        for _ in 0..200 {
+            let v: Vec<f64> = (0..7).map(|_| fuzz.value()).collect();
+            let mut arc = BezierArc::new_with_params(v[0], v[1], v[2], v[3], v[4], v[5]);
+            fuzz_rasterize(&mut arc);
+            let mut svg = BezierArcSvg::new_with_params(
+                v[0],
+                v[1],
+                v[2],
+                v[3],
+                v[4],
+                fuzz.next() % 2 == 0,
+                fuzz.next() % 2 == 0,
+                v[5],
+                v[6],
+            );
+            fuzz_rasterize(&mut ConvStroke::new(&mut svg));
+        }
+    }
 }
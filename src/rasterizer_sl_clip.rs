@@ -3,9 +3,21 @@
 //! Port of `agg_rasterizer_sl_clip.h` — coordinate conversion (double → 24.8
 //! fixed-point) and optional viewport clipping for the scanline rasterizer.
 //!
-//! Provides two concrete clipper types:
-//! - `RasterizerSlClipInt` — default, with viewport clipping
-//! - `RasterizerSlNoClip` — passthrough, no clipping
+//! The coordinate conversions (`ras_conv_*`) implement [`RasConv`]:
+//! - `RasConvInt` — clip on 24.8 fixed-point integers
+//! - `RasConvDbl` — clip on doubles, convert to 24.8 afterwards
+//! - `RasConvDbl3x` — as `RasConvDbl`, with X tripled for LCD subpixel output
+//!
+//! [`RasterizerSlClip`] clips with any of them; the `RasterizerSlClipInt`,
+//! `RasterizerSlClipDbl` and `RasterizerSlClipDbl3x` aliases name the
+//! combinations. `RasterizerSlNoClip` is a passthrough without clipping. All
+//! implement [`RasterizerClip`], the clipper parameter of
+//! `RasterizerScanlineAa`.
+//!
+//! The `_d` entry points ignore points with a non-finite coordinate, and
+//! integer upscaling saturates at ±2^30 instead of overflowing.
+
+use std::ops::{Add, Sub};
 
 use crate::basics::{iround, Rect, POLY_SUBPIXEL_SCALE};
 use crate::clip_liang_barsky::{clipping_flags, clipping_flags_y};
@@ -15,52 +27,197 @@ use crate::rasterizer_cells_aa::RasterizerCellsAa;
 // Coordinate conversion helpers (port of ras_conv_int)
 // ============================================================================
 
-/// Convert double to 24.8 fixed-point (upscale).
+/// Largest magnitude of an upscaled integer coordinate. Differences of two
+/// coordinates, as the clipper takes them, then stay within `i32`.
+const UPSCALE_LIMIT: f64 = ((1 << 30) - 1) as f64;
+
+/// Convert double to 24.8 fixed-point (upscale), saturating at
+/// ±`UPSCALE_LIMIT`.
 #[inline]
 fn upscale(v: f64) -> i32 {
-    iround(v * POLY_SUBPIXEL_SCALE as f64)
+    iround((v * POLY_SUBPIXEL_SCALE as f64).clamp(-UPSCALE_LIMIT, UPSCALE_LIMIT))
 }
 
-/// Integer coordinate to subpixel X (identity for int conv).
+/// Mul-div for integer coordinates: round(a * b / c).
 #[inline]
-fn xi(v: i32) -> i32 {
-    v
+fn mul_div(a: i32, b: i32, c: i32) -> i32 {
+    iround(a as f64 * b as f64 / c as f64)
 }
 
-/// Integer coordinate to subpixel Y (identity for int conv).
-#[inline]
-fn yi(v: i32) -> i32 {
-    v
+// ============================================================================
+// RasConv — coordinate conversion policies
+// ============================================================================
+
+/// Coordinate type and conversions a clipper works in.
+///
+/// Port of the C++ `ras_conv_*` policy structs.
+pub trait RasConv {
+    /// Coordinate type clipping happens in.
+    type Coord: Copy + PartialOrd + Add<Output = Self::Coord> + Sub<Output = Self::Coord>;
+
+    /// `a * b / c`, as used to intersect an edge with the clip box.
+    fn mul_div(a: Self::Coord, b: Self::Coord, c: Self::Coord) -> Self::Coord;
+    /// Clipper coordinate to 24.8 fixed-point cell X.
+    fn xi(v: Self::Coord) -> i32;
+    /// Clipper coordinate to 24.8 fixed-point cell Y.
+    fn yi(v: Self::Coord) -> i32;
+    /// Pixel coordinate to clipper coordinate.
+    fn upscale(v: f64) -> Self::Coord;
+    /// 24.8 fixed-point coordinate to clipper coordinate.
+    fn downscale(v: i32) -> Self::Coord;
 }
 
-/// Mul-div for integer coordinates: round(a * b / c).
-#[inline]
-fn mul_div(a: i32, b: i32, c: i32) -> i32 {
-    iround(a as f64 * b as f64 / c as f64)
+/// Clip in 24.8 fixed-point integers. Port of C++ `ras_conv_int`.
+pub struct RasConvInt;
+
+impl RasConv for RasConvInt {
+    type Coord = i32;
+
+    #[inline]
+    fn mul_div(a: i32, b: i32, c: i32) -> i32 {
+        mul_div(a, b, c)
+    }
+    #[inline]
+    fn xi(v: i32) -> i32 {
+        v
+    }
+    #[inline]
+    fn yi(v: i32) -> i32 {
+        v
+    }
+    #[inline]
+    fn upscale(v: f64) -> i32 {
+        upscale(v)
+    }
+    #[inline]
+    fn downscale(v: i32) -> i32 {
+        v
+    }
+}
+
+/// Clip in pixel-unit doubles, converting to 24.8 only after clipping, so
+/// coordinates far outside the 24.8 range are clipped correctly.
+///
+/// Port of C++ `ras_conv_dbl`.
+pub struct RasConvDbl;
+
+impl RasConv for RasConvDbl {
+    type Coord = f64;
+
+    #[inline]
+    fn mul_div(a: f64, b: f64, c: f64) -> f64 {
+        a * b / c
+    }
+    #[inline]
+    fn xi(v: f64) -> i32 {
+        iround(v * POLY_SUBPIXEL_SCALE as f64)
+    }
+    #[inline]
+    fn yi(v: f64) -> i32 {
+        iround(v * POLY_SUBPIXEL_SCALE as f64)
+    }
+    #[inline]
+    fn upscale(v: f64) -> f64 {
+        v
+    }
+    #[inline]
+    fn downscale(v: i32) -> f64 {
+        v as f64 / POLY_SUBPIXEL_SCALE as f64
+    }
+}
+
+/// `RasConvDbl` with X scaled by 3, for LCD subpixel rendering.
+///
+/// Port of C++ `ras_conv_dbl_3x`.
+pub struct RasConvDbl3x;
+
+impl RasConv for RasConvDbl3x {
+    type Coord = f64;
+
+    #[inline]
+    fn mul_div(a: f64, b: f64, c: f64) -> f64 {
+        a * b / c
+    }
+    #[inline]
+    fn xi(v: f64) -> i32 {
+        iround(v * POLY_SUBPIXEL_SCALE as f64 * 3.0)
+    }
+    #[inline]
+    fn yi(v: f64) -> i32 {
+        iround(v * POLY_SUBPIXEL_SCALE as f64)
+    }
+    #[inline]
+    fn upscale(v: f64) -> f64 {
+        v
+    }
+    #[inline]
+    fn downscale(v: i32) -> f64 {
+        v as f64 / POLY_SUBPIXEL_SCALE as f64
+    }
 }
 
 // ============================================================================
-// RasterizerSlClipInt — clipping policy with viewport clipping
+// RasterizerClip — the clipper interface used by RasterizerScanlineAa
+// ============================================================================
+
+/// Clipping policy of a scanline rasterizer: receives the outline in
+/// clipper coordinates and feeds clipped edges to the cell rasterizer.
+///
+/// The C++ `Clip` template parameter of `rasterizer_scanline_aa`.
+pub trait RasterizerClip: Default {
+    type Conv: RasConv;
+
+    /// Disable clipping.
+    fn reset_clipping(&mut self);
+    /// Set the clipping rectangle in clipper coordinates.
+    fn clip_box(
+        &mut self,
+        x1: <Self::Conv as RasConv>::Coord,
+        y1: <Self::Conv as RasConv>::Coord,
+        x2: <Self::Conv as RasConv>::Coord,
+        y2: <Self::Conv as RasConv>::Coord,
+    );
+    /// Start a new contour.
+    fn move_to(&mut self, x1: <Self::Conv as RasConv>::Coord, y1: <Self::Conv as RasConv>::Coord);
+    /// Clip the edge to `(x2, y2)` and emit it to `ras`.
+    fn line_to(
+        &mut self,
+        ras: &mut RasterizerCellsAa,
+        x2: <Self::Conv as RasConv>::Coord,
+        y2: <Self::Conv as RasConv>::Coord,
+    );
+}
+
+// ============================================================================
+// RasterizerSlClip — clipping policy with viewport clipping
 // ============================================================================
 
 /// Scanline rasterizer clipping policy that clips line segments against
 /// a viewport rectangle, then converts to 24.8 fixed-point coordinates.
 ///
-/// Port of C++ `rasterizer_sl_clip<ras_conv_int>`.
-pub struct RasterizerSlClipInt {
-    clip_box: Rect<i32>,
-    x1: i32,
-    y1: i32,
+/// Port of C++ `rasterizer_sl_clip<Conv>`.
+pub struct RasterizerSlClip<C: RasConv> {
+    clip_box: Rect<C::Coord>,
+    x1: C::Coord,
+    y1: C::Coord,
     f1: u32,
     clipping: bool,
 }
 
-impl RasterizerSlClipInt {
+/// Clips on 24.8 fixed-point integers. Port of C++ `rasterizer_sl_clip_int`.
+pub type RasterizerSlClipInt = RasterizerSlClip<RasConvInt>;
+/// Clips on doubles. Port of C++ `rasterizer_sl_clip_dbl`.
+pub type RasterizerSlClipDbl = RasterizerSlClip<RasConvDbl>;
+/// Clips on doubles, tripling X. Port of C++ `rasterizer_sl_clip_dbl_3x`.
+pub type RasterizerSlClipDbl3x = RasterizerSlClip<RasConvDbl3x>;
+
+impl<C: RasConv> RasterizerSlClip<C> {
     pub fn new() -> Self {
+        let zero = C::downscale(0);
         Self {
-            clip_box: Rect::new(0, 0, 0, 0),
-            x1: 0,
-            y1: 0,
+            clip_box: Rect::new(zero, zero, zero, zero),
+            x1: zero,
+            y1: zero,
             f1: 0,
             clipping: false,
         }
@@ -71,15 +228,16 @@ impl RasterizerSlClipInt {
         self.clipping = false;
     }
 
-    /// Set the clipping rectangle in 24.8 fixed-point coordinates.
-    pub fn clip_box(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+    /// Set the clipping rectangle in clipper coordinates (24.8 fixed-point
+    /// for `RasConvInt`, pixels for the double conversions).
+    pub fn clip_box(&mut self, x1: C::Coord, y1: C::Coord, x2: C::Coord, y2: C::Coord) {
         self.clip_box = Rect::new(x1, y1, x2, y2);
         self.clip_box.normalize();
         self.clipping = true;
     }
 
-    /// Record a move_to in 24.8 fixed-point coordinates.
-    pub fn move_to(&mut self, x1: i32, y1: i32) {
+    /// Record a move_to in clipper coordinates.
+    pub fn move_to(&mut self, x1: C::Coord, y1: C::Coord) {
         self.x1 = x1;
         self.y1 = y1;
         if self.clipping {
@@ -87,15 +245,18 @@ impl RasterizerSlClipInt {
         }
     }
 
-    /// Record a move_to from double coordinates (upscales to 24.8).
+    /// Record a move_to from double coordinates. Non-finite points are
+    /// ignored.
     pub fn move_to_d(&mut self, x: f64, y: f64) {
-        self.move_to(upscale(x), upscale(y));
+        if x.is_finite() && y.is_finite() {
+            self.move_to(C::upscale(x), C::upscale(y));
+        }
     }
 
     /// Clip and emit a line segment to the cell rasterizer.
     ///
     /// Implements the 13-case clipping switch from C++ `line_to`.
-    pub fn line_to(&mut self, ras: &mut RasterizerCellsAa, x2: i32, y2: i32) {
+    pub fn line_to(&mut self, ras: &mut RasterizerCellsAa, x2: C::Coord, y2: C::Coord) {
         if self.clipping {
             let f2 = clipping_flags(x2, y2, &self.clip_box);
 
@@ -110,6 +271,7 @@ impl RasterizerSlClipInt {
             let x1 = self.x1;
             let y1 = self.y1;
             let f1 = self.f1;
+            let cb = self.clip_box;
 
             match ((f1 & 5) << 1) | (f2 & 5) {
                 0 => {
@@ -118,59 +280,59 @@ impl RasterizerSlClipInt {
                 }
                 1 => {
                     // x2 > clip.x2
-                    let y3 = y1 + mul_div(self.clip_box.x2 - x1, y2 - y1, x2 - x1);
-                    let f3 = clipping_flags_y(y3, &self.clip_box);
-                    self.line_clip_y(ras, x1, y1, self.clip_box.x2, y3, f1, f3);
-                    self.line_clip_y(ras, self.clip_box.x2, y3, self.clip_box.x2, y2, f3, f2);
+                    let y3 = y1 + C::mul_div(cb.x2 - x1, y2 - y1, x2 - x1);
+                    let f3 = clipping_flags_y(y3, &cb);
+                    self.line_clip_y(ras, x1, y1, cb.x2, y3, f1, f3);
+                    self.line_clip_y(ras, cb.x2, y3, cb.x2, y2, f3, f2);
                 }
                 2 => {
                     // x1 > clip.x2
-                    let y3 = y1 + mul_div(self.clip_box.x2 - x1, y2 - y1, x2 - x1);
-                    let f3 = clipping_flags_y(y3, &self.clip_box);
-                    self.line_clip_y(ras, self.clip_box.x2, y1, self.clip_box.x2, y3, f1, f3);
-                    self.line_clip_y(ras, self.clip_box.x2, y3, x2, y2, f3, f2);
+                    let y3 = y1 + C::mul_div(cb.x2 - x1, y2 - y1, x2 - x1);
+                    let f3 = clipping_flags_y(y3, &cb);
+                    self.line_clip_y(ras, cb.x2, y1, cb.x2, y3, f1, f3);
+                    self.line_clip_y(ras, cb.x2, y3, x2, y2, f3, f2);
                 }
                 3 => {
                     // x1 > clip.x2 && x2 > clip.x2
-                    self.line_clip_y(ras, self.clip_box.x2, y1, self.clip_box.x2, y2, f1, f2);
+                    self.line_clip_y(ras, cb.x2, y1, cb.x2, y2, f1, f2);
                 }
                 4 => {
                     // x2 < clip.x1
-                    let y3 = y1 + mul_div(self.clip_box.x1 - x1, y2 - y1, x2 - x1);
-                    let f3 = clipping_flags_y(y3, &self.clip_box);
-                    self.line_clip_y(ras, x1, y1, self.clip_box.x1, y3, f1, f3);
-                    self.line_clip_y(ras, self.clip_box.x1, y3, self.clip_box.x1, y2, f3, f2);
+                    let y3 = y1 + C::mul_div(cb.x1 - x1, y2 - y1, x2 - x1);
+                    let f3 = clipping_flags_y(y3, &cb);
+                    self.line_clip_y(ras, x1, y1, cb.x1, y3, f1, f3);
+                    self.line_clip_y(ras, cb.x1, y3, cb.x1, y2, f3, f2);
                 }
                 6 => {
                     // x1 > clip.x2 && x2 < clip.x1
-                    let y3 = y1 + mul_div(self.clip_box.x2 - x1, y2 - y1, x2 - x1);
-                    let y4 = y1 + mul_div(self.clip_box.x1 - x1, y2 - y1, x2 - x1);
-                    let f3 = clipping_flags_y(y3, &self.clip_box);
-                    let f4 = clipping_flags_y(y4, &self.clip_box);
-                    self.line_clip_y(ras, self.clip_box.x2, y1, self.clip_box.x2, y3, f1, f3);
-                    self.line_clip_y(ras, self.clip_box.x2, y3, self.clip_box.x1, y4, f3, f4);
-                    self.line_clip_y(ras, self.clip_box.x1, y4, self.clip_box.x1, y2, f4, f2);
+                    let y3 = y1 + C::mul_div(cb.x2 - x1, y2 - y1, x2 - x1);
+                    let y4 = y1 + C::mul_div(cb.x1 - x1, y2 - y1, x2 - x1);
+                    let f3 = clipping_flags_y(y3, &cb);
+                    let f4 = clipping_flags_y(y4, &cb);
+                    self.line_clip_y(ras, cb.x2, y1, cb.x2, y3, f1, f3);
+                    self.line_clip_y(ras, cb.x2, y3, cb.x1, y4, f3, f4);
+                    self.line_clip_y(ras, cb.x1, y4, cb.x1, y2, f4, f2);
                 }
                 8 => {
                     // x1 < clip.x1
-                    let y3 = y1 + mul_div(self.clip_box.x1 - x1, y2 - y1, x2 - x1);
-                    let f3 = clipping_flags_y(y3, &self.clip_box);
-                    self.line_clip_y(ras, self.clip_box.x1, y1, self.clip_box.x1, y3, f1, f3);
-                    self.line_clip_y(ras, self.clip_box.x1, y3, x2, y2, f3, f2);
+                    let y3 = y1 + C::mul_div(cb.x1 - x1, y2 - y1, x2 - x1);
+                    let f3 = clipping_flags_y(y3, &cb);
+                    self.line_clip_y(ras, cb.x1, y1, cb.x1, y3, f1, f3);
+                    self.line_clip_y(ras, cb.x1, y3, x2, y2, f3, f2);
                 }
                 9 => {
                     // x1 < clip.x1 && x2 > clip.x2
-                    let y3 = y1 + mul_div(self.clip_box.x1 - x1, y2 - y1, x2 - x1);
-                    let y4 = y1 + mul_div(self.clip_box.x2 - x1, y2 - y1, x2 - x1);
-                    let f3 = clipping_flags_y(y3, &self.clip_box);
-                    let f4 = clipping_flags_y(y4, &self.clip_box);
-                    self.line_clip_y(ras, self.clip_box.x1, y1, self.clip_box.x1, y3, f1, f3);
-                    self.line_clip_y(ras, self.clip_box.x1, y3, self.clip_box.x2, y4, f3, f4);
-                    self.line_clip_y(ras, self.clip_box.x2, y4, self.clip_box.x2, y2, f4, f2);
+                    let y3 = y1 + C::mul_div(cb.x1 - x1, y2 - y1, x2 - x1);
+                    let y4 = y1 + C::mul_div(cb.x2 - x1, y2 - y1, x2 - x1);
+                    let f3 = clipping_flags_y(y3, &cb);
+                    let f4 = clipping_flags_y(y4, &cb);
+                    self.line_clip_y(ras, cb.x1, y1, cb.x1, y3, f1, f3);
+                    self.line_clip_y(ras, cb.x1, y3, cb.x2, y4, f3, f4);
+                    self.line_clip_y(ras, cb.x2, y4, cb.x2, y2, f4, f2);
                 }
                 12 => {
                     // x1 < clip.x1 && x2 < clip.x1
-                    self.line_clip_y(ras, self.clip_box.x1, y1, self.clip_box.x1, y2, f1, f2);
+                    self.line_clip_y(ras, cb.x1, y1, cb.x1, y2, f1, f2);
                 }
                 _ => {
                     // cases 5, 7, 10, 11 — cannot happen with valid clipping flags
@@ -178,15 +340,18 @@ impl RasterizerSlClipInt {
             }
             self.f1 = f2;
         } else {
-            ras.line(xi(self.x1), yi(self.y1), xi(x2), yi(y2));
+            ras.line(C::xi(self.x1), C::yi(self.y1), C::xi(x2), C::yi(y2));
         }
         self.x1 = x2;
         self.y1 = y2;
     }
 
-    /// Emit a line_to from double coordinates (upscales to 24.8).
+    /// Emit a line_to from double coordinates. Non-finite points are
+    /// ignored.
     pub fn line_to_d(&mut self, ras: &mut RasterizerCellsAa, x: f64, y: f64) {
-        self.line_to(ras, upscale(x), upscale(y));
+        if x.is_finite() && y.is_finite() {
+            self.line_to(ras, C::upscale(x), C::upscale(y));
+        }
     }
 
     /// Clip a line segment in Y and emit to the rasterizer.
@@ -194,10 +359,10 @@ impl RasterizerSlClipInt {
     fn line_clip_y(
         &self,
         ras: &mut RasterizerCellsAa,
-        x1: i32,
-        y1: i32,
-        x2: i32,
-        y2: i32,
+        x1: C::Coord,
+        y1: C::Coord,
+        x2: C::Coord,
+        y2: C::Coord,
         f1: u32,
         f2: u32,
     ) {
@@ -206,9 +371,10 @@ impl RasterizerSlClipInt {
 
         if (f1 | f2) == 0 {
             // Fully visible
-            ras.line(xi(x1), yi(y1), xi(x2), yi(y2));
+            ras.line(C::xi(x1), C::yi(y1), C::xi(x2), C::yi(y2));
         } else if f1 != f2 {
             // Partially visible — clip in Y
+            let cb = &self.clip_box;
             let mut tx1 = x1;
             let mut ty1 = y1;
             let mut tx2 = x2;
@@ -216,40 +382,59 @@ impl RasterizerSlClipInt {
 
             if f1 & 8 != 0 {
                 // y1 < clip.y1
-                tx1 = x1 + mul_div(self.clip_box.y1 - y1, x2 - x1, y2 - y1);
-                ty1 = self.clip_box.y1;
+                tx1 = x1 + C::mul_div(cb.y1 - y1, x2 - x1, y2 - y1);
+                ty1 = cb.y1;
             }
 
             if f1 & 2 != 0 {
                 // y1 > clip.y2
-                tx1 = x1 + mul_div(self.clip_box.y2 - y1, x2 - x1, y2 - y1);
-                ty1 = self.clip_box.y2;
+                tx1 = x1 + C::mul_div(cb.y2 - y1, x2 - x1, y2 - y1);
+                ty1 = cb.y2;
             }
 
             if f2 & 8 != 0 {
                 // y2 < clip.y1
-                tx2 = x1 + mul_div(self.clip_box.y1 - y1, x2 - x1, y2 - y1);
-                ty2 = self.clip_box.y1;
+                tx2 = x1 + C::mul_div(cb.y1 - y1, x2 - x1, y2 - y1);
+                ty2 = cb.y1;
             }
 
             if f2 & 2 != 0 {
                 // y2 > clip.y2
-                tx2 = x1 + mul_div(self.clip_box.y2 - y1, x2 - x1, y2 - y1);
-                ty2 = self.clip_box.y2;
+                tx2 = x1 + C::mul_div(cb.y2 - y1, x2 - x1, y2 - y1);
+                ty2 = cb.y2;
             }
 
-            ras.line(xi(tx1), yi(ty1), xi(tx2), yi(ty2));
+            ras.line(C::xi(tx1), C::yi(ty1), C::xi(tx2), C::yi(ty2));
         }
         // else: f1 == f2, both invisible by Y on same side → skip
     }
 }
 
-impl Default for RasterizerSlClipInt {
+impl<C: RasConv> Default for RasterizerSlClip<C> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl<C: RasConv> RasterizerClip for RasterizerSlClip<C> {
+    type Conv = C;
+
+    fn reset_clipping(&mut self) {
+        self.reset_clipping();
+    }
+    fn clip_box(&mut self, x1: C::Coord, y1: C::Coord, x2: C::Coord, y2: C::Coord) {
+        self.clip_box(x1, y1, x2, y2);
+    }
+    #[inline]
+    fn move_to(&mut self, x1: C::Coord, y1: C::Coord) {
+        self.move_to(x1, y1);
+    }
+    #[inline]
+    fn line_to(&mut self, ras: &mut RasterizerCellsAa, x2: C::Coord, y2: C::Coord) {
+        self.line_to(ras, x2, y2);
+    }
+}
+
 // ============================================================================
 // RasterizerSlNoClip — passthrough (no clipping)
 // ============================================================================
@@ -279,7 +464,9 @@ impl RasterizerSlNoClip {
     }
 
     pub fn move_to_d(&mut self, x: f64, y: f64) {
-        self.move_to(upscale(x), upscale(y));
+        if x.is_finite() && y.is_finite() {
+            self.move_to(upscale(x), upscale(y));
+        }
     }
 
     pub fn line_to(&mut self, ras: &mut RasterizerCellsAa, x2: i32, y2: i32) {
@@ -289,7 +476,9 @@ impl RasterizerSlNoClip {
     }
 
     pub fn line_to_d(&mut self, ras: &mut RasterizerCellsAa, x: f64, y: f64) {
-        self.line_to(ras, upscale(x), upscale(y));
+        if x.is_finite() && y.is_finite() {
+            self.line_to(ras, upscale(x), upscale(y));
+        }
     }
 }
 
@@ -299,6 +488,21 @@ impl Default for RasterizerSlNoClip {
     }
 }
 
+impl RasterizerClip for RasterizerSlNoClip {
+    type Conv = RasConvInt;
+
+    fn reset_clipping(&mut self) {}
+    fn clip_box(&mut self, _x1: i32, _y1: i32, _x2: i32, _y2: i32) {}
+    #[inline]
+    fn move_to(&mut self, x1: i32, y1: i32) {
+        self.move_to(x1, y1);
+    }
+    #[inline]
+    fn line_to(&mut self, ras: &mut RasterizerCellsAa, x2: i32, y2: i32) {
+        self.line_to(ras, x2, y2);
+    }
+}
+
 // ============================================================================
 // Public helpers
 // ============================================================================
@@ -462,6 +666,58 @@ mod tests {
         assert!(ras.total_cells() > 0);
     }
 
+    #[test]
+    fn test_clip_dbl_matches_int() {
+        let s = POLY_SUBPIXEL_SCALE as f64;
+        let mut int = RasterizerSlClipInt::new();
+        let mut dbl = RasterizerSlClipDbl::new();
+        int.clip_box(0, 0, upscale(50.0), upscale(40.0));
+        dbl.clip_box(0.0, 0.0, 50.0, 40.0);
+        let mut ras_int = RasterizerCellsAa::new();
+        let mut ras_dbl = RasterizerCellsAa::new();
+
+        int.move_to_d(-10.0, 5.0);
+        dbl.move_to_d(-10.0, 5.0);
+        for &(x, y) in &[(60.0, 20.0), (25.0, 70.0), (-10.0, 5.0)] {
+            int.line_to_d(&mut ras_int, x, y);
+            dbl.line_to_d(&mut ras_dbl, x, y);
+        }
+        ras_int.sort_cells();
+        ras_dbl.sort_cells();
+
+        assert_eq!(ras_dbl.total_cells(), ras_int.total_cells());
+        assert_eq!(ras_dbl.min_x(), 0);
+        assert_eq!(ras_dbl.max_y(), (40.0 * s) as i32 >> 8);
+    }
+
+    #[test]
+    fn test_clip_dbl_far_coordinates() {
+        // 1e8 px is far outside the 24.8 range; clipping in doubles first
+        // keeps the edge exact.
+        let mut clip = RasterizerSlClipDbl::new();
+        let mut ras = RasterizerCellsAa::new();
+        clip.clip_box(0.0, 0.0, 100.0, 100.0);
+        clip.move_to_d(-1e8, 10.0);
+        clip.line_to_d(&mut ras, 1e8, 12.0);
+        ras.sort_cells();
+
+        assert!(ras.total_cells() > 0);
+        assert_eq!((ras.min_x(), ras.max_x()), (0, 100));
+        assert_eq!((ras.min_y(), ras.max_y()), (10, 12));
+    }
+
+    #[test]
+    fn test_clip_dbl_3x_triples_x() {
+        let mut clip = RasterizerSlClipDbl3x::new();
+        let mut ras = RasterizerCellsAa::new();
+        clip.move_to_d(10.0, 0.0);
+        clip.line_to_d(&mut ras, 20.0, 10.0);
+        ras.sort_cells();
+
+        assert_eq!((ras.min_x(), ras.max_x()), (30, 60));
+        assert_eq!((ras.min_y(), ras.max_y()), (0, 10));
+    }
+
     #[test]
     fn test_poly_coord() {
         assert_eq!(poly_coord(1.0), POLY_SUBPIXEL_SCALE as i32);
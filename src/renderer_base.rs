@@ -3,7 +3,8 @@
 //! Port of `agg_renderer_base.h` — wraps a pixel format with a clip rectangle,
 //! ensuring all rendering operations are bounded within the visible area.
 
-use crate::basics::{CoverType, RectI};
+use crate::basics::{CoverType, RectI, COVER_MASK, COVER_SHIFT};
+use crate::color::Gray8;
 use crate::pixfmt_rgba::PixelFormat;
 
 // ============================================================================
@@ -389,6 +390,193 @@ impl<PF: PixelFormat> RendererBase<PF> {
             self.ren.blend_pixel(x, y + i as i32, &colors[colors_offset + i], c);
         }
     }
+
+    // ========================================================================
+    // Blitting from another pixel format
+    // ========================================================================
+
+    /// Clip a destination/source rectangle pair against the clip box and
+    /// the source extent. Both rects are half-open (`x2`/`y2` exclusive).
+    /// Returns the size of the area to process as `(width, height)`.
+    ///
+    /// Port of C++ `renderer_base::clip_rect_area`.
+    fn clip_rect_area(&self, dst: &mut RectI, src: &mut RectI, wsrc: i32, hsrc: i32) -> (i32, i32) {
+        let cb = RectI::new(
+            self.clip_box.x1,
+            self.clip_box.y1,
+            self.clip_box.x2 + 1,
+            self.clip_box.y2 + 1,
+        );
+
+        if src.x1 < 0 {
+            dst.x1 -= src.x1;
+            src.x1 = 0;
+        }
+        if src.y1 < 0 {
+            dst.y1 -= src.y1;
+            src.y1 = 0;
+        }
+        src.x2 = src.x2.min(wsrc);
+        src.y2 = src.y2.min(hsrc);
+
+        if dst.x1 < cb.x1 {
+            src.x1 += cb.x1 - dst.x1;
+            dst.x1 = cb.x1;
+        }
+        if dst.y1 < cb.y1 {
+            src.y1 += cb.y1 - dst.y1;
+            dst.y1 = cb.y1;
+        }
+        dst.x2 = dst.x2.min(cb.x2);
+        dst.y2 = dst.y2.min(cb.y2);
+
+        (
+            (dst.x2 - dst.x1).min(src.x2 - src.x1),
+            (dst.y2 - dst.y1).min(src.y2 - src.y1),
+        )
+    }
+
+    /// Resolve the source rect (inclusive, `None` = whole source) and the
+    /// destination offset into clipped start points and an area size.
+    /// Returns `(dst_x, dst_y, src_x, src_y, width, height)`.
+    fn blit_area<S: PixelFormat>(
+        &self,
+        src: &S,
+        rect_src: Option<&RectI>,
+        dx: i32,
+        dy: i32,
+    ) -> (i32, i32, i32, i32, i32, i32) {
+        let mut rsrc = match rect_src {
+            Some(r) => RectI::new(r.x1, r.y1, r.x2 + 1, r.y2 + 1),
+            None => RectI::new(0, 0, src.width() as i32, src.height() as i32),
+        };
+        let mut rdst = RectI::new(rsrc.x1 + dx, rsrc.y1 + dy, rsrc.x2 + dx, rsrc.y2 + dy);
+        let (w, h) = self.clip_rect_area(
+            &mut rdst,
+            &mut rsrc,
+            src.width() as i32,
+            src.height() as i32,
+        );
+        (rdst.x1, rdst.y1, rsrc.x1, rsrc.y1, w, h)
+    }
+
+    /// Copy pixels from `src` (clipped), replacing the destination.
+    ///
+    /// `rect_src` selects an inclusive source rectangle (`None` copies all
+    /// of `src`); it lands at its own position shifted by `(dx, dy)`.
+    ///
+    /// Port of C++ `renderer_base::copy_from`. C++ copies raw rows of a
+    /// rendering buffer; here `src` is any pixel format with the same color
+    /// type.
+    pub fn copy_from<S>(&mut self, src: &S, rect_src: Option<&RectI>, dx: i32, dy: i32)
+    where
+        S: PixelFormat<ColorType = PF::ColorType>,
+    {
+        let (xdst, ydst, xsrc, ysrc, w, h) = self.blit_area(src, rect_src, dx, dy);
+        if w <= 0 {
+            return;
+        }
+        for row in 0..h {
+            for i in 0..w {
+                let c = src.pixel(xsrc + i, ysrc + row);
+                self.ren.copy_pixel(xdst + i, ydst + row, &c);
+            }
+        }
+    }
+
+    /// Blend pixels from `src` (clipped) using their own alpha, scaled by
+    /// the global `cover`.
+    ///
+    /// `src` may be any pixel format whose color converts into this one's
+    /// (e.g. BGRA32 onto RGBA32, where the color types are identical).
+    ///
+    /// Port of C++ `renderer_base::blend_from`.
+    pub fn blend_from<S>(
+        &mut self,
+        src: &S,
+        rect_src: Option<&RectI>,
+        dx: i32,
+        dy: i32,
+        cover: CoverType,
+    ) where
+        S: PixelFormat,
+        PF::ColorType: From<S::ColorType>,
+    {
+        let (xdst, ydst, xsrc, ysrc, w, h) = self.blit_area(src, rect_src, dx, dy);
+        if w <= 0 {
+            return;
+        }
+        let mut colors = Vec::with_capacity(w as usize);
+        for row in 0..h {
+            colors.clear();
+            colors.extend((0..w).map(|i| PF::ColorType::from(src.pixel(xsrc + i, ysrc + row))));
+            self.ren
+                .blend_color_hspan(xdst, ydst + row, w as u32, &colors, &[], cover);
+        }
+    }
+
+    /// Blend a solid `color` through a Gray8 `src` used as per-pixel
+    /// coverage, scaled by the global `cover` — e.g. colorizing a glyph or
+    /// alpha mask onto RGBA.
+    ///
+    /// Port of C++ `renderer_base::blend_from_color`.
+    pub fn blend_from_color<S>(
+        &mut self,
+        src: &S,
+        color: &PF::ColorType,
+        rect_src: Option<&RectI>,
+        dx: i32,
+        dy: i32,
+        cover: CoverType,
+    ) where
+        S: PixelFormat<ColorType = Gray8>,
+    {
+        let (xdst, ydst, xsrc, ysrc, w, h) = self.blit_area(src, rect_src, dx, dy);
+        if w <= 0 {
+            return;
+        }
+        let mut covers = Vec::with_capacity(w as usize);
+        for row in 0..h {
+            covers.clear();
+            covers.extend((0..w).map(|i| {
+                let v = src.pixel(xsrc + i, ysrc + row).v as u32;
+                ((v * cover as u32 + COVER_MASK) >> COVER_SHIFT) as CoverType
+            }));
+            self.ren
+                .blend_solid_hspan(xdst, ydst + row, w as u32, color, &covers);
+        }
+    }
+
+    /// Blend colors looked up in `color_lut` by the Gray8 `src` values,
+    /// scaled by the global `cover`.
+    ///
+    /// Port of C++ `renderer_base::blend_from_lut`.
+    pub fn blend_from_lut<S>(
+        &mut self,
+        src: &S,
+        color_lut: &[PF::ColorType; 256],
+        rect_src: Option<&RectI>,
+        dx: i32,
+        dy: i32,
+        cover: CoverType,
+    ) where
+        S: PixelFormat<ColorType = Gray8>,
+        PF::ColorType: Clone,
+    {
+        let (xdst, ydst, xsrc, ysrc, w, h) = self.blit_area(src, rect_src, dx, dy);
+        if w <= 0 {
+            return;
+        }
+        let mut colors = Vec::with_capacity(w as usize);
+        for row in 0..h {
+            colors.clear();
+            colors.extend(
+                (0..w).map(|i| color_lut[src.pixel(xsrc + i, ysrc + row).v as usize].clone()),
+            );
+            self.ren
+                .blend_color_hspan(xdst, ydst + row, w as u32, &colors, &[], cover);
+        }
+    }
 }
 
 // ============================================================================
@@ -593,4 +781,94 @@ mod tests {
         let p = ren.ren().pixel(5, 0);
         assert_eq!(p.r, 0);
     }
+
+    fn make_gray(w: u32, h: u32, data: &[u8]) -> (Vec<u8>, RowAccessor) {
+        let buf = data.to_vec();
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, w, h, w as i32);
+        }
+        (buf, ra)
+    }
+
+    #[test]
+    fn test_copy_from_rect_offset_and_clip() {
+        let (_sbuf, mut sra) = make_renderer(4, 4);
+        let mut src = PixfmtRgba32::new(&mut sra);
+        for y in 0..4 {
+            for x in 0..4 {
+                src.copy_pixel(x, y, &Rgba8::new(x as u32 * 10, y as u32 * 10, 0, 255));
+            }
+        }
+
+        let (_dbuf, mut dra) = make_renderer(5, 5);
+        let mut ren = RendererBase::new(PixfmtRgba32::new(&mut dra));
+        // Source (1,1)-(3,3) lands at (3,2)-(5,4); column 5 is clipped.
+        ren.copy_from(&src, Some(&RectI::new(1, 1, 3, 3)), 2, 1);
+        let p = ren.ren().pixel(3, 2);
+        assert_eq!((p.r, p.g, p.a), (10, 10, 255));
+        let p = ren.ren().pixel(4, 4);
+        assert_eq!((p.r, p.g, p.a), (20, 30, 255));
+        assert_eq!(ren.ren().pixel(2, 2).a, 0);
+        assert_eq!(ren.ren().pixel(3, 1).a, 0);
+
+        // Negative offset pulls the source up and left, clipped at 0.
+        ren.copy_from(&src, None, -3, -3);
+        let p = ren.ren().pixel(0, 0);
+        assert_eq!((p.r, p.g), (30, 30));
+    }
+
+    #[test]
+    fn test_blend_from_across_channel_orders() {
+        use crate::pixfmt_rgba::PixfmtBgra32;
+
+        let (sbuf, mut sra) = make_renderer(2, 1);
+        let mut src = PixfmtBgra32::new(&mut sra);
+        src.copy_pixel(0, 0, &Rgba8::new(255, 0, 0, 255));
+        src.copy_pixel(1, 0, &Rgba8::new(0, 0, 255, 0));
+        assert_eq!(&sbuf[..4], &[0, 0, 255, 255]);
+
+        let (_dbuf, mut dra) = make_renderer(2, 1);
+        let mut ren = RendererBase::new(PixfmtRgba32::new(&mut dra));
+        ren.clear(&Rgba8::new(0, 0, 0, 255));
+        ren.blend_from(&src, None, 0, 0, 128);
+        let p = ren.ren().pixel(0, 0);
+        assert!((p.r as i32 - 128).abs() <= 1, "r={}", p.r);
+        // Fully transparent source pixel leaves the destination alone.
+        assert_eq!(ren.ren().pixel(1, 0), Rgba8::new(0, 0, 0, 255));
+    }
+
+    #[test]
+    fn test_blend_from_color_gray_mask() {
+        use crate::pixfmt_gray::PixfmtGray8;
+
+        let (_gbuf, mut gra) = make_gray(3, 1, &[0, 128, 255]);
+        let mask = PixfmtGray8::new(&mut gra);
+        let (_dbuf, mut dra) = make_renderer(4, 1);
+        let mut ren = RendererBase::new(PixfmtRgba32::new(&mut dra));
+        ren.clear(&Rgba8::new(255, 255, 255, 255));
+        ren.blend_from_color(&mask, &Rgba8::new(0, 0, 255, 255), None, 1, 0, 255);
+        assert_eq!(ren.ren().pixel(0, 0), Rgba8::new(255, 255, 255, 255));
+        assert_eq!(ren.ren().pixel(1, 0), Rgba8::new(255, 255, 255, 255));
+        let mid = ren.ren().pixel(2, 0);
+        assert!((mid.r as i32 - 127).abs() <= 1, "r={}", mid.r);
+        assert_eq!(ren.ren().pixel(3, 0), Rgba8::new(0, 0, 255, 255));
+    }
+
+    #[test]
+    fn test_blend_from_lut() {
+        use crate::pixfmt_gray::PixfmtGray8;
+
+        let (_gbuf, mut gra) = make_gray(2, 1, &[0, 255]);
+        let src = PixfmtGray8::new(&mut gra);
+        let mut lut = [Rgba8::default(); 256];
+        for (i, c) in lut.iter_mut().enumerate() {
+            *c = Rgba8::new(i as u32, 0, 255 - i as u32, 255);
+        }
+        let (_dbuf, mut dra) = make_renderer(2, 1);
+        let mut ren = RendererBase::new(PixfmtRgba32::new(&mut dra));
+        ren.blend_from_lut(&src, &lut, None, 0, 0, 255);
+        assert_eq!(ren.ren().pixel(0, 0), Rgba8::new(0, 0, 255, 255));
+        assert_eq!(ren.ren().pixel(1, 0), Rgba8::new(255, 0, 0, 255));
+    }
 }
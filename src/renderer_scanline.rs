@@ -7,9 +7,10 @@
 //! filled polygons with anti-aliased edges in a single solid color.
 
 use crate::pixfmt_rgba::PixelFormat;
-use crate::rasterizer_scanline_aa::{RasterizerScanlineAa, Scanline};
+use crate::rasterizer_scanline_aa::RasterizerScanlineAa;
+use crate::rasterizer_sl_clip::RasterizerClip;
 use crate::renderer_base::RendererBase;
-use crate::scanline_u::ScanlineU8;
+use crate::scanline_u::ScanlineSpans;
 use crate::span_allocator::SpanAllocator;
 
 // ============================================================================
@@ -23,11 +24,11 @@ use crate::span_allocator::SpanAllocator;
 ///
 /// Port of C++ `render_scanlines_aa_solid()`.
 ///
-/// Works with `ScanlineU8` (unpacked per-pixel coverage). Each span has
-/// positive `len` and references into the covers array.
-pub fn render_scanlines_aa_solid<PF: PixelFormat>(
-    ras: &mut RasterizerScanlineAa,
-    sl: &mut ScanlineU8,
+/// Works with any `ScanlineSpans` container: `ScanlineU8`, `ScanlineP8` and
+/// their alpha-masked variants.
+pub fn render_scanlines_aa_solid<PF: PixelFormat, SL: ScanlineSpans, CL: RasterizerClip>(
+    ras: &mut RasterizerScanlineAa<CL>,
+    sl: &mut SL,
     ren: &mut RendererBase<PF>,
     color: &PF::ColorType,
 ) {
@@ -37,16 +38,16 @@ pub fn render_scanlines_aa_solid<PF: PixelFormat>(
 
     sl.reset(ras.min_x(), ras.max_x());
     while ras.sweep_scanline(sl) {
-        render_scanline_aa_solid_u8(sl, ren, color);
+        render_scanline_aa_solid(sl, ren, color);
     }
 }
 
-/// Render a single scanline from `ScanlineU8` to the renderer.
+/// Render a single scanline to the renderer.
 ///
-/// Port of C++ `render_scanline_aa_solid()` specialized for ScanlineU8
-/// where all spans have positive len (per-pixel covers).
-fn render_scanline_aa_solid_u8<PF: PixelFormat>(
-    sl: &ScanlineU8,
+/// Port of C++ `render_scanline_aa_solid()`. Spans with positive `len` carry
+/// per-pixel covers; negative `len` is a solid span (ScanlineP8).
+fn render_scanline_aa_solid<PF: PixelFormat, SL: ScanlineSpans>(
+    sl: &SL,
     ren: &mut RendererBase<PF>,
     color: &PF::ColorType,
 ) {
@@ -65,9 +66,9 @@ fn render_scanline_aa_solid_u8<PF: PixelFormat>(
                 color,
                 &covers[span.cover_offset..span.cover_offset + len as usize],
             );
+        } else {
+            ren.blend_hline(x, y, x - len - 1, color, covers[span.cover_offset]);
         }
-        // ScanlineU8 always has positive len, but handle negative for safety
-        // (negative len would mean solid span like ScanlineP8 uses)
     }
 }
 
@@ -100,7 +101,11 @@ where
     }
 
     /// Render all scanlines from the rasterizer.
-    pub fn render(&mut self, ras: &mut RasterizerScanlineAa, sl: &mut ScanlineU8) {
+    pub fn render<SL: ScanlineSpans, CL: RasterizerClip>(
+        &mut self,
+        ras: &mut RasterizerScanlineAa<CL>,
+        sl: &mut SL,
+    ) {
         render_scanlines_aa_solid(ras, sl, self.ren, &self.color);
     }
 }
@@ -139,16 +144,18 @@ pub trait SpanGenerator {
 /// generator produces an array of colors which are blended into the output.
 ///
 /// Port of C++ `render_scanlines_aa()` (span generator variant).
-pub fn render_scanlines_aa<PF, SG>(
-    ras: &mut RasterizerScanlineAa,
-    sl: &mut ScanlineU8,
+pub fn render_scanlines_aa<PF, SL, SG, CL>(
+    ras: &mut RasterizerScanlineAa<CL>,
+    sl: &mut SL,
     ren: &mut RendererBase<PF>,
     alloc: &mut SpanAllocator<SG::Color>,
     span_gen: &mut SG,
 ) where
     PF: PixelFormat<ColorType = SG::Color>,
+    SL: ScanlineSpans,
     SG: SpanGenerator,
     SG::Color: Default + Clone,
+    CL: RasterizerClip,
 {
     if !ras.rewind_scanlines() {
         return;
@@ -164,13 +171,14 @@ pub fn render_scanlines_aa<PF, SG>(
 /// Render a single scanline using a span generator.
 ///
 /// Port of C++ `render_scanline_aa()` (span generator variant).
-fn render_scanline_aa<PF, SG>(
-    sl: &ScanlineU8,
+fn render_scanline_aa<PF, SL, SG>(
+    sl: &SL,
     ren: &mut RendererBase<PF>,
     alloc: &mut SpanAllocator<SG::Color>,
     span_gen: &mut SG,
 ) where
     PF: PixelFormat<ColorType = SG::Color>,
+    SL: ScanlineSpans,
     SG: SpanGenerator,
     SG::Color: Default + Clone,
 {
@@ -211,6 +219,7 @@ mod tests {
     use crate::path_storage::PathStorage;
     use crate::pixfmt_rgba::{PixelFormat, PixfmtRgba32};
     use crate::rendering_buffer::RowAccessor;
+    use crate::scanline_u::ScanlineU8;
 
     const BPP: usize = 4;
 
@@ -600,4 +609,71 @@ mod tests {
         let right = ren.ren().pixel(95, 25);
         assert!(right.b > 200, "Right b={} should be blue", right.b);
     }
+
+    // ========================================================================
+    // Packed and alpha-masked scanlines
+    // ========================================================================
+
+    fn render_ellipse_with<SL: ScanlineSpans>(sl: &mut SL) -> Vec<u8> {
+        let (buf, mut ra) = make_rgba_buffer(60, 60);
+        let pf = PixfmtRgba32::new(&mut ra);
+        let mut ren = RendererBase::new(pf);
+        let mut ras = RasterizerScanlineAa::new();
+        let mut ell = Ellipse::new(30.0, 30.0, 25.0, 18.0, 64, false);
+        ras.add_path(&mut ell, 0);
+        render_scanlines_aa_solid(&mut ras, sl, &mut ren, &Rgba8::new(0, 0, 200, 200));
+        buf
+    }
+
+    #[test]
+    fn test_scanline_p8_matches_u8() {
+        use crate::scanline_p::ScanlineP8;
+
+        let u8_buf = render_ellipse_with(&mut ScanlineU8::new());
+        let p8_buf = render_ellipse_with(&mut ScanlineP8::new());
+        assert_eq!(u8_buf, p8_buf);
+    }
+
+    fn render_rect_solid_span<SL: ScanlineSpans>(sl: &mut SL, w: u32, h: u32) -> Vec<u8> {
+        use crate::span_solid::SpanSolid;
+
+        let (buf, mut ra) = make_rgba_buffer(w, h);
+        let mut ren = RendererBase::new(PixfmtRgba32::new(&mut ra));
+        let mut ras = RasterizerScanlineAa::new();
+        ras.move_to_d(2.0, 2.0);
+        ras.line_to_d(w as f64 - 2.0, 2.0);
+        ras.line_to_d(w as f64 - 2.0, h as f64 - 2.0);
+        ras.line_to_d(2.0, h as f64 - 2.0);
+        let mut sg = SpanSolid::new();
+        sg.set_color(Rgba8::new(255, 0, 0, 255));
+        let mut alloc = SpanAllocator::new();
+        render_scanlines_aa(&mut ras, sl, &mut ren, &mut alloc, &mut sg);
+        buf
+    }
+
+    #[test]
+    fn test_masked_scanlines_with_span_generator() {
+        use crate::alpha_mask_u8::{AlphaMaskGray8, OneComponentMask};
+        use crate::scanline_p::ScanlineP8Am;
+        use crate::scanline_u::ScanlineU8Am;
+
+        // Mask: left half fully open, right half closed.
+        let (w, h) = (40u32, 20u32);
+        let mask_buf: Vec<u8> = (0..w * h)
+            .map(|i| if i % w < w / 2 { 255 } else { 0 })
+            .collect();
+        let mut mask_ra = RowAccessor::new();
+        unsafe {
+            mask_ra.attach(mask_buf.as_ptr() as *mut u8, w, h, w as i32);
+        }
+        let mask = AlphaMaskGray8::new(&mask_ra, OneComponentMask);
+
+        let u8_buf = render_rect_solid_span(&mut ScanlineU8Am::new(&mask), w, h);
+        let p8_buf = render_rect_solid_span(&mut ScanlineP8Am::new(&mask), w, h);
+        assert_eq!(u8_buf, p8_buf);
+
+        let px = |x: u32, y: u32| &u8_buf[((y * w + x) * 4) as usize..][..4];
+        assert_eq!(px(10, 10), &[255, 0, 0, 255]);
+        assert_eq!(px(30, 10), &[255, 255, 255, 255]);
+    }
 }
@@ -0,0 +1,405 @@
+//! Tiled and banded rendering drivers.
+//!
+//! Not part of AGG. [`TiledRenderer`] renders an image far larger than a
+//! single buffer could hold by running the scene once per tile into a
+//! reused tile-sized buffer and handing each finished tile to a sink.
+//! [`BandRenderer`] renders into one image in horizontal bands, which the
+//! `parallel` feature spreads across threads.
+//!
+//! Per tile or band, the scene gets a [`TransAffine`] translation that moves the
+//! tile's origin to `(0, 0)` and a rasterizer clipped to the whole output
+//! but windowed to the tile (see [`RasterizerScanlineAa::window`]). Edges
+//! are never split at tile borders, so every pixel gets exactly the
+//! coverage it would get in one big render and the tiles join seamlessly.
+//!
+//! ```
+//! use agg_rust::color::Rgba8;
+//! use agg_rust::conv_transform::ConvTransform;
+//! use agg_rust::ellipse::Ellipse;
+//! use agg_rust::renderer_scanline::render_scanlines_aa_solid;
+//! use agg_rust::renderer_tiled::TiledRenderer;
+//! use agg_rust::scanline_u::ScanlineU8;
+//!
+//! let mut sl = ScanlineU8::new();
+//! let mut tiles = 0;
+//! TiledRenderer::new(1000, 600, 256, 256).render(
+//!     |ctx| {
+//!         let mut circle = ConvTransform::new(Ellipse::new(500.0, 300.0, 200.0, 200.0, 100, false), ctx.mtx);
+//!         ctx.ras.add_path(&mut circle, 0);
+//!         render_scanlines_aa_solid(ctx.ras, &mut sl, &mut ctx.ren, &Rgba8::new(0, 0, 0, 255));
+//!     },
+//!     |_tile| tiles += 1,
+//! );
+//! assert_eq!(tiles, 4 * 3);
+//! ```
+
+use crate::basics::RectI;
+use crate::image_buffer::{ImageBuffer, ImageView, PixelFormatTag};
+use crate::pixfmt_rgba::PixfmtRgba32;
+use crate::rasterizer_scanline_aa::RasterizerScanlineAa;
+use crate::renderer_base::RendererBase;
+use crate::trans_affine::TransAffine;
+
+/// What the scene callback renders one tile (or band) with.
+pub struct TileContext<'a> {
+    /// Renderer over the tile. `TiledRenderer` tiles start out transparent
+    /// black; `BandRenderer` bands hold the image's existing pixels.
+    pub ren: RendererBase<PixfmtRgba32<'a, ImageView<'a>>>,
+    /// Rasterizer reset, clipped to the output and windowed to the tile.
+    pub ras: &'a mut RasterizerScanlineAa,
+    /// Output-to-tile translation. Apply it last, as its own
+    /// `ConvTransform` stage: folded into a rotating or scaling matrix it
+    /// rounds differently and tiles may no longer match at their borders.
+    pub mtx: TransAffine,
+    /// The tile's inclusive rectangle in output coordinates.
+    pub rect: RectI,
+}
+
+/// A finished tile, handed to the sink.
+pub struct Tile<'a> {
+    /// Left edge of the tile in output pixels.
+    pub x: u32,
+    /// Top edge of the tile in output pixels.
+    pub y: u32,
+    /// The tile's pixels (RGBA32); edge tiles may be smaller than the
+    /// nominal tile size.
+    pub image: &'a ImageBuffer,
+}
+
+/// Renders a `width` x `height` RGBA32 image tile by tile.
+pub struct TiledRenderer {
+    width: u32,
+    height: u32,
+    tile_width: u32,
+    tile_height: u32,
+}
+
+impl TiledRenderer {
+    /// # Panics
+    /// If a tile dimension is zero.
+    pub fn new(width: u32, height: u32, tile_width: u32, tile_height: u32) -> Self {
+        assert!(
+            tile_width > 0 && tile_height > 0,
+            "tile size must be non-zero"
+        );
+        Self {
+            width,
+            height,
+            tile_width,
+            tile_height,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn tile_width(&self) -> u32 {
+        self.tile_width
+    }
+
+    pub fn tile_height(&self) -> u32 {
+        self.tile_height
+    }
+
+    /// Number of tile columns and rows.
+    pub fn tile_count(&self) -> (u32, u32) {
+        let count = |len: u32, tile: u32| len / tile + u32::from(len % tile != 0);
+        (
+            count(self.width, self.tile_width),
+            count(self.height, self.tile_height),
+        )
+    }
+
+    /// Render every tile in row-major order: call `scene` to draw it, then
+    /// pass the result to `sink`.
+    ///
+    /// The rasterizer is shared between tiles, so settings such as gamma
+    /// and filling rule carry over; its clip box is replaced per tile.
+    pub fn render<F, S>(&self, mut scene: F, mut sink: S)
+    where
+        F: FnMut(&mut TileContext<'_>),
+        S: FnMut(&Tile<'_>),
+    {
+        let mut ras = RasterizerScanlineAa::new();
+        let mut image = ImageBuffer::new(0, 0, PixelFormatTag::Rgba32);
+        let (cols, rows) = self.tile_count();
+        for row in 0..rows {
+            let y = row * self.tile_height;
+            let h = self.tile_height.min(self.height - y);
+            for col in 0..cols {
+                let x = col * self.tile_width;
+                let w = self.tile_width.min(self.width - x);
+                if image.width() != w || image.height() != h {
+                    image = ImageBuffer::new(w, h, PixelFormatTag::Rgba32);
+                } else {
+                    image.data_mut().fill(0);
+                }
+
+                render_tile(
+                    &mut ras,
+                    image.view(),
+                    (x, y),
+                    (self.width, self.height),
+                    &mut scene,
+                );
+                sink(&Tile {
+                    x,
+                    y,
+                    image: &image,
+                });
+            }
+        }
+    }
+}
+
+/// Renders into an RGBA32 [`ImageBuffer`] in horizontal bands of
+/// `band_height` rows, each with its own rasterizer and scene call.
+///
+/// Bands are full-width tiles, so the result is identical to rendering the
+/// scene once over the whole image. With the `parallel` feature,
+/// `render_parallel` renders the bands on the rayon thread pool.
+pub struct BandRenderer {
+    band_height: u32,
+}
+
+impl BandRenderer {
+    /// # Panics
+    /// If `band_height` is zero.
+    pub fn new(band_height: u32) -> Self {
+        assert!(band_height > 0, "band height must be non-zero");
+        Self { band_height }
+    }
+
+    pub fn band_height(&self) -> u32 {
+        self.band_height
+    }
+
+    /// Render the bands one after another, top to bottom.
+    pub fn render<F>(&self, image: &mut ImageBuffer, mut scene: F)
+    where
+        F: FnMut(&mut TileContext<'_>),
+    {
+        let mut ras = RasterizerScanlineAa::new();
+        let (width, height) = (image.width(), image.height());
+        for (i, rows) in self.bands(image).enumerate() {
+            let y = i as u32 * self.band_height;
+            self.render_band(&mut ras, rows, y, (width, height), &mut scene);
+        }
+    }
+
+    /// Render the bands in parallel into their disjoint row ranges of
+    /// `image`. `scene` is called concurrently, once per band.
+    #[cfg(feature = "parallel")]
+    pub fn render_parallel<F>(&self, image: &mut ImageBuffer, scene: F)
+    where
+        F: Fn(&mut TileContext<'_>) + Sync,
+    {
+        use rayon::prelude::*;
+
+        let (width, height) = (image.width(), image.height());
+        let rows: Vec<&mut [u8]> = self.bands(image).collect();
+        rows.into_par_iter().enumerate().for_each(|(i, rows)| {
+            let y = i as u32 * self.band_height;
+            let mut ras = RasterizerScanlineAa::new();
+            self.render_band(&mut ras, rows, y, (width, height), &mut |ctx| scene(ctx));
+        });
+    }
+
+    /// Split `image` into the byte ranges of its bands.
+    fn bands<'a>(&self, image: &'a mut ImageBuffer) -> impl Iterator<Item = &'a mut [u8]> {
+        assert_eq!(
+            image.format(),
+            PixelFormatTag::Rgba32,
+            "band rendering needs an RGBA32 image"
+        );
+        let band_bytes = (self.band_height as usize * image.stride() as usize).max(1);
+        image.data_mut().chunks_mut(band_bytes)
+    }
+
+    fn render_band<F>(
+        &self,
+        ras: &mut RasterizerScanlineAa,
+        rows: &mut [u8],
+        y: u32,
+        size: (u32, u32),
+        scene: &mut F,
+    ) where
+        F: FnMut(&mut TileContext<'_>),
+    {
+        let (width, height) = size;
+        let h = self.band_height.min(height - y);
+        let stride = (width * 4) as i32;
+        let view = ImageView::from_slice(rows, width, h, stride, PixelFormatTag::Rgba32);
+        render_tile(ras, view, (0, y), size, scene);
+    }
+}
+
+/// Run `scene` for the tile of `view`'s size at `origin` of a `size` output.
+fn render_tile<F>(
+    ras: &mut RasterizerScanlineAa,
+    view: ImageView<'_>,
+    origin: (u32, u32),
+    size: (u32, u32),
+    scene: &mut F,
+) where
+    F: FnMut(&mut TileContext<'_>),
+{
+    let (x, y) = origin;
+    let (w, h) = (view.width(), view.height());
+    // Rebind so the view's lifetime can shrink to the context's.
+    let mut view = view;
+
+    // Clip to the whole output, as a single render would, and restrict the
+    // cells to the tile.
+    ras.clip_box(
+        -(x as f64),
+        -(y as f64),
+        (size.0 - x) as f64,
+        (size.1 - y) as f64,
+    );
+    ras.window(0, 0, w as i32 - 1, h as i32 - 1);
+    let mut ctx = TileContext {
+        ren: RendererBase::new(PixfmtRgba32::new(&mut view)),
+        ras,
+        mtx: TransAffine::new_translation(-(x as f64), -(y as f64)),
+        rect: RectI::new(x as i32, y as i32, (x + w) as i32 - 1, (y + h) as i32 - 1),
+    };
+    scene(&mut ctx);
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgba8;
+    use crate::conv_transform::ConvTransform;
+    use crate::ellipse::Ellipse;
+    use crate::path_storage::PathStorage;
+    use crate::renderer_scanline::render_scanlines_aa_solid;
+    use crate::scanline_u::ScanlineU8;
+
+    const W: u32 = 150;
+    const H: u32 = 110;
+
+    /// Draw a few overlapping anti-aliased shapes, including edges that run
+    /// across tile borders and off the output.
+    fn scene(
+        ras: &mut RasterizerScanlineAa,
+        ren: &mut RendererBase<PixfmtRgba32<ImageView>>,
+        mtx: TransAffine,
+    ) {
+        let mut sl = ScanlineU8::new();
+        let rotation = TransAffine::new_rotation(0.1);
+        let ellipse = Ellipse::new(70.3, 50.7, 45.2, 38.9, 64, false);
+        let mut circle = ConvTransform::new(ConvTransform::new(ellipse, rotation), mtx);
+        ras.add_path(&mut circle, 0);
+        render_scanlines_aa_solid(ras, &mut sl, ren, &Rgba8::new(200, 40, 40, 180));
+
+        let mut tri = PathStorage::new();
+        tri.move_to(5.25, 100.5);
+        tri.line_to(171.0, -8.3);
+        tri.line_to(120.6, 108.0);
+        let mut tri = ConvTransform::new(tri, mtx);
+        ras.add_path(&mut tri, 0);
+        render_scanlines_aa_solid(ras, &mut sl, ren, &Rgba8::new(20, 90, 220, 140));
+    }
+
+    fn render_whole() -> ImageBuffer {
+        let mut img = ImageBuffer::new(W, H, PixelFormatTag::Rgba32);
+        {
+            let mut view = img.view();
+            let mut ren = RendererBase::new(PixfmtRgba32::new(&mut view));
+            let mut ras = RasterizerScanlineAa::new();
+            ras.clip_box(0.0, 0.0, W as f64, H as f64);
+            scene(&mut ras, &mut ren, TransAffine::new());
+        }
+        img
+    }
+
+    fn render_tiled(tw: u32, th: u32) -> (ImageBuffer, u32) {
+        let mut out = ImageBuffer::new(W, H, PixelFormatTag::Rgba32);
+        let mut tiles = 0;
+        TiledRenderer::new(W, H, tw, th).render(
+            |ctx| scene(ctx.ras, &mut ctx.ren, ctx.mtx),
+            |tile| {
+                tiles += 1;
+                let img = tile.image;
+                let row_len = (img.width() * 4) as usize;
+                for ty in 0..img.height() {
+                    let src = &img.data()[(ty * img.width() * 4) as usize..][..row_len];
+                    let off = (((tile.y + ty) * W + tile.x) * 4) as usize;
+                    out.data_mut()[off..off + row_len].copy_from_slice(src);
+                }
+            },
+        );
+        (out, tiles)
+    }
+
+    #[test]
+    fn test_tiles_are_seamless() {
+        let whole = render_whole();
+        for (tw, th) in [(32, 32), (37, 23), (1, 110), (150, 1), (500, 500)] {
+            let (tiled, _) = render_tiled(tw, th);
+            assert!(
+                tiled.data() == whole.data(),
+                "{tw}x{th} tiles differ from a single render"
+            );
+        }
+    }
+
+    #[test]
+    fn test_edge_tiles_are_clipped() {
+        let r = TiledRenderer::new(W, H, 64, 50);
+        assert_eq!(r.tile_count(), (3, 3));
+        let mut sizes = Vec::new();
+        r.render(
+            |ctx| {
+                assert_eq!(ctx.rect.x2 - ctx.rect.x1 + 1, ctx.ren.width() as i32);
+                ctx.ren.clear(&Rgba8::new(1, 2, 3, 4));
+            },
+            |tile| {
+                assert!(tile.image.data().chunks(4).all(|p| p == [1, 2, 3, 4]));
+                sizes.push((tile.x, tile.y, tile.image.width(), tile.image.height()));
+            },
+        );
+        assert_eq!(sizes.len(), 9);
+        assert_eq!(sizes[2], (128, 0, 22, 50));
+        assert_eq!(sizes[8], (128, 100, 22, 10));
+    }
+
+    #[test]
+    fn test_bands_match_single_render() {
+        let whole = render_whole();
+        for bh in [1, 16, 37, 110, 200] {
+            let mut img = ImageBuffer::new(W, H, PixelFormatTag::Rgba32);
+            BandRenderer::new(bh).render(&mut img, |ctx| scene(ctx.ras, &mut ctx.ren, ctx.mtx));
+            assert!(
+                img.data() == whole.data(),
+                "{bh}-row bands differ from a single render"
+            );
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_bands_match_serial() {
+        let whole = render_whole();
+        for bh in [1, 16, 37, 200] {
+            let mut img = ImageBuffer::new(W, H, PixelFormatTag::Rgba32);
+            BandRenderer::new(bh)
+                .render_parallel(&mut img, |ctx| scene(ctx.ras, &mut ctx.ren, ctx.mtx));
+            assert!(
+                img.data() == whole.data(),
+                "{bh}-row parallel bands differ from a single render"
+            );
+        }
+    }
+}
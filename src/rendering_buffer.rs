@@ -32,6 +32,7 @@ pub struct RowAccessor {
     width: u32,
     height: u32,
     stride: i32,
+    row_len: u32,
 }
 
 impl RowAccessor {
@@ -43,6 +44,7 @@ impl RowAccessor {
             width: 0,
             height: 0,
             stride: 0,
+            row_len: 0,
         }
     }
 
@@ -67,11 +69,30 @@ impl RowAccessor {
         self.width = width;
         self.height = height;
         self.stride = stride;
+        self.row_len = stride.unsigned_abs();
         if stride < 0 {
             self.start = buf.offset(-((height as i64 - 1) * stride as i64) as isize);
         }
     }
 
+    /// Attach to a buffer whose rows hold only `row_len` valid bytes, fewer
+    /// than the stride — a sub-rectangle of a larger image.
+    ///
+    /// # Safety
+    /// `buf` must point to `height` rows spaced `stride` bytes apart (as in
+    /// `attach`), each with at least `row_len` valid bytes.
+    pub(crate) unsafe fn attach_rows(
+        &mut self,
+        buf: *mut u8,
+        width: u32,
+        height: u32,
+        stride: i32,
+        row_len: u32,
+    ) {
+        self.attach(buf, width, height, stride);
+        self.row_len = row_len;
+    }
+
     /// Raw buffer pointer.
     pub fn buf(&self) -> *mut u8 {
         self.buf
@@ -104,7 +125,8 @@ impl RowAccessor {
 
     /// Get a safe immutable slice for row `y`.
     ///
-    /// Returns the full row of `stride_abs()` bytes.
+    /// Returns the full row of `stride_abs()` bytes (only the sub-rectangle's
+    /// bytes when attached to part of a larger image).
     pub fn row_slice(&self, y: u32) -> &[u8] {
         assert!(
             y < self.height,
@@ -114,7 +136,7 @@ impl RowAccessor {
         );
         unsafe {
             let ptr = self.row_ptr(y as i32);
-            std::slice::from_raw_parts(ptr, self.stride_abs() as usize)
+            std::slice::from_raw_parts(ptr, self.row_len as usize)
         }
     }
 
@@ -128,7 +150,7 @@ impl RowAccessor {
         );
         unsafe {
             let ptr = self.row_ptr(y as i32);
-            std::slice::from_raw_parts_mut(ptr, self.stride_abs() as usize)
+            std::slice::from_raw_parts_mut(ptr, self.row_len as usize)
         }
     }
 
@@ -144,7 +166,7 @@ impl RowAccessor {
     /// Copy pixel data from another buffer (min of both dimensions).
     pub fn copy_from<T: RenderingBufferAccess>(&mut self, src: &T) {
         let h = self.height.min(src.height());
-        let l = self.stride_abs().min(src.stride_abs()) as usize;
+        let l = self.row_len.min(src.row_len()) as usize;
         for y in 0..h {
             unsafe {
                 let dst = self.row_ptr(y as i32);
@@ -156,12 +178,8 @@ impl RowAccessor {
 
     /// Fill every byte in the buffer with `value`.
     pub fn clear(&mut self, value: u8) {
-        let stride = self.stride_abs() as usize;
         for y in 0..self.height {
-            let row = self.row_slice_mut(y);
-            for byte in row[..stride].iter_mut() {
-                *byte = value;
-            }
+            self.row_slice_mut(y).fill(value);
         }
     }
 }
@@ -342,6 +360,10 @@ pub trait RenderingBufferAccess {
     fn width(&self) -> u32;
     fn height(&self) -> u32;
     fn stride_abs(&self) -> u32;
+    /// Valid bytes per row; less than `stride_abs()` for sub-rectangle views.
+    fn row_len(&self) -> u32 {
+        self.stride_abs()
+    }
     /// # Safety
     /// `y` must be in `[0, height)`.
     unsafe fn row_ptr_const(&self, y: i32) -> *const u8;
@@ -357,6 +379,9 @@ impl RenderingBufferAccess for RowAccessor {
     fn stride_abs(&self) -> u32 {
         self.stride_abs()
     }
+    fn row_len(&self) -> u32 {
+        self.row_len
+    }
     unsafe fn row_ptr_const(&self, y: i32) -> *const u8 {
         self.row_ptr(y) as *const u8
     }
@@ -377,6 +402,40 @@ impl RenderingBufferAccess for RowPtrCache {
     }
 }
 
+/// Writable row access for pixel formats — the `RenBuf` template parameter
+/// of the C++ pixel formats.
+///
+/// Writes announce the pixel range they touch, so a buffer may allocate rows
+/// lazily (see `RenderingBufferDynarow`). Reads go through
+/// [`RenderingBufferAccess::row_ptr_const`].
+pub trait RenderingBufferMut: RenderingBufferAccess {
+    /// Get a pointer to row `y` for writing pixels `x..x + len`.
+    ///
+    /// # Safety
+    /// `y` must be in `[0, height)` and `x..x + len` in `[0, width)`.
+    unsafe fn row_ptr_mut(&mut self, x: i32, y: i32, len: u32) -> *mut u8;
+}
+
+/// Panic unless every row of `rbuf` holds `width` pixels of `bpp` bytes.
+///
+/// Pixel formats slice `width * bpp` bytes out of each row, so they check
+/// this when attached to a buffer.
+#[inline]
+pub(crate) fn assert_row_len<RB: RenderingBufferAccess + ?Sized>(rbuf: &RB, bpp: usize) {
+    let (row_len, width) = (rbuf.row_len() as usize, rbuf.width() as usize);
+    assert!(
+        row_len >= width * bpp,
+        "rows of {row_len} bytes cannot hold {width} pixels of {bpp} bytes"
+    );
+}
+
+impl RenderingBufferMut for RowAccessor {
+    #[inline]
+    unsafe fn row_ptr_mut(&mut self, _x: i32, y: i32, _len: u32) -> *mut u8 {
+        self.row_ptr(y)
+    }
+}
+
 /// Default rendering buffer type (matches C++ `typedef row_accessor<int8u> rendering_buffer`).
 pub type RenderingBuffer = RowAccessor;
 
@@ -0,0 +1,276 @@
+//! Dynamic-row rendering buffer — rows are allocated on first write.
+//!
+//! Port of `agg_rendering_buffer_dynarow.h`. Memory grows with the number of
+//! rows actually drawn on rather than with the canvas height, which suits
+//! huge, mostly empty canvases. Each row records the pixel range written to
+//! it, so the touched rows can be exported afterwards. Unwritten rows read
+//! as zeros (transparent black), so there is no need to clear the buffer —
+//! clearing through a renderer writes, and thus allocates, every row.
+//!
+//! The RGBA32 pixel formats render into it directly through
+//! [`RenderingBufferMut`]:
+//!
+//! ```
+//! use agg_rust::color::Rgba8;
+//! use agg_rust::pixfmt_rgba::{PixelFormat, PixfmtRgba32};
+//! use agg_rust::rendering_buffer_dynarow::RenderingBufferDynarow;
+//!
+//! let mut rbuf = RenderingBufferDynarow::new(50_000, 50_000, 50_000 * 4);
+//! let mut pf = PixfmtRgba32::new(&mut rbuf);
+//! pf.copy_hline(100, 30_000, 10, &Rgba8::new(255, 0, 0, 255));
+//!
+//! let touched: Vec<_> = rbuf.touched_rows().map(|(y, row)| (y, row.x1, row.x2)).collect();
+//! assert_eq!(touched, [(30_000, 100, 109)]);
+//! ```
+
+use crate::rendering_buffer::{RenderingBufferAccess, RenderingBufferMut, RowData};
+
+/// One row of a [`RenderingBufferDynarow`]: its storage, once allocated,
+/// and the inclusive range of pixels written so far.
+struct DynRow {
+    ptr: Option<Box<[u8]>>,
+    x1: i32,
+    x2: i32,
+}
+
+impl DynRow {
+    const EMPTY: DynRow = DynRow {
+        ptr: None,
+        x1: 0,
+        x2: 0,
+    };
+}
+
+/// Rendering buffer whose rows are allocated (zero-filled) on first write.
+///
+/// Port of C++ `agg::rendering_buffer_dynarow`. Reads of a row that was
+/// never written see zeros.
+pub struct RenderingBufferDynarow {
+    rows: Vec<DynRow>,
+    width: u32,
+    height: u32,
+    byte_width: u32,
+    // Shared row returned for reads of unallocated rows (C++ returns null).
+    zero_row: Box<[u8]>,
+}
+
+impl RenderingBufferDynarow {
+    /// Create a buffer of `height` rows of `width` pixels, `byte_width`
+    /// bytes each. No row memory is allocated until a row is written.
+    ///
+    /// Pixel formats attached to the buffer panic unless `byte_width` holds
+    /// `width` of their pixels.
+    pub fn new(width: u32, height: u32, byte_width: u32) -> Self {
+        let mut rbuf = Self {
+            rows: Vec::new(),
+            width: 0,
+            height: 0,
+            byte_width: 0,
+            zero_row: Box::default(),
+        };
+        rbuf.init(width, height, byte_width);
+        rbuf
+    }
+
+    /// Resize the buffer, freeing every allocated row.
+    pub fn init(&mut self, width: u32, height: u32, byte_width: u32) {
+        self.rows.clear();
+        self.rows.resize_with(height as usize, || DynRow::EMPTY);
+        self.width = width;
+        self.height = height;
+        if byte_width != self.byte_width {
+            self.zero_row = vec![0; byte_width as usize].into_boxed_slice();
+        }
+        self.byte_width = byte_width;
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn byte_width(&self) -> u32 {
+        self.byte_width
+    }
+
+    /// Get row `y` for writing pixels `x..x + len`, allocating it if needed
+    /// and widening its touched range. Returns the whole row.
+    pub fn row_mut(&mut self, x: i32, y: u32, len: u32) -> &mut [u8] {
+        assert!(
+            y < self.height,
+            "row {} out of bounds (height={})",
+            y,
+            self.height
+        );
+        let byte_width = self.byte_width as usize;
+        let r = &mut self.rows[y as usize];
+        let x2 = x + len as i32 - 1;
+        match r.ptr {
+            Some(_) => {
+                r.x1 = r.x1.min(x);
+                r.x2 = r.x2.max(x2);
+            }
+            None => {
+                r.x1 = x;
+                r.x2 = x2;
+            }
+        }
+        r.ptr
+            .get_or_insert_with(|| vec![0; byte_width].into_boxed_slice())
+    }
+
+    /// Row data for `y`, or `None` if the row was never written.
+    pub fn row(&self, y: u32) -> Option<RowData<'_>> {
+        let r = &self.rows[y as usize];
+        r.ptr.as_deref().map(|ptr| RowData {
+            x1: r.x1,
+            x2: r.x2,
+            ptr,
+        })
+    }
+
+    /// Iterate over the written rows in ascending `y`.
+    pub fn touched_rows(&self) -> impl Iterator<Item = (u32, RowData<'_>)> + '_ {
+        self.rows.iter().enumerate().filter_map(|(y, r)| {
+            r.ptr.as_deref().map(|ptr| {
+                let row = RowData {
+                    x1: r.x1,
+                    x2: r.x2,
+                    ptr,
+                };
+                (y as u32, row)
+            })
+        })
+    }
+
+    /// Number of allocated rows.
+    pub fn touched_count(&self) -> usize {
+        self.rows.iter().filter(|r| r.ptr.is_some()).count()
+    }
+}
+
+impl RenderingBufferAccess for RenderingBufferDynarow {
+    fn width(&self) -> u32 {
+        self.width
+    }
+    fn height(&self) -> u32 {
+        self.height
+    }
+    fn stride_abs(&self) -> u32 {
+        self.byte_width
+    }
+    unsafe fn row_ptr_const(&self, y: i32) -> *const u8 {
+        match &self.rows.get_unchecked(y as usize).ptr {
+            Some(p) => p.as_ptr(),
+            None => self.zero_row.as_ptr(),
+        }
+    }
+}
+
+impl RenderingBufferMut for RenderingBufferDynarow {
+    #[inline]
+    unsafe fn row_ptr_mut(&mut self, x: i32, y: i32, len: u32) -> *mut u8 {
+        self.row_mut(x, y as u32, len).as_mut_ptr()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Rgba8;
+    use crate::pixfmt_rgba::PixfmtRgba32;
+    use crate::rasterizer_scanline_aa::RasterizerScanlineAa;
+    use crate::renderer_base::RendererBase;
+    use crate::renderer_scanline::render_scanlines_aa_solid;
+    use crate::rendering_buffer::RowAccessor;
+    use crate::scanline_u::ScanlineU8;
+
+    fn triangle(ras: &mut RasterizerScanlineAa) {
+        ras.move_to_d(10.5, 300.0);
+        ras.line_to_d(60.0, 310.5);
+        ras.line_to_d(20.0, 340.0);
+    }
+
+    #[test]
+    fn test_rows_allocated_on_write() {
+        let mut rbuf = RenderingBufferDynarow::new(10, 1000, 40);
+        assert_eq!(rbuf.touched_count(), 0);
+        assert!(rbuf.row(5).is_none());
+
+        rbuf.row_mut(3, 5, 2)[12] = 7;
+        rbuf.row_mut(1, 5, 1);
+        let row = rbuf.row(5).unwrap();
+        assert_eq!((row.x1, row.x2), (1, 4));
+        assert_eq!(row.ptr.len(), 40);
+        assert_eq!(row.ptr[12], 7);
+        assert_eq!(rbuf.touched_count(), 1);
+
+        rbuf.init(10, 1000, 40);
+        assert_eq!(rbuf.touched_count(), 0);
+    }
+
+    #[test]
+    fn test_unwritten_rows_read_as_zero() {
+        let rbuf = RenderingBufferDynarow::new(4, 3, 16);
+        let p = unsafe { rbuf.row_ptr_const(2) };
+        let row = unsafe { std::slice::from_raw_parts(p, 16) };
+        assert!(row.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_render_matches_row_accessor() {
+        let (w, h) = (80u32, 400u32);
+        let color = Rgba8::new(0, 0, 255, 200);
+
+        let mut dynarow = RenderingBufferDynarow::new(w, h, w * 4);
+        {
+            let mut ren = RendererBase::new(PixfmtRgba32::new(&mut dynarow));
+            let mut ras = RasterizerScanlineAa::new();
+            let mut sl = ScanlineU8::new();
+            triangle(&mut ras);
+            render_scanlines_aa_solid(&mut ras, &mut sl, &mut ren, &color);
+        }
+
+        let mut buf = vec![0u8; (w * h * 4) as usize];
+        let mut ra = unsafe { RowAccessor::new_with_buf(buf.as_mut_ptr(), w, h, (w * 4) as i32) };
+        {
+            let mut ren = RendererBase::new(PixfmtRgba32::new(&mut ra));
+            let mut ras = RasterizerScanlineAa::new();
+            let mut sl = ScanlineU8::new();
+            triangle(&mut ras);
+            render_scanlines_aa_solid(&mut ras, &mut sl, &mut ren, &color);
+        }
+
+        // Only the triangle's rows are allocated, each spanning its pixels.
+        let touched: Vec<u32> = dynarow.touched_rows().map(|(y, _)| y).collect();
+        assert_eq!(touched, (300..340).collect::<Vec<_>>());
+        for (y, row) in dynarow.touched_rows() {
+            let line = &buf[(y * w * 4) as usize..][..(w * 4) as usize];
+            assert_eq!(row.ptr, line, "row {y}");
+            let first = line.chunks(4).position(|p| p != [0; 4]).unwrap() as i32;
+            assert!(row.x1 <= first && row.x2 < w as i32);
+        }
+
+        // Exporting through `RenderingBufferAccess` reproduces the image.
+        let mut out = vec![0xFFu8; buf.len()];
+        let mut out_ra =
+            unsafe { RowAccessor::new_with_buf(out.as_mut_ptr(), w, h, (w * 4) as i32) };
+        out_ra.copy_from(&dynarow);
+        assert_eq!(out, buf);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot hold")]
+    fn test_narrow_rows_rejected_by_pixfmt() {
+        // 100 bytes per row is too few for 100 RGBA pixels.
+        let mut rbuf = RenderingBufferDynarow::new(100, 10, 100);
+        let _pf = PixfmtRgba32::new(&mut rbuf);
+    }
+}
@@ -3,6 +3,8 @@
 //! Port of `agg_scanline_bin.h` — simplest scanline type with no coverage
 //! data. Each span just records X and length. Used for non-anti-aliased
 //! rendering or as a clipping mask.
+//!
+//! Spans are `i32`, so `Scanline32Bin` is just an alias.
 
 use crate::rasterizer_scanline_aa::Scanline;
 
@@ -31,6 +33,9 @@ pub struct ScanlineBin {
     cur_span: usize,
 }
 
+/// Port of C++ `scanline32_bin`; the same type as [`ScanlineBin`].
+pub type Scanline32Bin = ScanlineBin;
+
 impl ScanlineBin {
     pub fn new() -> Self {
         Self {
@@ -42,8 +47,8 @@ impl ScanlineBin {
     }
 
     /// Prepare for a new scanline with the given X range.
-    pub fn reset(&mut self, _min_x: i32, max_x: i32) {
-        let max_len = (max_x + 3) as usize;
+    pub fn reset(&mut self, min_x: i32, max_x: i32) {
+        let max_len = (max_x - min_x + 3) as usize;
         if max_len > self.spans.len() {
             self.spans.resize(max_len, BinSpan::default());
         }
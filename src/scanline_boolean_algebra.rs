@@ -5,6 +5,7 @@
 //! rasterized shapes stored in scanline storage.
 
 use crate::rasterizer_scanline_aa::{RasterizerScanlineAa, Scanline};
+use crate::rasterizer_sl_clip::RasterizerClip;
 use crate::scanline_storage_aa::ScanlineStorageAa;
 use crate::scanline_storage_bin::ScanlineStorageBin;
 use crate::scanline_u::ScanlineU8;
@@ -107,10 +108,10 @@ fn xor_covers_abs_diff(c1: u32, c2: u32) -> u8 {
 /// Perform a boolean operation on two rasterized shapes.
 ///
 /// Takes two rasterizers, rasterizes both into storage, then combines them.
-pub fn sbool_combine_shapes_aa(
+pub fn sbool_combine_shapes_aa<CL: RasterizerClip>(
     op: SBoolOp,
-    ras1: &mut RasterizerScanlineAa,
-    ras2: &mut RasterizerScanlineAa,
+    ras1: &mut RasterizerScanlineAa<CL>,
+    ras2: &mut RasterizerScanlineAa<CL>,
     sl1: &mut ScanlineU8,
     sl2: &mut ScanlineU8,
     sl_result: &mut ScanlineU8,
@@ -132,8 +133,8 @@ pub fn sbool_combine_shapes_aa(
 }
 
 /// Render a rasterizer's output into AA storage.
-fn render_to_storage(
-    ras: &mut RasterizerScanlineAa,
+fn render_to_storage<CL: RasterizerClip>(
+    ras: &mut RasterizerScanlineAa<CL>,
     sl: &mut ScanlineU8,
     storage: &mut ScanlineStorageAa,
 ) {
@@ -420,10 +421,10 @@ fn combine_scanlines_aa(
 // ============================================================================
 
 /// Perform a boolean operation on two rasterized shapes (binary/no AA).
-pub fn sbool_combine_shapes_bin(
+pub fn sbool_combine_shapes_bin<CL: RasterizerClip>(
     op: SBoolOp,
-    ras1: &mut RasterizerScanlineAa,
-    ras2: &mut RasterizerScanlineAa,
+    ras1: &mut RasterizerScanlineAa<CL>,
+    ras2: &mut RasterizerScanlineAa<CL>,
     sl1: &mut ScanlineU8,
     sl2: &mut ScanlineU8,
     sl_result: &mut ScanlineU8,
@@ -445,8 +446,8 @@ pub fn sbool_combine_shapes_bin(
 }
 
 /// Render a rasterizer's output into binary storage.
-fn render_to_bin_storage(
-    ras: &mut RasterizerScanlineAa,
+fn render_to_bin_storage<CL: RasterizerClip>(
+    ras: &mut RasterizerScanlineAa<CL>,
     sl: &mut ScanlineU8,
     storage: &mut ScanlineStorageBin,
 ) {
@@ -906,6 +907,65 @@ mod tests {
         assert_eq!(result.num_scanlines(), 20);
     }
 
+    #[test]
+    fn test_shapes_wider_than_16_bit() {
+        use crate::scanline_u::Scanline32U8;
+
+        fn band(x1: f64, x2: f64, y1: f64, y2: f64) -> RasterizerScanlineAa {
+            let mut ras = RasterizerScanlineAa::new();
+            ras.move_to_d(x1, y1);
+            ras.line_to_d(x2, y1);
+            ras.line_to_d(x2, y2);
+            ras.line_to_d(x1, y2);
+            ras
+        }
+        let mut ras1 = band(0.0, 100_000.0, 0.0, 4.0);
+        let mut ras2 = band(50_000.0, 150_000.0, 2.0, 6.0);
+        let (mut sl1, mut sl2, mut sl) = (
+            Scanline32U8::new(),
+            Scanline32U8::new(),
+            Scanline32U8::new(),
+        );
+        let mut s1 = ScanlineStorageAa::new();
+        let mut s2 = ScanlineStorageAa::new();
+        let mut result = ScanlineStorageAa::new();
+
+        sbool_combine_shapes_aa(
+            SBoolOp::And,
+            &mut ras1,
+            &mut ras2,
+            &mut sl1,
+            &mut sl2,
+            &mut sl,
+            &mut s1,
+            &mut s2,
+            &mut result,
+        );
+        assert_eq!(result.num_scanlines(), 2);
+        assert_eq!((result.min_x(), result.max_x()), (50_000, 99_999));
+        let spans: Vec<_> = result.embedded_spans(0).collect();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].abs_len(), 50_000);
+        assert!((0..50_000).all(|i| spans[0].cover_at(i) == 255));
+
+        sbool_combine_shapes_aa(
+            SBoolOp::Or,
+            &mut ras1,
+            &mut ras2,
+            &mut sl1,
+            &mut sl2,
+            &mut sl,
+            &mut s1,
+            &mut s2,
+            &mut result,
+        );
+        assert_eq!((result.min_x(), result.max_x()), (0, 149_999));
+        let widest = (0..result.num_scanlines())
+            .flat_map(|i| result.embedded_spans(i).map(|sp| sp.abs_len()))
+            .max();
+        assert_eq!(widest, Some(150_000));
+    }
+
     #[test]
     fn test_cover_math_intersect() {
         assert_eq!(intersect_covers(255, 255), 255); // full*full → full
@@ -2,9 +2,14 @@
 //!
 //! Port of `agg_scanline_p.h` — stores coverage data in a packed/RLE format.
 //! Solid spans (uniform coverage) use negative `len` with a single cover value,
-//! saving memory for large filled areas.
+//! saving memory for large filled areas. `ScanlineP8Am` applies an alpha mask
+//! as each scanline is finalized.
+//!
+//! As with `ScanlineU8`, spans are `i32`, so `Scanline32P8` is just an alias.
 
+use crate::alpha_mask_u8::AlphaMask;
 use crate::rasterizer_scanline_aa::Scanline;
+use crate::scanline_u::{ScanlineSpan, ScanlineSpans};
 
 // ============================================================================
 // PackedSpan — a span in a packed scanline
@@ -16,12 +21,7 @@ use crate::rasterizer_scanline_aa::Scanline;
 ///   for `len` values
 /// - `len < 0`: solid span of `-len` pixels, all with the single cover value
 ///   at `cover_offset`
-#[derive(Debug, Clone, Copy, Default)]
-pub struct PackedSpan {
-    pub x: i32,
-    pub len: i32,
-    pub cover_offset: usize,
-}
+pub type PackedSpan = ScanlineSpan;
 
 // ============================================================================
 // ScanlineP8 — packed scanline with RLE for solid spans
@@ -40,6 +40,9 @@ pub struct ScanlineP8 {
     cur_span: usize,
 }
 
+/// Port of C++ `scanline32_p8`; the same type as [`ScanlineP8`].
+pub type Scanline32P8 = ScanlineP8;
+
 impl ScanlineP8 {
     pub fn new() -> Self {
         Self {
@@ -53,8 +56,8 @@ impl ScanlineP8 {
     }
 
     /// Prepare for a new scanline with the given X range.
-    pub fn reset(&mut self, _min_x: i32, max_x: i32) {
-        let max_len = (max_x + 3) as usize;
+    pub fn reset(&mut self, min_x: i32, max_x: i32) {
+        let max_len = (max_x - min_x + 3) as usize;
         if max_len > self.spans.len() {
             self.spans.resize(max_len, PackedSpan::default());
             self.covers.resize(max_len, 0);
@@ -129,12 +132,136 @@ impl Scanline for ScanlineP8 {
     }
 }
 
+impl ScanlineSpans for ScanlineP8 {
+    fn reset(&mut self, min_x: i32, max_x: i32) {
+        ScanlineP8::reset(self, min_x, max_x);
+    }
+
+    fn begin(&self) -> &[ScanlineSpan] {
+        ScanlineP8::begin(self)
+    }
+
+    fn covers(&self) -> &[u8] {
+        ScanlineP8::covers(self)
+    }
+}
+
 impl Default for ScanlineP8 {
     fn default() -> Self {
         Self::new()
     }
 }
 
+// ============================================================================
+// ScanlineP8Am — packed scanline with alpha masking
+// ============================================================================
+
+/// `ScanlineP8` whose covers are multiplied by an alpha mask on `finalize`.
+///
+/// AGG has no packed masked scanline; this is the packed counterpart of
+/// `scanline_u8_am`. A mask varies per pixel, so `finalize` expands solid
+/// spans and the spans seen by renderers always carry per-pixel covers.
+pub struct ScanlineP8Am<'a, AM> {
+    sl: ScanlineP8,
+    alpha_mask: &'a AM,
+    spans: Vec<PackedSpan>,
+    covers: Vec<u8>,
+}
+
+impl<'a, AM: AlphaMask> ScanlineP8Am<'a, AM> {
+    pub fn new(alpha_mask: &'a AM) -> Self {
+        Self {
+            sl: ScanlineP8::new(),
+            alpha_mask,
+            spans: Vec::new(),
+            covers: Vec::new(),
+        }
+    }
+
+    pub fn attach_alpha_mask(&mut self, alpha_mask: &'a AM) {
+        self.alpha_mask = alpha_mask;
+    }
+
+    /// Prepare for a new scanline with the given X range.
+    pub fn reset(&mut self, min_x: i32, max_x: i32) {
+        self.sl.reset(min_x, max_x);
+        self.spans.clear();
+    }
+
+    /// Get the slice of active spans (for renderer iteration).
+    pub fn begin(&self) -> &[PackedSpan] {
+        &self.spans
+    }
+
+    /// Get the full covers array (spans reference into this via `cover_offset`).
+    pub fn covers(&self) -> &[u8] {
+        &self.covers
+    }
+}
+
+impl<AM: AlphaMask> Scanline for ScanlineP8Am<'_, AM> {
+    fn reset_spans(&mut self) {
+        self.sl.reset_spans();
+        self.spans.clear();
+    }
+
+    fn add_cell(&mut self, x: i32, cover: u32) {
+        self.sl.add_cell(x, cover);
+    }
+
+    fn add_span(&mut self, x: i32, len: u32, cover: u32) {
+        self.sl.add_span(x, len, cover);
+    }
+
+    fn finalize(&mut self, y: i32) {
+        self.sl.finalize(y);
+        self.spans.clear();
+        self.covers.clear();
+        let packed = self.sl.covers();
+        for span in self.sl.begin() {
+            let cover_offset = self.covers.len();
+            if span.len < 0 {
+                let len = span.len.unsigned_abs() as usize;
+                let cover = packed[span.cover_offset];
+                self.covers.resize(cover_offset + len, cover);
+            } else {
+                let src = &packed[span.cover_offset..span.cover_offset + span.len as usize];
+                self.covers.extend_from_slice(src);
+            }
+            let len = self.covers.len() - cover_offset;
+            self.alpha_mask
+                .combine_hspan(span.x, y, &mut self.covers[cover_offset..]);
+            self.spans.push(PackedSpan {
+                x: span.x,
+                len: len as i32,
+                cover_offset,
+            });
+        }
+    }
+
+    fn num_spans(&self) -> u32 {
+        self.sl.num_spans()
+    }
+
+    fn y(&self) -> i32 {
+        self.sl.y()
+    }
+}
+
+impl<AM: AlphaMask> ScanlineSpans for ScanlineP8Am<'_, AM> {
+    fn reset(&mut self, min_x: i32, max_x: i32) {
+        ScanlineP8Am::reset(self, min_x, max_x);
+    }
+
+    fn begin(&self) -> &[ScanlineSpan] {
+        ScanlineP8Am::begin(self)
+    }
+
+    fn covers(&self) -> &[u8] {
+        ScanlineP8Am::covers(self)
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -142,6 +269,8 @@ impl Default for ScanlineP8 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::alpha_mask_u8::{AlphaMaskGray8, OneComponentMask};
+    use crate::rendering_buffer::RowAccessor;
 
     #[test]
     fn test_new() {
@@ -214,6 +343,19 @@ mod tests {
         assert_eq!(sl.num_spans(), 2);
     }
 
+    #[test]
+    fn test_wide_row_far_from_origin() {
+        // Sized by the row's width, not its distance from x = 0.
+        let mut sl = Scanline32P8::new();
+        sl.reset(-120_000, -20_000);
+        sl.add_cell(-120_000, 64);
+        sl.add_span(-119_999, 99_998, 255);
+        sl.add_cell(-20_001, 64);
+        let lens: Vec<i32> = sl.begin().iter().map(|s| s.len).collect();
+        assert_eq!(lens, [1, -99_998, 1]);
+        assert_eq!(sl.begin()[2].x, -20_001);
+    }
+
     #[test]
     fn test_reset_spans() {
         let mut sl = ScanlineP8::new();
@@ -222,4 +364,26 @@ mod tests {
         sl.reset_spans();
         assert_eq!(sl.num_spans(), 0);
     }
+
+    #[test]
+    fn test_am_expands_solid_spans_and_applies_mask() {
+        let buf = [255u8, 0, 128, 255, 64, 255];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, 6, 1, 6);
+        }
+        let mask = AlphaMaskGray8::new(&ra, OneComponentMask);
+        let mut sl = ScanlineP8Am::new(&mask);
+        sl.reset(0, 10);
+        sl.add_cell(0, 100);
+        sl.add_span(2, 3, 255);
+        sl.finalize(0);
+        assert_eq!(sl.num_spans(), 2);
+        let spans = sl.begin();
+        assert_eq!((spans[0].x, spans[0].len), (0, 1));
+        assert_eq!((spans[1].x, spans[1].len), (2, 3));
+        assert_eq!(sl.covers()[spans[0].cover_offset], 100);
+        let off = spans[1].cover_offset;
+        assert_eq!(&sl.covers()[off..off + 3], &[128, 255, 64]);
+    }
 }
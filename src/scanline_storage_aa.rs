@@ -3,6 +3,9 @@
 //! Port of `agg_scanline_storage_aa.h`.
 //! Stores rasterized scanlines in memory for later boolean operations
 //! or serialized replay. Each span stores per-pixel coverage data.
+//!
+//! Span coordinates and lengths are stored as `i32`, as in C++, so rows
+//! wider than 32767 pixels from `Scanline32U8` / `Scanline32P8` fit.
 
 use crate::rasterizer_scanline_aa::Scanline;
 
@@ -2,7 +2,8 @@
 //!
 //! Port of `agg_scanline_storage_bin.h`.
 //! Stores rasterized scanlines without coverage data, for boolean
-//! operations on binary (non-anti-aliased) shapes.
+//! operations on binary (non-anti-aliased) shapes. Spans are stored as
+//! `i32`, so rows of any width fit.
 
 use crate::rasterizer_scanline_aa::Scanline;
 
@@ -2,8 +2,14 @@
 //!
 //! Port of `agg_scanline_u.h` — stores per-pixel coverage values in a flat
 //! array, with spans referencing into it. This is the most commonly used
-//! scanline type for anti-aliased rendering.
+//! scanline type for anti-aliased rendering. `ScanlineU8Am` applies an
+//! alpha mask to the covers as each scanline is finalized.
+//!
+//! Span coordinates and lengths are `i32`, so these already are the C++
+//! `scanline32_*` types and handle rows wider than 32767 pixels; the
+//! `Scanline32*` aliases name them for code ported from C++.
 
+use crate::alpha_mask_u8::AlphaMask;
 use crate::rasterizer_scanline_aa::Scanline;
 
 // ============================================================================
@@ -13,7 +19,8 @@ use crate::rasterizer_scanline_aa::Scanline;
 /// A horizontal span within a scanline, referencing coverage data.
 ///
 /// For `ScanlineU8`: `len` is always positive, `cover_offset` indexes into
-/// the covers array.
+/// the covers array. For `ScanlineP8`, a negative `len` marks a solid span
+/// of `-len` pixels sharing the single cover at `cover_offset`.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ScanlineSpan {
     pub x: i32,
@@ -21,6 +28,26 @@ pub struct ScanlineSpan {
     pub cover_offset: usize,
 }
 
+// ============================================================================
+// ScanlineSpans — read access for renderers
+// ============================================================================
+
+/// Scanline containers that renderers can read spans and covers back from.
+///
+/// Implemented by `ScanlineU8`, `ScanlineP8` and their alpha-masked
+/// variants, so `render_scanlines_aa_solid` and `render_scanlines_aa` accept
+/// any of them.
+pub trait ScanlineSpans: Scanline {
+    /// Prepare for a new sweep over the given X range.
+    fn reset(&mut self, min_x: i32, max_x: i32);
+
+    /// The active spans of the current scanline.
+    fn begin(&self) -> &[ScanlineSpan];
+
+    /// The covers array the spans reference into.
+    fn covers(&self) -> &[u8];
+}
+
 // ============================================================================
 // ScanlineU8 — unpacked scanline with per-pixel coverage
 // ============================================================================
@@ -45,6 +72,9 @@ pub struct ScanlineU8 {
     cur_span: usize, // index of current span (0 = sentinel, spans start at 1)
 }
 
+/// Port of C++ `scanline32_u8`; the same type as [`ScanlineU8`].
+pub type Scanline32U8 = ScanlineU8;
+
 impl ScanlineU8 {
     pub fn new() -> Self {
         Self {
@@ -79,6 +109,15 @@ impl ScanlineU8 {
     pub fn covers(&self) -> &[u8] {
         &self.covers
     }
+
+    /// Multiply every span's covers by `mask`, as C++ `scanline_u8_am` does
+    /// in `finalize`.
+    pub(crate) fn combine_mask<AM: AlphaMask>(&mut self, mask: &AM) {
+        for span in &self.spans[1..=self.cur_span] {
+            let covers = &mut self.covers[span.cover_offset..span.cover_offset + span.len as usize];
+            mask.combine_hspan(span.x, self.y_val, covers);
+        }
+    }
 }
 
 impl Scanline for ScanlineU8 {
@@ -131,12 +170,111 @@ impl Scanline for ScanlineU8 {
     }
 }
 
+impl ScanlineSpans for ScanlineU8 {
+    fn reset(&mut self, min_x: i32, max_x: i32) {
+        ScanlineU8::reset(self, min_x, max_x);
+    }
+
+    fn begin(&self) -> &[ScanlineSpan] {
+        ScanlineU8::begin(self)
+    }
+
+    fn covers(&self) -> &[u8] {
+        ScanlineU8::covers(self)
+    }
+}
+
 impl Default for ScanlineU8 {
     fn default() -> Self {
         Self::new()
     }
 }
 
+// ============================================================================
+// ScanlineU8Am — unpacked scanline with alpha masking
+// ============================================================================
+
+/// `ScanlineU8` whose covers are multiplied by an alpha mask on `finalize`.
+///
+/// Port of C++ `scanline_u8_am<AlphaMask>`. Works with any renderer, since
+/// masking happens before the renderer sees the covers.
+pub struct ScanlineU8Am<'a, AM> {
+    sl: ScanlineU8,
+    alpha_mask: &'a AM,
+}
+
+/// Port of C++ `scanline32_u8_am`; the same type as [`ScanlineU8Am`].
+pub type Scanline32U8Am<'a, AM> = ScanlineU8Am<'a, AM>;
+
+impl<'a, AM: AlphaMask> ScanlineU8Am<'a, AM> {
+    pub fn new(alpha_mask: &'a AM) -> Self {
+        Self {
+            sl: ScanlineU8::new(),
+            alpha_mask,
+        }
+    }
+
+    pub fn attach_alpha_mask(&mut self, alpha_mask: &'a AM) {
+        self.alpha_mask = alpha_mask;
+    }
+
+    /// Prepare for a new scanline with the given X range.
+    pub fn reset(&mut self, min_x: i32, max_x: i32) {
+        self.sl.reset(min_x, max_x);
+    }
+
+    /// Get the slice of active spans (for renderer iteration).
+    pub fn begin(&self) -> &[ScanlineSpan] {
+        self.sl.begin()
+    }
+
+    /// Get the full covers array (spans reference into this via `cover_offset`).
+    pub fn covers(&self) -> &[u8] {
+        self.sl.covers()
+    }
+}
+
+impl<AM: AlphaMask> Scanline for ScanlineU8Am<'_, AM> {
+    fn reset_spans(&mut self) {
+        self.sl.reset_spans();
+    }
+
+    fn add_cell(&mut self, x: i32, cover: u32) {
+        self.sl.add_cell(x, cover);
+    }
+
+    fn add_span(&mut self, x: i32, len: u32, cover: u32) {
+        self.sl.add_span(x, len, cover);
+    }
+
+    fn finalize(&mut self, y: i32) {
+        self.sl.finalize(y);
+        self.sl.combine_mask(self.alpha_mask);
+    }
+
+    fn num_spans(&self) -> u32 {
+        self.sl.num_spans()
+    }
+
+    fn y(&self) -> i32 {
+        self.sl.y()
+    }
+}
+
+impl<AM: AlphaMask> ScanlineSpans for ScanlineU8Am<'_, AM> {
+    fn reset(&mut self, min_x: i32, max_x: i32) {
+        self.sl.reset(min_x, max_x);
+    }
+
+    fn begin(&self) -> &[ScanlineSpan] {
+        self.sl.begin()
+    }
+
+    fn covers(&self) -> &[u8] {
+        self.sl.covers()
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -144,6 +282,8 @@ impl Default for ScanlineU8 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::alpha_mask_u8::{AlphaMaskGray8, OneComponentMask};
+    use crate::rendering_buffer::RowAccessor;
 
     #[test]
     fn test_new() {
@@ -251,4 +391,28 @@ mod tests {
         assert_eq!(sl.covers()[spans[0].cover_offset], 128);
         assert_eq!(sl.covers()[spans[0].cover_offset + 1], 64);
     }
+
+    #[test]
+    fn test_am_finalize_applies_mask() {
+        // 4x2 mask: row 0 = 0, 128, 255, 64; row 1 = 255.
+        let buf = [0u8, 128, 255, 64, 255, 255, 255, 255];
+        let mut ra = RowAccessor::new();
+        unsafe {
+            ra.attach(buf.as_ptr() as *mut u8, 4, 2, 4);
+        }
+        let mask = AlphaMaskGray8::new(&ra, OneComponentMask);
+        let mut sl = ScanlineU8Am::new(&mask);
+        sl.reset(0, 10);
+        sl.add_span(0, 6, 255);
+        sl.finalize(0);
+        let off = sl.begin()[0].cover_offset;
+        // Outside the mask (x >= 4) the cover is clipped to zero.
+        assert_eq!(&sl.covers()[off..off + 6], &[0, 128, 255, 64, 0, 0]);
+
+        sl.reset_spans();
+        sl.add_cell(1, 100);
+        sl.finalize(1);
+        let off = sl.begin()[0].cover_offset;
+        assert_eq!(sl.covers()[off], 100);
+    }
 }
@@ -4,8 +4,7 @@
 //! triangle using DDA-based scanline interpolation.
 
 use crate::basics::{iround, VertexSource};
-use crate::color::Rgba8;
-use crate::dda_line::DdaLineInterpolator;
+use crate::color::{Rgba8, RgbaIntColor};
 use crate::math::cross_product;
 use crate::renderer_scanline::SpanGenerator;
 use crate::span_gouraud::{CoordType, SpanGouraud};
@@ -66,20 +65,22 @@ impl RgbaCalc {
         }
     }
 
-    fn init(&mut self, c1: &CoordType<Rgba8>, c2: &CoordType<Rgba8>) {
+    fn init<C: RgbaIntColor>(&mut self, c1: &CoordType<C>, c2: &CoordType<C>) {
         self.x1 = c1.x - 0.5;
         self.y1 = c1.y - 0.5;
         self.dx = c2.x - c1.x;
         let dy = c2.y - c1.y;
         self.inv_dy = if dy < 1e-5 { 1e5 } else { 1.0 / dy };
-        self.r1 = c1.color.r as i32;
-        self.g1 = c1.color.g as i32;
-        self.b1 = c1.color.b as i32;
-        self.a1 = c1.color.a as i32;
-        self.dr = c2.color.r as i32 - self.r1;
-        self.dg = c2.color.g as i32 - self.g1;
-        self.db = c2.color.b as i32 - self.b1;
-        self.da = c2.color.a as i32 - self.a1;
+        let [r1, g1, b1, a1] = c1.color.channels();
+        let [r2, g2, b2, a2] = c2.color.channels();
+        self.r1 = r1 as i32;
+        self.g1 = g1 as i32;
+        self.b1 = b1 as i32;
+        self.a1 = a1 as i32;
+        self.dr = r2 as i32 - self.r1;
+        self.dg = g2 as i32 - self.g1;
+        self.db = b2 as i32 - self.b1;
+        self.da = a2 as i32 - self.a1;
     }
 
     fn calc(&mut self, y: f64) {
@@ -92,8 +93,52 @@ impl RgbaCalc {
     }
 }
 
+// ============================================================================
+// ChannelDda — horizontal color interpolator
+// ============================================================================
+
+/// Horizontal per-channel interpolator.
+///
+/// Same stepping as C++ `dda_line_interpolator<14>`, but the accumulator is
+/// 64-bit: with 16-bit channels and a very short edge-to-edge run (near a
+/// vertex `nlen` can be a single subpixel), rolling back to the span start or
+/// running past the far edge overflows an `i32`. Results are identical
+/// whenever the 32-bit version would not overflow.
+struct ChannelDda {
+    y: i32,
+    inc: i64,
+    dy: i64,
+}
+
+impl ChannelDda {
+    const FRACTION_SHIFT: i32 = 14;
+
+    fn new(y1: i32, y2: i32, count: u32) -> Self {
+        Self {
+            y: y1,
+            inc: (((y2 - y1) << Self::FRACTION_SHIFT) / count as i32) as i64,
+            dy: 0,
+        }
+    }
+
+    #[inline]
+    fn inc_by(&mut self, n: u32) {
+        self.dy += self.inc * n as i64;
+    }
+
+    #[inline]
+    fn dec_by(&mut self, n: u32) {
+        self.dy -= self.inc * n as i64;
+    }
+
+    #[inline]
+    fn y(&self) -> i32 {
+        (self.y as i64 + (self.dy >> Self::FRACTION_SHIFT)) as i32
+    }
+}
+
 /// Adjust DDA by a signed step count (equivalent to C++ `r -= start`).
-fn dda_sub(dda: &mut DdaLineInterpolator<14, 0>, n: i32) {
+fn dda_sub(dda: &mut ChannelDda, n: i32) {
     if n >= 0 {
         dda.dec_by(n as u32);
     } else {
@@ -107,13 +152,15 @@ fn dda_sub(dda: &mut DdaLineInterpolator<14, 0>, n: i32) {
 
 /// RGBA Gouraud shading span generator.
 ///
-/// Composes `SpanGouraud<Rgba8>` for triangle storage and provides the
+/// Composes `SpanGouraud<C>` for triangle storage and provides the
 /// `SpanGenerator` implementation that interpolates RGBA colors across
-/// scanlines using DDA.
+/// scanlines using DDA. The color type defaults to [`Rgba8`];
+/// `SpanGouraudRgba<Rgba16>` (built with `new_for` or
+/// `new_with_triangle`) feeds 16-bit pixel formats.
 ///
 /// Port of C++ `span_gouraud_rgba<ColorT>`.
-pub struct SpanGouraudRgba {
-    base: SpanGouraud<Rgba8>,
+pub struct SpanGouraudRgba<C: RgbaIntColor = Rgba8> {
+    base: SpanGouraud<C>,
     swap: bool,
     y2: i32,
     rgba1: RgbaCalc,
@@ -123,6 +170,13 @@ pub struct SpanGouraudRgba {
 
 impl SpanGouraudRgba {
     pub fn new() -> Self {
+        Self::new_for()
+    }
+}
+
+impl<C: RgbaIntColor> SpanGouraudRgba<C> {
+    /// Like `new`, for a span of colors of type `C`.
+    pub fn new_for() -> Self {
         Self {
             base: SpanGouraud::new(),
             swap: false,
@@ -135,9 +189,9 @@ impl SpanGouraudRgba {
 
     #[allow(clippy::too_many_arguments)]
     pub fn new_with_triangle(
-        c1: Rgba8,
-        c2: Rgba8,
-        c3: Rgba8,
+        c1: C,
+        c2: C,
+        c3: C,
         x1: f64,
         y1: f64,
         x2: f64,
@@ -157,7 +211,7 @@ impl SpanGouraudRgba {
     }
 
     /// Delegate to base: set vertex colors.
-    pub fn colors(&mut self, c1: Rgba8, c2: Rgba8, c3: Rgba8) {
+    pub fn colors(&mut self, c1: C, c2: C, c3: C) {
         self.base.colors(c1, c2, c3);
     }
 
@@ -168,13 +222,13 @@ impl SpanGouraudRgba {
     }
 }
 
-impl Default for SpanGouraudRgba {
+impl<C: RgbaIntColor> Default for SpanGouraudRgba<C> {
     fn default() -> Self {
-        Self::new()
+        Self::new_for()
     }
 }
 
-impl VertexSource for SpanGouraudRgba {
+impl<C: RgbaIntColor> VertexSource for SpanGouraudRgba<C> {
     fn rewind(&mut self, path_id: u32) {
         self.base.rewind(path_id);
     }
@@ -184,8 +238,8 @@ impl VertexSource for SpanGouraudRgba {
     }
 }
 
-impl SpanGenerator for SpanGouraudRgba {
-    type Color = Rgba8;
+impl<C: RgbaIntColor> SpanGenerator for SpanGouraudRgba<C> {
+    type Color = C;
 
     fn prepare(&mut self) {
         let coord = self.base.arrange_vertices();
@@ -201,7 +255,7 @@ impl SpanGenerator for SpanGouraudRgba {
         self.rgba3.init(&coord[1], &coord[2]);
     }
 
-    fn generate(&mut self, span: &mut [Rgba8], x: i32, y: i32, len: u32) {
+    fn generate(&mut self, span: &mut [C], x: i32, y: i32, len: u32) {
         self.rgba1.calc(y as f64);
 
         let (pc1_r, pc1_g, pc1_b, pc1_a, pc1_x, pc2_r, pc2_g, pc2_b, pc2_a, pc2_x);
@@ -266,10 +320,10 @@ impl SpanGenerator for SpanGouraudRgba {
             nlen = 1;
         }
 
-        let mut r = DdaLineInterpolator::<14, 0>::new(pc1_r, pc2_r, nlen as u32);
-        let mut g = DdaLineInterpolator::<14, 0>::new(pc1_g, pc2_g, nlen as u32);
-        let mut b = DdaLineInterpolator::<14, 0>::new(pc1_b, pc2_b, nlen as u32);
-        let mut a = DdaLineInterpolator::<14, 0>::new(pc1_a, pc2_a, nlen as u32);
+        let mut r = ChannelDda::new(pc1_r, pc2_r, nlen as u32);
+        let mut g = ChannelDda::new(pc1_g, pc2_g, nlen as u32);
+        let mut b = ChannelDda::new(pc1_b, pc2_b, nlen as u32);
+        let mut a = ChannelDda::new(pc1_a, pc2_a, nlen as u32);
 
         // Roll back interpolators to span start
         let mut start = pc1_x - (x << SUBPIXEL_SHIFT);
@@ -279,7 +333,7 @@ impl SpanGenerator for SpanGouraudRgba {
         dda_sub(&mut a, start);
         nlen += start;
 
-        let lim = Rgba8::BASE_MASK as i32;
+        let lim = C::BASE_MASK as i32;
         let mut idx = 0usize;
         let mut remaining = len as i32;
 
@@ -289,10 +343,7 @@ impl SpanGenerator for SpanGouraudRgba {
             let vg = g.y().clamp(0, lim);
             let vb = b.y().clamp(0, lim);
             let va = a.y().clamp(0, lim);
-            span[idx].r = vr as u8;
-            span[idx].g = vg as u8;
-            span[idx].b = vb as u8;
-            span[idx].a = va as u8;
+            span[idx] = C::from_channels(vr as u32, vg as u32, vb as u32, va as u32);
             r.inc_by(SUBPIXEL_SCALE as u32);
             g.inc_by(SUBPIXEL_SCALE as u32);
             b.inc_by(SUBPIXEL_SCALE as u32);
@@ -305,10 +356,7 @@ impl SpanGenerator for SpanGouraudRgba {
 
         // Middle part — no overflow checking needed
         while remaining > 0 && nlen > 0 {
-            span[idx].r = r.y() as u8;
-            span[idx].g = g.y() as u8;
-            span[idx].b = b.y() as u8;
-            span[idx].a = a.y() as u8;
+            span[idx] = C::from_channels(r.y() as u32, g.y() as u32, b.y() as u32, a.y() as u32);
             r.inc_by(SUBPIXEL_SCALE as u32);
             g.inc_by(SUBPIXEL_SCALE as u32);
             b.inc_by(SUBPIXEL_SCALE as u32);
@@ -324,10 +372,7 @@ impl SpanGenerator for SpanGouraudRgba {
             let vg = g.y().clamp(0, lim);
             let vb = b.y().clamp(0, lim);
             let va = a.y().clamp(0, lim);
-            span[idx].r = vr as u8;
-            span[idx].g = vg as u8;
-            span[idx].b = vb as u8;
-            span[idx].a = va as u8;
+            span[idx] = C::from_channels(vr as u32, vg as u32, vb as u32, va as u32);
             r.inc_by(SUBPIXEL_SCALE as u32);
             g.inc_by(SUBPIXEL_SCALE as u32);
             b.inc_by(SUBPIXEL_SCALE as u32);
@@ -502,7 +547,7 @@ mod tests {
 
     #[test]
     fn test_dda_sub_positive() {
-        let mut dda = DdaLineInterpolator::<14, 0>::new(0, 255, 100);
+        let mut dda = ChannelDda::new(0, 255, 100);
         let y_before = dda.y();
         dda_sub(&mut dda, 10);
         // After subtracting 10 steps, y should decrease
@@ -511,7 +556,7 @@ mod tests {
 
     #[test]
     fn test_dda_sub_negative() {
-        let mut dda = DdaLineInterpolator::<14, 0>::new(0, 255, 100);
+        let mut dda = ChannelDda::new(0, 255, 100);
         let y_before = dda.y();
         dda_sub(&mut dda, -10);
         // After subtracting negative steps (= adding), y should increase
@@ -2,23 +2,36 @@
 //!
 //! Port of `agg_span_image_filter_rgba.h` — span generators for transforming
 //! RGBA images with various filter kernels: nearest neighbor, bilinear,
-//! custom 2x2, general N-tap, and resampling variants.
+//! custom 2x2, general N-tap, and resampling variants. Nearest-neighbor and
+//! bilinear are also provided for floating-point (RGBA128) sources.
+//!
+//! The RGBA generators take the source's channel order `O` as a type
+//! parameter (default `OrderRgba`), like C++ `source_type::order_type`;
+//! `new_ordered` builds one for another order.
 
-use crate::color::Rgba8;
+use crate::color::{OrderRgba, Rgba32, Rgba8, RgbaOrder};
 use crate::image_accessors::ImageSource;
 use crate::image_filters::{
     ImageFilterLut, IMAGE_FILTER_SCALE, IMAGE_FILTER_SHIFT, IMAGE_SUBPIXEL_MASK,
     IMAGE_SUBPIXEL_SCALE, IMAGE_SUBPIXEL_SHIFT,
 };
+use crate::pixfmt_rgba128::get_pix as get_pix_f32;
 use crate::renderer_scanline::SpanGenerator;
 use crate::rendering_buffer::RowAccessor;
 use crate::span_image_filter::{SpanImageFilterBase, SpanImageResampleAffine};
 use crate::span_interpolator_linear::{SpanInterpolator, SpanInterpolatorLinear};
 use crate::trans_affine::TransAffine;
+use std::marker::PhantomData;
 
 /// Base mask for 8-bit color (255).
 const BASE_MASK: i32 = 255;
 
+/// Positions of R, G, B and A within a source pixel of order `O`.
+#[inline]
+fn order_index<O: RgbaOrder>() -> [usize; 4] {
+    [O::R, O::G, O::B, O::A]
+}
+
 // ============================================================================
 // SpanImageFilterRgbaNn — nearest neighbor
 // ============================================================================
@@ -28,16 +41,25 @@ const BASE_MASK: i32 = 255;
 /// Simplest and fastest: picks the nearest pixel without interpolation.
 ///
 /// Port of C++ `span_image_filter_rgba_nn<Source, Interpolator>`.
-pub struct SpanImageFilterRgbaNn<'a, S: ImageSource, I> {
+pub struct SpanImageFilterRgbaNn<'a, S: ImageSource, I, O = OrderRgba> {
     base: SpanImageFilterBase<'a, I>,
     source: &'a mut S,
+    order: PhantomData<O>,
 }
 
 impl<'a, S: ImageSource, I> SpanImageFilterRgbaNn<'a, S, I> {
     pub fn new(source: &'a mut S, interpolator: &'a mut I) -> Self {
+        Self::new_ordered(source, interpolator)
+    }
+}
+
+impl<'a, S: ImageSource, I, O> SpanImageFilterRgbaNn<'a, S, I, O> {
+    /// Like `new`, for a source whose pixels are in channel order `O`.
+    pub fn new_ordered(source: &'a mut S, interpolator: &'a mut I) -> Self {
         Self {
             base: SpanImageFilterBase::new(interpolator, None),
             source,
+            order: PhantomData,
         }
     }
 
@@ -50,12 +72,15 @@ impl<'a, S: ImageSource, I> SpanImageFilterRgbaNn<'a, S, I> {
     }
 }
 
-impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaNn<'_, S, I> {
+impl<S: ImageSource, I: SpanInterpolator, O: RgbaOrder> SpanGenerator
+    for SpanImageFilterRgbaNn<'_, S, I, O>
+{
     type Color = Rgba8;
 
     fn prepare(&mut self) {}
 
     fn generate(&mut self, span: &mut [Rgba8], x: i32, y: i32, len: u32) {
+        let [ri, gi, bi, ai] = order_index::<O>();
         let dx_dbl = self.base.filter_dx_dbl();
         let dy_dbl = self.base.filter_dy_dbl();
         self.base
@@ -73,10 +98,10 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaN
                 1,
             );
             *pixel = Rgba8::new(
-                fg_ptr[0] as u32,
-                fg_ptr[1] as u32,
-                fg_ptr[2] as u32,
-                fg_ptr[3] as u32,
+                fg_ptr[ri] as u32,
+                fg_ptr[gi] as u32,
+                fg_ptr[bi] as u32,
+                fg_ptr[ai] as u32,
             );
             self.base.interpolator_mut().next();
         }
@@ -93,16 +118,25 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaN
 /// Source must use an image accessor that handles boundary conditions.
 ///
 /// Port of C++ `span_image_filter_rgba_bilinear<Source, Interpolator>`.
-pub struct SpanImageFilterRgbaBilinear<'a, S: ImageSource, I> {
+pub struct SpanImageFilterRgbaBilinear<'a, S: ImageSource, I, O = OrderRgba> {
     base: SpanImageFilterBase<'a, I>,
     source: &'a mut S,
+    order: PhantomData<O>,
 }
 
 impl<'a, S: ImageSource, I> SpanImageFilterRgbaBilinear<'a, S, I> {
     pub fn new(source: &'a mut S, interpolator: &'a mut I) -> Self {
+        Self::new_ordered(source, interpolator)
+    }
+}
+
+impl<'a, S: ImageSource, I, O> SpanImageFilterRgbaBilinear<'a, S, I, O> {
+    /// Like `new`, for a source whose pixels are in channel order `O`.
+    pub fn new_ordered(source: &'a mut S, interpolator: &'a mut I) -> Self {
         Self {
             base: SpanImageFilterBase::new(interpolator, None),
             source,
+            order: PhantomData,
         }
     }
 
@@ -115,12 +149,15 @@ impl<'a, S: ImageSource, I> SpanImageFilterRgbaBilinear<'a, S, I> {
     }
 }
 
-impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaBilinear<'_, S, I> {
+impl<S: ImageSource, I: SpanInterpolator, O: RgbaOrder> SpanGenerator
+    for SpanImageFilterRgbaBilinear<'_, S, I, O>
+{
     type Color = Rgba8;
 
     fn prepare(&mut self) {}
 
     fn generate(&mut self, span: &mut [Rgba8], x: i32, y: i32, len: u32) {
+        let [ri, gi, bi, ai] = order_index::<O>();
         let dx_dbl = self.base.filter_dx_dbl();
         let dy_dbl = self.base.filter_dy_dbl();
         let dx_int = self.base.filter_dx_int() as i32;
@@ -151,34 +188,34 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaB
             // Top-left
             let p = self.source.span(x_lr, y_lr, 2);
             let weight = (subpix - x_frac) * (subpix - y_frac);
-            fg[0] += weight * p[0] as i32;
-            fg[1] += weight * p[1] as i32;
-            fg[2] += weight * p[2] as i32;
-            fg[3] += weight * p[3] as i32;
+            fg[0] += weight * p[ri] as i32;
+            fg[1] += weight * p[gi] as i32;
+            fg[2] += weight * p[bi] as i32;
+            fg[3] += weight * p[ai] as i32;
 
             // Top-right
             let p = self.source.next_x();
             let weight = x_frac * (subpix - y_frac);
-            fg[0] += weight * p[0] as i32;
-            fg[1] += weight * p[1] as i32;
-            fg[2] += weight * p[2] as i32;
-            fg[3] += weight * p[3] as i32;
+            fg[0] += weight * p[ri] as i32;
+            fg[1] += weight * p[gi] as i32;
+            fg[2] += weight * p[bi] as i32;
+            fg[3] += weight * p[ai] as i32;
 
             // Bottom-left
             let p = self.source.next_y();
             let weight = (subpix - x_frac) * y_frac;
-            fg[0] += weight * p[0] as i32;
-            fg[1] += weight * p[1] as i32;
-            fg[2] += weight * p[2] as i32;
-            fg[3] += weight * p[3] as i32;
+            fg[0] += weight * p[ri] as i32;
+            fg[1] += weight * p[gi] as i32;
+            fg[2] += weight * p[bi] as i32;
+            fg[3] += weight * p[ai] as i32;
 
             // Bottom-right
             let p = self.source.next_x();
             let weight = x_frac * y_frac;
-            fg[0] += weight * p[0] as i32;
-            fg[1] += weight * p[1] as i32;
-            fg[2] += weight * p[2] as i32;
-            fg[3] += weight * p[3] as i32;
+            fg[0] += weight * p[ri] as i32;
+            fg[1] += weight * p[gi] as i32;
+            fg[2] += weight * p[bi] as i32;
+            fg[3] += weight * p[ai] as i32;
 
             let shift = IMAGE_SUBPIXEL_SHIFT * 2;
             *pixel = Rgba8::new(
@@ -193,6 +230,178 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaB
     }
 }
 
+// ============================================================================
+// SpanImageFilterRgba128Nn / Bilinear — floating-point RGBA sources
+// ============================================================================
+
+/// Nearest-neighbor image filter for RGBA128 (f32) images.
+///
+/// Reads 16-byte pixels, so pair it with an accessor instantiated with
+/// `PIX_WIDTH = 16`. Values are passed through unclamped.
+///
+/// Port of C++ `span_image_filter_rgba_nn<Source, Interpolator>` as
+/// instantiated for `pixfmt_rgba128`.
+pub struct SpanImageFilterRgba128Nn<'a, S: ImageSource, I, O = OrderRgba> {
+    base: SpanImageFilterBase<'a, I>,
+    source: &'a mut S,
+    order: PhantomData<O>,
+}
+
+impl<'a, S: ImageSource, I> SpanImageFilterRgba128Nn<'a, S, I> {
+    pub fn new(source: &'a mut S, interpolator: &'a mut I) -> Self {
+        Self::new_ordered(source, interpolator)
+    }
+}
+
+impl<'a, S: ImageSource, I, O> SpanImageFilterRgba128Nn<'a, S, I, O> {
+    /// Like `new`, for a source whose pixels are in channel order `O`.
+    pub fn new_ordered(source: &'a mut S, interpolator: &'a mut I) -> Self {
+        Self {
+            base: SpanImageFilterBase::new(interpolator, None),
+            source,
+            order: PhantomData,
+        }
+    }
+
+    pub fn base(&self) -> &SpanImageFilterBase<'a, I> {
+        &self.base
+    }
+
+    pub fn base_mut(&mut self) -> &mut SpanImageFilterBase<'a, I> {
+        &mut self.base
+    }
+}
+
+impl<S: ImageSource, I: SpanInterpolator, O: RgbaOrder> SpanGenerator
+    for SpanImageFilterRgba128Nn<'_, S, I, O>
+{
+    type Color = Rgba32;
+
+    fn prepare(&mut self) {}
+
+    fn generate(&mut self, span: &mut [Rgba32], x: i32, y: i32, len: u32) {
+        let [ri, gi, bi, ai] = order_index::<O>();
+        let dx_dbl = self.base.filter_dx_dbl();
+        let dy_dbl = self.base.filter_dy_dbl();
+        self.base
+            .interpolator_mut()
+            .begin(x as f64 + dx_dbl, y as f64 + dy_dbl, len);
+
+        for pixel in span.iter_mut().take(len as usize) {
+            let mut x_hr = 0i32;
+            let mut y_hr = 0i32;
+            self.base.interpolator().coordinates(&mut x_hr, &mut y_hr);
+
+            let v = get_pix_f32(self.source.span(
+                x_hr >> IMAGE_SUBPIXEL_SHIFT,
+                y_hr >> IMAGE_SUBPIXEL_SHIFT,
+                1,
+            ));
+            *pixel = Rgba32::new(v[ri], v[gi], v[bi], v[ai]);
+            self.base.interpolator_mut().next();
+        }
+    }
+}
+
+/// Bilinear image filter for RGBA128 (f32) images.
+///
+/// Same sampling as [`SpanImageFilterRgbaBilinear`], with the weighted sum
+/// accumulated in `f64` instead of fixed point.
+///
+/// Port of C++ `span_image_filter_rgba_bilinear<Source, Interpolator>` as
+/// instantiated for `pixfmt_rgba128`.
+pub struct SpanImageFilterRgba128Bilinear<'a, S: ImageSource, I, O = OrderRgba> {
+    base: SpanImageFilterBase<'a, I>,
+    source: &'a mut S,
+    order: PhantomData<O>,
+}
+
+impl<'a, S: ImageSource, I> SpanImageFilterRgba128Bilinear<'a, S, I> {
+    pub fn new(source: &'a mut S, interpolator: &'a mut I) -> Self {
+        Self::new_ordered(source, interpolator)
+    }
+}
+
+impl<'a, S: ImageSource, I, O> SpanImageFilterRgba128Bilinear<'a, S, I, O> {
+    /// Like `new`, for a source whose pixels are in channel order `O`.
+    pub fn new_ordered(source: &'a mut S, interpolator: &'a mut I) -> Self {
+        Self {
+            base: SpanImageFilterBase::new(interpolator, None),
+            source,
+            order: PhantomData,
+        }
+    }
+
+    pub fn base(&self) -> &SpanImageFilterBase<'a, I> {
+        &self.base
+    }
+
+    pub fn base_mut(&mut self) -> &mut SpanImageFilterBase<'a, I> {
+        &mut self.base
+    }
+}
+
+impl<S: ImageSource, I: SpanInterpolator, O: RgbaOrder> SpanGenerator
+    for SpanImageFilterRgba128Bilinear<'_, S, I, O>
+{
+    type Color = Rgba32;
+
+    fn prepare(&mut self) {}
+
+    fn generate(&mut self, span: &mut [Rgba32], x: i32, y: i32, len: u32) {
+        let [ri, gi, bi, ai] = order_index::<O>();
+        let dx_dbl = self.base.filter_dx_dbl();
+        let dy_dbl = self.base.filter_dy_dbl();
+        let dx_int = self.base.filter_dx_int() as i32;
+        let dy_int = self.base.filter_dy_int() as i32;
+        self.base
+            .interpolator_mut()
+            .begin(x as f64 + dx_dbl, y as f64 + dy_dbl, len);
+
+        let subpix = IMAGE_SUBPIXEL_SCALE as i32;
+        let norm = 1.0 / (IMAGE_SUBPIXEL_SCALE * IMAGE_SUBPIXEL_SCALE) as f64;
+
+        for pixel in span.iter_mut().take(len as usize) {
+            let mut x_hr = 0i32;
+            let mut y_hr = 0i32;
+            self.base.interpolator().coordinates(&mut x_hr, &mut y_hr);
+
+            x_hr -= dx_int;
+            y_hr -= dy_int;
+
+            let x_lr = x_hr >> IMAGE_SUBPIXEL_SHIFT;
+            let y_lr = y_hr >> IMAGE_SUBPIXEL_SHIFT;
+            let x_frac = x_hr & IMAGE_SUBPIXEL_MASK as i32;
+            let y_frac = y_hr & IMAGE_SUBPIXEL_MASK as i32;
+
+            let mut fg = [0.0f64; 4];
+            let mut accumulate = |p: &[u8], weight: i32| {
+                let v = get_pix_f32(p);
+                for (acc, i) in fg.iter_mut().zip([ri, gi, bi, ai]) {
+                    *acc += weight as f64 * v[i] as f64;
+                }
+            };
+
+            accumulate(
+                self.source.span(x_lr, y_lr, 2),
+                (subpix - x_frac) * (subpix - y_frac),
+            );
+            accumulate(self.source.next_x(), x_frac * (subpix - y_frac));
+            accumulate(self.source.next_y(), (subpix - x_frac) * y_frac);
+            accumulate(self.source.next_x(), x_frac * y_frac);
+
+            *pixel = Rgba32::new(
+                (fg[0] * norm) as f32,
+                (fg[1] * norm) as f32,
+                (fg[2] * norm) as f32,
+                (fg[3] * norm) as f32,
+            );
+
+            self.base.interpolator_mut().next();
+        }
+    }
+}
+
 // ============================================================================
 // SpanImageFilterRgbaBilinearClip — bilinear with background color
 // ============================================================================
@@ -203,18 +412,27 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaB
 /// returns a background color for pixels that fall outside the image.
 ///
 /// Port of C++ `span_image_filter_rgba_bilinear_clip<Source, Interpolator>`.
-pub struct SpanImageFilterRgbaBilinearClip<'a, I> {
+pub struct SpanImageFilterRgbaBilinearClip<'a, I, O = OrderRgba> {
     base: SpanImageFilterBase<'a, I>,
     rbuf: &'a RowAccessor,
     back_color: Rgba8,
+    order: PhantomData<O>,
 }
 
 impl<'a, I> SpanImageFilterRgbaBilinearClip<'a, I> {
     pub fn new(rbuf: &'a RowAccessor, back_color: Rgba8, interpolator: &'a mut I) -> Self {
+        Self::new_ordered(rbuf, back_color, interpolator)
+    }
+}
+
+impl<'a, I, O> SpanImageFilterRgbaBilinearClip<'a, I, O> {
+    /// Like `new`, for a buffer whose pixels are in channel order `O`.
+    pub fn new_ordered(rbuf: &'a RowAccessor, back_color: Rgba8, interpolator: &'a mut I) -> Self {
         Self {
             base: SpanImageFilterBase::new(interpolator, None),
             rbuf,
             back_color,
+            order: PhantomData,
         }
     }
 
@@ -235,12 +453,15 @@ impl<'a, I> SpanImageFilterRgbaBilinearClip<'a, I> {
     }
 }
 
-impl<I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaBilinearClip<'_, I> {
+impl<I: SpanInterpolator, O: RgbaOrder> SpanGenerator
+    for SpanImageFilterRgbaBilinearClip<'_, I, O>
+{
     type Color = Rgba8;
 
     fn prepare(&mut self) {}
 
     fn generate(&mut self, span: &mut [Rgba8], x: i32, y: i32, len: u32) {
+        let [ri, gi, bi, ai] = order_index::<O>();
         let dx_dbl = self.base.filter_dx_dbl();
         let dy_dbl = self.base.filter_dy_dbl();
         let dx_int = self.base.filter_dx_int() as i32;
@@ -281,29 +502,29 @@ impl<I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaBilinearClip<'_,
                 let off = (x_lr as usize) << 2;
 
                 let weight = (subpix - x_frac) * (subpix - y_frac);
-                fg[0] += weight * row[off] as i32;
-                fg[1] += weight * row[off + 1] as i32;
-                fg[2] += weight * row[off + 2] as i32;
-                fg[3] += weight * row[off + 3] as i32;
+                fg[0] += weight * row[off + ri] as i32;
+                fg[1] += weight * row[off + gi] as i32;
+                fg[2] += weight * row[off + bi] as i32;
+                fg[3] += weight * row[off + ai] as i32;
 
                 let weight = x_frac * (subpix - y_frac);
-                fg[0] += weight * row[off + 4] as i32;
-                fg[1] += weight * row[off + 5] as i32;
-                fg[2] += weight * row[off + 6] as i32;
-                fg[3] += weight * row[off + 7] as i32;
+                fg[0] += weight * row[off + 4 + ri] as i32;
+                fg[1] += weight * row[off + 4 + gi] as i32;
+                fg[2] += weight * row[off + 4 + bi] as i32;
+                fg[3] += weight * row[off + 4 + ai] as i32;
 
                 let row2 = self.rbuf.row_slice((y_lr + 1) as u32);
                 let weight = (subpix - x_frac) * y_frac;
-                fg[0] += weight * row2[off] as i32;
-                fg[1] += weight * row2[off + 1] as i32;
-                fg[2] += weight * row2[off + 2] as i32;
-                fg[3] += weight * row2[off + 3] as i32;
+                fg[0] += weight * row2[off + ri] as i32;
+                fg[1] += weight * row2[off + gi] as i32;
+                fg[2] += weight * row2[off + bi] as i32;
+                fg[3] += weight * row2[off + ai] as i32;
 
                 let weight = x_frac * y_frac;
-                fg[0] += weight * row2[off + 4] as i32;
-                fg[1] += weight * row2[off + 5] as i32;
-                fg[2] += weight * row2[off + 6] as i32;
-                fg[3] += weight * row2[off + 7] as i32;
+                fg[0] += weight * row2[off + 4 + ri] as i32;
+                fg[1] += weight * row2[off + 4 + gi] as i32;
+                fg[2] += weight * row2[off + 4 + bi] as i32;
+                fg[3] += weight * row2[off + 4 + ai] as i32;
 
                 let shift = IMAGE_SUBPIXEL_SHIFT * 2;
                 fg[0] >>= shift;
@@ -323,10 +544,10 @@ impl<I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaBilinearClip<'_,
                 if x_lr >= 0 && y_lr >= 0 && x_lr <= maxx && y_lr <= maxy {
                     let row = self.rbuf.row_slice(y_lr as u32);
                     let off = (x_lr as usize) << 2;
-                    fg[0] += weight * row[off] as i32;
-                    fg[1] += weight * row[off + 1] as i32;
-                    fg[2] += weight * row[off + 2] as i32;
-                    fg[3] += weight * row[off + 3] as i32;
+                    fg[0] += weight * row[off + ri] as i32;
+                    fg[1] += weight * row[off + gi] as i32;
+                    fg[2] += weight * row[off + bi] as i32;
+                    fg[3] += weight * row[off + ai] as i32;
                 } else {
                     fg[0] += back_r * weight;
                     fg[1] += back_g * weight;
@@ -339,10 +560,10 @@ impl<I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaBilinearClip<'_,
                 if x_lr >= 0 && y_lr >= 0 && x_lr <= maxx && y_lr <= maxy {
                     let row = self.rbuf.row_slice(y_lr as u32);
                     let off = (x_lr as usize) << 2;
-                    fg[0] += weight * row[off] as i32;
-                    fg[1] += weight * row[off + 1] as i32;
-                    fg[2] += weight * row[off + 2] as i32;
-                    fg[3] += weight * row[off + 3] as i32;
+                    fg[0] += weight * row[off + ri] as i32;
+                    fg[1] += weight * row[off + gi] as i32;
+                    fg[2] += weight * row[off + bi] as i32;
+                    fg[3] += weight * row[off + ai] as i32;
                 } else {
                     fg[0] += back_r * weight;
                     fg[1] += back_g * weight;
@@ -356,10 +577,10 @@ impl<I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaBilinearClip<'_,
                 if x_lr >= 0 && y_lr >= 0 && x_lr <= maxx && y_lr <= maxy {
                     let row = self.rbuf.row_slice(y_lr as u32);
                     let off = (x_lr as usize) << 2;
-                    fg[0] += weight * row[off] as i32;
-                    fg[1] += weight * row[off + 1] as i32;
-                    fg[2] += weight * row[off + 2] as i32;
-                    fg[3] += weight * row[off + 3] as i32;
+                    fg[0] += weight * row[off + ri] as i32;
+                    fg[1] += weight * row[off + gi] as i32;
+                    fg[2] += weight * row[off + bi] as i32;
+                    fg[3] += weight * row[off + ai] as i32;
                 } else {
                     fg[0] += back_r * weight;
                     fg[1] += back_g * weight;
@@ -372,10 +593,10 @@ impl<I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaBilinearClip<'_,
                 if x_lr >= 0 && y_lr >= 0 && x_lr <= maxx && y_lr <= maxy {
                     let row = self.rbuf.row_slice(y_lr as u32);
                     let off = (x_lr as usize) << 2;
-                    fg[0] += weight * row[off] as i32;
-                    fg[1] += weight * row[off + 1] as i32;
-                    fg[2] += weight * row[off + 2] as i32;
-                    fg[3] += weight * row[off + 3] as i32;
+                    fg[0] += weight * row[off + ri] as i32;
+                    fg[1] += weight * row[off + gi] as i32;
+                    fg[2] += weight * row[off + bi] as i32;
+                    fg[3] += weight * row[off + ai] as i32;
                 } else {
                     fg[0] += back_r * weight;
                     fg[1] += back_g * weight;
@@ -406,16 +627,29 @@ impl<I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaBilinearClip<'_,
 /// separable 2x2 filter kernel.
 ///
 /// Port of C++ `span_image_filter_rgba_2x2<Source, Interpolator>`.
-pub struct SpanImageFilterRgba2x2<'a, S: ImageSource, I> {
+pub struct SpanImageFilterRgba2x2<'a, S: ImageSource, I, O = OrderRgba> {
     base: SpanImageFilterBase<'a, I>,
     source: &'a mut S,
+    order: PhantomData<O>,
 }
 
 impl<'a, S: ImageSource, I> SpanImageFilterRgba2x2<'a, S, I> {
     pub fn new(source: &'a mut S, interpolator: &'a mut I, filter: &'a ImageFilterLut) -> Self {
+        Self::new_ordered(source, interpolator, filter)
+    }
+}
+
+impl<'a, S: ImageSource, I, O> SpanImageFilterRgba2x2<'a, S, I, O> {
+    /// Like `new`, for a source whose pixels are in channel order `O`.
+    pub fn new_ordered(
+        source: &'a mut S,
+        interpolator: &'a mut I,
+        filter: &'a ImageFilterLut,
+    ) -> Self {
         Self {
             base: SpanImageFilterBase::new(interpolator, Some(filter)),
             source,
+            order: PhantomData,
         }
     }
 
@@ -428,12 +662,15 @@ impl<'a, S: ImageSource, I> SpanImageFilterRgba2x2<'a, S, I> {
     }
 }
 
-impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgba2x2<'_, S, I> {
+impl<S: ImageSource, I: SpanInterpolator, O: RgbaOrder> SpanGenerator
+    for SpanImageFilterRgba2x2<'_, S, I, O>
+{
     type Color = Rgba8;
 
     fn prepare(&mut self) {}
 
     fn generate(&mut self, span: &mut [Rgba8], x: i32, y: i32, len: u32) {
+        let [ri, gi, bi, ai] = order_index::<O>();
         let dx_dbl = self.base.filter_dx_dbl();
         let dy_dbl = self.base.filter_dy_dbl();
         let dx_int = self.base.filter_dx_int() as i32;
@@ -472,10 +709,10 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgba2
                 * weight_array[wa_offset + y_frac + subpix] as i32)
                 + IMAGE_FILTER_SCALE / 2)
                 >> IMAGE_FILTER_SHIFT;
-            fg[0] += weight * p[0] as i32;
-            fg[1] += weight * p[1] as i32;
-            fg[2] += weight * p[2] as i32;
-            fg[3] += weight * p[3] as i32;
+            fg[0] += weight * p[ri] as i32;
+            fg[1] += weight * p[gi] as i32;
+            fg[2] += weight * p[bi] as i32;
+            fg[3] += weight * p[ai] as i32;
 
             // Top-right
             let p = self.source.next_x();
@@ -483,10 +720,10 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgba2
                 * weight_array[wa_offset + y_frac + subpix] as i32)
                 + IMAGE_FILTER_SCALE / 2)
                 >> IMAGE_FILTER_SHIFT;
-            fg[0] += weight * p[0] as i32;
-            fg[1] += weight * p[1] as i32;
-            fg[2] += weight * p[2] as i32;
-            fg[3] += weight * p[3] as i32;
+            fg[0] += weight * p[ri] as i32;
+            fg[1] += weight * p[gi] as i32;
+            fg[2] += weight * p[bi] as i32;
+            fg[3] += weight * p[ai] as i32;
 
             // Bottom-left
             let p = self.source.next_y();
@@ -494,10 +731,10 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgba2
                 * weight_array[wa_offset + y_frac] as i32)
                 + IMAGE_FILTER_SCALE / 2)
                 >> IMAGE_FILTER_SHIFT;
-            fg[0] += weight * p[0] as i32;
-            fg[1] += weight * p[1] as i32;
-            fg[2] += weight * p[2] as i32;
-            fg[3] += weight * p[3] as i32;
+            fg[0] += weight * p[ri] as i32;
+            fg[1] += weight * p[gi] as i32;
+            fg[2] += weight * p[bi] as i32;
+            fg[3] += weight * p[ai] as i32;
 
             // Bottom-right
             let p = self.source.next_x();
@@ -505,10 +742,10 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgba2
                 * weight_array[wa_offset + y_frac] as i32)
                 + IMAGE_FILTER_SCALE / 2)
                 >> IMAGE_FILTER_SHIFT;
-            fg[0] += weight * p[0] as i32;
-            fg[1] += weight * p[1] as i32;
-            fg[2] += weight * p[2] as i32;
-            fg[3] += weight * p[3] as i32;
+            fg[0] += weight * p[ri] as i32;
+            fg[1] += weight * p[gi] as i32;
+            fg[2] += weight * p[bi] as i32;
+            fg[3] += weight * p[ai] as i32;
 
             fg[0] >>= IMAGE_FILTER_SHIFT;
             fg[1] >>= IMAGE_FILTER_SHIFT;
@@ -545,16 +782,29 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgba2
 /// weighted pixel values. Handles negative weights correctly.
 ///
 /// Port of C++ `span_image_filter_rgba<Source, Interpolator>`.
-pub struct SpanImageFilterRgbaGen<'a, S: ImageSource, I> {
+pub struct SpanImageFilterRgbaGen<'a, S: ImageSource, I, O = OrderRgba> {
     base: SpanImageFilterBase<'a, I>,
     source: &'a mut S,
+    order: PhantomData<O>,
 }
 
 impl<'a, S: ImageSource, I> SpanImageFilterRgbaGen<'a, S, I> {
     pub fn new(source: &'a mut S, interpolator: &'a mut I, filter: &'a ImageFilterLut) -> Self {
+        Self::new_ordered(source, interpolator, filter)
+    }
+}
+
+impl<'a, S: ImageSource, I, O> SpanImageFilterRgbaGen<'a, S, I, O> {
+    /// Like `new`, for a source whose pixels are in channel order `O`.
+    pub fn new_ordered(
+        source: &'a mut S,
+        interpolator: &'a mut I,
+        filter: &'a ImageFilterLut,
+    ) -> Self {
         Self {
             base: SpanImageFilterBase::new(interpolator, Some(filter)),
             source,
+            order: PhantomData,
         }
     }
 
@@ -567,12 +817,15 @@ impl<'a, S: ImageSource, I> SpanImageFilterRgbaGen<'a, S, I> {
     }
 }
 
-impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaGen<'_, S, I> {
+impl<S: ImageSource, I: SpanInterpolator, O: RgbaOrder> SpanGenerator
+    for SpanImageFilterRgbaGen<'_, S, I, O>
+{
     type Color = Rgba8;
 
     fn prepare(&mut self) {}
 
     fn generate(&mut self, span: &mut [Rgba8], x: i32, y: i32, len: u32) {
+        let [ri, gi, bi, ai] = order_index::<O>();
         let dx_dbl = self.base.filter_dx_dbl();
         let dy_dbl = self.base.filter_dy_dbl();
         let dx_int = self.base.filter_dx_int() as i32;
@@ -617,10 +870,10 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaG
                         + IMAGE_FILTER_SCALE / 2)
                         >> IMAGE_FILTER_SHIFT;
 
-                    fg[0] += weight * fg_ptr[0] as i32;
-                    fg[1] += weight * fg_ptr[1] as i32;
-                    fg[2] += weight * fg_ptr[2] as i32;
-                    fg[3] += weight * fg_ptr[3] as i32;
+                    fg[0] += weight * fg_ptr[ri] as i32;
+                    fg[1] += weight * fg_ptr[gi] as i32;
+                    fg[2] += weight * fg_ptr[bi] as i32;
+                    fg[3] += weight * fg_ptr[ai] as i32;
 
                     x_count -= 1;
                     if x_count == 0 {
@@ -665,9 +918,10 @@ impl<S: ImageSource, I: SpanInterpolator> SpanGenerator for SpanImageFilterRgbaG
 /// to walk a variable-size filter kernel that adapts to the magnification.
 ///
 /// Port of C++ `span_image_resample_rgba_affine<Source>`.
-pub struct SpanImageResampleRgbaAffine<'a, S: ImageSource> {
+pub struct SpanImageResampleRgbaAffine<'a, S: ImageSource, O = OrderRgba> {
     base: SpanImageResampleAffine<'a>,
     source: &'a mut S,
+    order: PhantomData<O>,
 }
 
 impl<'a, S: ImageSource> SpanImageResampleRgbaAffine<'a, S> {
@@ -675,10 +929,22 @@ impl<'a, S: ImageSource> SpanImageResampleRgbaAffine<'a, S> {
         source: &'a mut S,
         interpolator: &'a mut SpanInterpolatorLinear<TransAffine>,
         filter: &'a ImageFilterLut,
+    ) -> Self {
+        Self::new_ordered(source, interpolator, filter)
+    }
+}
+
+impl<'a, S: ImageSource, O> SpanImageResampleRgbaAffine<'a, S, O> {
+    /// Like `new`, for a source whose pixels are in channel order `O`.
+    pub fn new_ordered(
+        source: &'a mut S,
+        interpolator: &'a mut SpanInterpolatorLinear<TransAffine>,
+        filter: &'a ImageFilterLut,
     ) -> Self {
         Self {
             base: SpanImageResampleAffine::new(interpolator, filter),
             source,
+            order: PhantomData,
         }
     }
 
@@ -691,7 +957,7 @@ impl<'a, S: ImageSource> SpanImageResampleRgbaAffine<'a, S> {
     }
 }
 
-impl<S: ImageSource> SpanGenerator for SpanImageResampleRgbaAffine<'_, S> {
+impl<S: ImageSource, O: RgbaOrder> SpanGenerator for SpanImageResampleRgbaAffine<'_, S, O> {
     type Color = Rgba8;
 
     fn prepare(&mut self) {
@@ -699,6 +965,7 @@ impl<S: ImageSource> SpanGenerator for SpanImageResampleRgbaAffine<'_, S> {
     }
 
     fn generate(&mut self, span: &mut [Rgba8], x: i32, y: i32, len: u32) {
+        let [ri, gi, bi, ai] = order_index::<O>();
         let filter = self.base.base().filter().unwrap();
         let diameter = filter.diameter() as i32;
         let filter_scale = diameter << IMAGE_SUBPIXEL_SHIFT;
@@ -755,10 +1022,10 @@ impl<S: ImageSource> SpanGenerator for SpanImageResampleRgbaAffine<'_, S> {
                         + IMAGE_FILTER_SCALE / 2)
                         >> IMAGE_FILTER_SHIFT;
 
-                    fg[0] += fg_ptr[0] as i32 * weight;
-                    fg[1] += fg_ptr[1] as i32 * weight;
-                    fg[2] += fg_ptr[2] as i32 * weight;
-                    fg[3] += fg_ptr[3] as i32 * weight;
+                    fg[0] += fg_ptr[ri] as i32 * weight;
+                    fg[1] += fg_ptr[gi] as i32 * weight;
+                    fg[2] += fg_ptr[bi] as i32 * weight;
+                    fg[3] += fg_ptr[ai] as i32 * weight;
                     total_weight += weight;
 
                     x_wa += rx_inv;
@@ -800,6 +1067,7 @@ impl<S: ImageSource> SpanGenerator for SpanImageResampleRgbaAffine<'_, S> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::color::OrderBgra;
     use crate::image_accessors::{ImageAccessorClip, ImageAccessorClone};
     use crate::image_filters::ImageFilterBilinear;
     use crate::rendering_buffer::RowAccessor;
@@ -836,6 +1104,23 @@ mod tests {
         assert_eq!(span[0].a, 255);
     }
 
+    #[test]
+    fn test_nn_bgra_source() {
+        let mut data = Vec::new();
+        let rbuf = make_rgba_buffer(2, 2, &mut data);
+        // Stored as B, G, R, A
+        set_pixel(&mut data, 2, 1, 1, [200, 150, 100, 255]);
+
+        let mut acc = ImageAccessorClone::<4>::new(&rbuf);
+        let mut interp = SpanInterpolatorLinear::new(TransAffine::new());
+        let mut filter =
+            SpanImageFilterRgbaNn::<_, _, OrderBgra>::new_ordered(&mut acc, &mut interp);
+
+        let mut span = vec![Rgba8::default(); 1];
+        filter.generate(&mut span, 1, 1, 1);
+        assert_eq!(span[0], Rgba8::new(100, 150, 200, 255));
+    }
+
     #[test]
     fn test_nn_multiple_pixels() {
         let mut data = Vec::new();
@@ -923,6 +1208,34 @@ mod tests {
 
     // -- Bilinear Clip tests --
 
+    #[test]
+    fn test_rgba128_bilinear_keeps_hdr_values() {
+        let (w, h) = (2u32, 2u32);
+        let stride = (w * 16) as usize;
+        let mut data = vec![0u8; stride * h as usize];
+        for (i, v) in [0.0f32, 8.0, 0.0, 8.0].iter().enumerate() {
+            let off = i * 16;
+            data[off..off + 4].copy_from_slice(&v.to_ne_bytes());
+            data[off + 12..off + 16].copy_from_slice(&1.0f32.to_ne_bytes());
+        }
+        let rbuf = unsafe { RowAccessor::new_with_buf(data.as_mut_ptr(), w, h, stride as i32) };
+
+        let mut acc = ImageAccessorClone::<16>::new(&rbuf);
+        let mut interp = SpanInterpolatorLinear::new(TransAffine::new_translation(0.5, 0.0));
+        let mut filter = SpanImageFilterRgba128Bilinear::new(&mut acc, &mut interp);
+
+        let mut span = vec![Rgba32::default(); 1];
+        filter.generate(&mut span, 0, 0, 1);
+        assert_eq!(span[0].r, 4.0);
+        assert_eq!(span[0].a, 1.0);
+
+        let mut acc = ImageAccessorClone::<16>::new(&rbuf);
+        let mut interp = SpanInterpolatorLinear::new(TransAffine::new());
+        let mut filter = SpanImageFilterRgba128Nn::new(&mut acc, &mut interp);
+        filter.generate(&mut span, 1, 0, 1);
+        assert_eq!(span[0].r, 8.0);
+    }
+
     #[test]
     fn test_bilinear_clip_in_bounds() {
         let mut data = Vec::new();
@@ -962,6 +1275,31 @@ mod tests {
         assert_eq!(span[0].a, 88);
     }
 
+    #[test]
+    fn test_bilinear_clip_bgra_source() {
+        let mut data = Vec::new();
+        let rbuf = make_rgba_buffer(2, 2, &mut data);
+        // Stored as B, G, R, A
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            set_pixel(&mut data, 2, x, y, [200, 150, 100, 255]);
+        }
+
+        let mut interp = SpanInterpolatorLinear::new(TransAffine::new());
+        let bg = Rgba8::new(10, 20, 30, 40);
+        let mut filter =
+            SpanImageFilterRgbaBilinearClip::<_, OrderBgra>::new_ordered(&rbuf, bg, &mut interp);
+        let mut span = vec![Rgba8::default(); 1];
+        filter.generate(&mut span, 0, 0, 1);
+        assert_eq!(span[0], Rgba8::new(100, 150, 200, 255));
+
+        // Half a pixel off the left edge: an even mix with the background.
+        let mut interp = SpanInterpolatorLinear::new(TransAffine::new_translation(-0.5, 0.0));
+        let mut filter =
+            SpanImageFilterRgbaBilinearClip::<_, OrderBgra>::new_ordered(&rbuf, bg, &mut interp);
+        filter.generate(&mut span, 0, 0, 1);
+        assert_eq!(span[0], Rgba8::new(55, 85, 115, 147));
+    }
+
     #[test]
     fn test_bilinear_clip_background_setter() {
         let mut data = Vec::new();
@@ -4,25 +4,36 @@
 //! Generates pixel spans by reading from a tiled source image with
 //! coordinate offsets. Used for repeating pattern fills.
 
-use crate::color::Rgba8;
+use crate::color::{OrderRgba, Rgba8, RgbaOrder};
 use crate::image_accessors::ImageSource;
 use crate::renderer_scanline::SpanGenerator;
+use std::marker::PhantomData;
 
 /// RGBA span pattern generator — fills spans from a tiled source image.
 ///
 /// Port of C++ `span_pattern_rgba<Source>`.
 /// Reads pixels from the attached `ImageSource`, applying x/y offsets
-/// for pattern positioning.
-pub struct SpanPatternRgba<Src> {
+/// for pattern positioning. `O` is the channel order of the source's
+/// pixels.
+pub struct SpanPatternRgba<Src, O = OrderRgba> {
     src: Src,
+    order: PhantomData<O>,
     offset_x: u32,
     offset_y: u32,
 }
 
 impl<Src: ImageSource> SpanPatternRgba<Src> {
     pub fn new(src: Src, offset_x: u32, offset_y: u32) -> Self {
+        Self::new_ordered(src, offset_x, offset_y)
+    }
+}
+
+impl<Src: ImageSource, O> SpanPatternRgba<Src, O> {
+    /// Like `new`, for a source whose pixels are in channel order `O`.
+    pub fn new_ordered(src: Src, offset_x: u32, offset_y: u32) -> Self {
         Self {
             src,
+            order: PhantomData,
             offset_x,
             offset_y,
         }
@@ -53,7 +64,7 @@ impl<Src: ImageSource> SpanPatternRgba<Src> {
     }
 }
 
-impl<Src: ImageSource> SpanGenerator for SpanPatternRgba<Src> {
+impl<Src: ImageSource, O: RgbaOrder> SpanGenerator for SpanPatternRgba<Src, O> {
     type Color = Rgba8;
 
     fn prepare(&mut self) {}
@@ -62,12 +73,14 @@ impl<Src: ImageSource> SpanGenerator for SpanPatternRgba<Src> {
         let sx = x + self.offset_x as i32;
         let sy = y + self.offset_y as i32;
 
+        let (r, g, b, a) = (O::R, O::G, O::B, O::A);
+
         let p = self.src.span(sx, sy, len);
-        span[0] = Rgba8::new(p[0] as u32, p[1] as u32, p[2] as u32, p[3] as u32);
+        span[0] = Rgba8::new(p[r] as u32, p[g] as u32, p[b] as u32, p[a] as u32);
 
         for i in 1..len as usize {
             let p = self.src.next_x();
-            span[i] = Rgba8::new(p[0] as u32, p[1] as u32, p[2] as u32, p[3] as u32);
+            span[i] = Rgba8::new(p[r] as u32, p[g] as u32, p[b] as u32, p[a] as u32);
         }
     }
 }
@@ -75,6 +88,10 @@ impl<Src: ImageSource> SpanGenerator for SpanPatternRgba<Src> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::color::OrderBgra;
+    use crate::image_accessors::ImageAccessorWrap;
+    use crate::image_accessors::WrapModeRepeat;
+    use crate::rendering_buffer::RowAccessor;
 
     /// A simple test image source that returns a fixed color.
     struct ConstantSource {
@@ -128,4 +145,17 @@ mod tests {
         assert_eq!(pattern.offset_x(), 5);
         assert_eq!(pattern.offset_y(), 15);
     }
+
+    #[test]
+    fn test_bgra_source() {
+        // One BGRA pixel holding opaque orange
+        let mut data = vec![0x00u8, 0x80, 0xFF, 0xFF];
+        let rbuf = unsafe { RowAccessor::new_with_buf(data.as_mut_ptr(), 1, 1, 4) };
+        let src = ImageAccessorWrap::<4, WrapModeRepeat, WrapModeRepeat>::new(&rbuf);
+        let mut pattern = SpanPatternRgba::<_, OrderBgra>::new_ordered(src, 0, 0);
+
+        let mut span = vec![Rgba8::default(); 3];
+        pattern.generate(&mut span, 7, 3, 3);
+        assert!(span.iter().all(|c| *c == Rgba8::new(255, 128, 0, 255)));
+    }
 }
@@ -2,6 +2,10 @@
 //!
 //! Port of `agg_vcgen_dash.h` / `agg_vcgen_dash.cpp` — generates
 //! dashed lines from a continuous center-line path.
+//!
+//! Unlike C++ AGG, non-finite source vertices and invalid dash lengths are
+//! ignored, and an all-zero pattern produces no output instead of looping
+//! forever.
 
 use crate::array::{shorten_path, VertexDist, VertexSequence};
 use crate::basics::{
@@ -72,8 +76,11 @@ impl VcgenDash {
         self.curr_dash = 0;
     }
 
+    /// Append a dash/gap pair. Pairs with a negative or non-finite length
+    /// are ignored.
     pub fn add_dash(&mut self, dash_len: f64, gap_len: f64) {
-        if self.num_dashes < MAX_DASHES {
+        let valid = |len: f64| len >= 0.0 && len.is_finite();
+        if self.num_dashes < MAX_DASHES && valid(dash_len) && valid(gap_len) {
             self.total_dash_len += dash_len + gap_len;
             self.dashes[self.num_dashes] = dash_len;
             self.num_dashes += 1;
@@ -83,6 +90,9 @@ impl VcgenDash {
     }
 
     pub fn dash_start(&mut self, ds: f64) {
+        if !ds.is_finite() {
+            return;
+        }
         self.dash_start = ds;
         self.calc_dash_start(ds.abs());
     }
@@ -90,6 +100,12 @@ impl VcgenDash {
     fn calc_dash_start(&mut self, mut ds: f64) {
         self.curr_dash = 0;
         self.curr_dash_start = 0.0;
+        if self.total_dash_len <= 0.0 {
+            return;
+        }
+        // Skip whole pattern repeats up front; a large start would
+        // otherwise step through them one dash at a time.
+        ds %= self.total_dash_len;
         while ds > 0.0 {
             if ds > self.dashes[self.curr_dash] {
                 ds -= self.dashes[self.curr_dash];
@@ -120,8 +136,13 @@ impl VcgenDash {
         self.closed = 0;
     }
 
+    /// Add a source vertex. Vertices with a non-finite coordinate are
+    /// skipped, as if absent from the path.
     pub fn add_vertex(&mut self, x: f64, y: f64, cmd: u32) {
         self.status = Status::Initial;
+        if is_vertex(cmd) && !(x.is_finite() && y.is_finite()) {
+            return;
+        }
         if is_move_to(cmd) {
             self.src_vertices.modify_last(VertexDist::new(x, y));
         } else if is_vertex(cmd) {
@@ -156,7 +177,11 @@ impl VcgenDash {
                     // fall through to Ready
                 }
                 Status::Ready => {
-                    if self.num_dashes < 2 || self.src_vertices.size() < 2 {
+                    // An all-zero pattern would never advance along the path.
+                    if self.num_dashes < 2
+                        || self.total_dash_len <= 0.0
+                        || self.src_vertices.size() < 2
+                    {
                         cmd = PATH_CMD_STOP;
                         continue; // re-check while condition (is_stop → break)
                     }
@@ -452,4 +477,40 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_invalid_input_ignored() {
+        let mut gen = VcgenDash::new();
+        gen.add_dash(10.0, 5.0);
+        gen.add_dash(f64::NAN, 5.0);
+        gen.add_dash(-1.0, 5.0);
+        gen.dash_start(f64::INFINITY);
+        gen.add_vertex(0.0, 0.0, PATH_CMD_MOVE_TO);
+        gen.add_vertex(f64::NAN, 50.0, PATH_CMD_LINE_TO);
+        gen.add_vertex(100.0, 0.0, PATH_CMD_LINE_TO);
+        let verts = collect_gen_vertices(&mut gen);
+
+        let mut clean = VcgenDash::new();
+        clean.add_dash(10.0, 5.0);
+        clean.add_vertex(0.0, 0.0, PATH_CMD_MOVE_TO);
+        clean.add_vertex(100.0, 0.0, PATH_CMD_LINE_TO);
+        assert_eq!(verts, collect_gen_vertices(&mut clean));
+    }
+
+    #[test]
+    fn test_zero_pattern_and_huge_start_terminate() {
+        let mut gen = VcgenDash::new();
+        gen.add_dash(0.0, 0.0);
+        gen.dash_start(5.0);
+        gen.add_vertex(0.0, 0.0, PATH_CMD_MOVE_TO);
+        gen.add_vertex(100.0, 0.0, PATH_CMD_LINE_TO);
+        assert!(collect_gen_vertices(&mut gen).is_empty());
+
+        let mut gen = VcgenDash::new();
+        gen.add_dash(20.0, 10.0);
+        gen.dash_start(1e300);
+        gen.add_vertex(0.0, 0.0, PATH_CMD_MOVE_TO);
+        gen.add_vertex(100.0, 0.0, PATH_CMD_LINE_TO);
+        assert!(!collect_gen_vertices(&mut gen).is_empty());
+    }
 }
@@ -2,6 +2,8 @@
 //!
 //! Port of `agg_vcgen_stroke.h` / `agg_vcgen_stroke.cpp` — generates
 //! a stroked outline from a center-line path using `MathStroke`.
+//!
+//! Source vertices with a non-finite coordinate are skipped.
 
 use crate::array::{shorten_path, VertexDist, VertexSequence};
 use crate::basics::{
@@ -130,8 +132,13 @@ impl VcgenStroke {
         self.status = Status::Initial;
     }
 
+    /// Add a source vertex. Vertices with a non-finite coordinate are
+    /// skipped, as if absent from the path.
     pub fn add_vertex(&mut self, x: f64, y: f64, cmd: u32) {
         self.status = Status::Initial;
+        if is_vertex(cmd) && !(x.is_finite() && y.is_finite()) {
+            return;
+        }
         if is_move_to(cmd) {
             self.src_vertices.modify_last(VertexDist::new(x, y));
         } else if is_vertex(cmd) {
@@ -482,4 +489,21 @@ mod tests {
         assert!(max_y >= 4.5, "Max y={} should be >= 4.5", max_y);
         assert!(min_y <= -4.5, "Min y={} should be <= -4.5", min_y);
     }
+
+    #[test]
+    fn test_non_finite_vertices_skipped() {
+        let mut gen = VcgenStroke::new();
+        gen.set_width(10.0);
+        gen.add_vertex(0.0, 0.0, PATH_CMD_MOVE_TO);
+        gen.add_vertex(f64::INFINITY, 3.0, PATH_CMD_LINE_TO);
+        gen.add_vertex(50.0, f64::NAN, PATH_CMD_LINE_TO);
+        gen.add_vertex(100.0, 0.0, PATH_CMD_LINE_TO);
+        let verts = collect_gen_vertices(&mut gen);
+
+        let mut clean = VcgenStroke::new();
+        clean.set_width(10.0);
+        clean.add_vertex(0.0, 0.0, PATH_CMD_MOVE_TO);
+        clean.add_vertex(100.0, 0.0, PATH_CMD_LINE_TO);
+        assert_eq!(verts, collect_gen_vertices(&mut clean));
+    }
 }
@@ -0,0 +1,225 @@
+//! Vertex processor generator: polygon clipper.
+//!
+//! Port of `agg_vpgen_clip_polygon.h` + `agg_vpgen_clip_polygon.cpp`.
+//! Clips closed polygons to a rectangle with Liang-Barsky, keeping the
+//! parts of the outline that run along the clip box so the result is still
+//! a closed polygon.
+
+use crate::basics::{RectD, PATH_CMD_LINE_TO, PATH_CMD_MOVE_TO, PATH_CMD_STOP};
+use crate::clip_liang_barsky::{clip_liang_barsky_f64, clipping_flags};
+use crate::conv_adaptor_vpgen::VpgenProcessor;
+
+/// Clips polygons to a rectangular clip box.
+///
+/// Used by `ConvAdaptorVpgen` / `ConvClipPolygon`. Every contour is closed
+/// automatically; vertices outside the box are moved onto its boundary.
+pub struct VpgenClipPolygon {
+    clip_box: RectD,
+    x1: f64,
+    y1: f64,
+    clip_flags: u32,
+    x: [f64; 4],
+    y: [f64; 4],
+    num_vertices: usize,
+    vertex: usize,
+    cmd: u32,
+}
+
+impl VpgenClipPolygon {
+    pub fn new() -> Self {
+        Self {
+            clip_box: RectD::new(0.0, 0.0, 1.0, 1.0),
+            x1: 0.0,
+            y1: 0.0,
+            clip_flags: 0,
+            x: [0.0; 4],
+            y: [0.0; 4],
+            num_vertices: 0,
+            vertex: 0,
+            cmd: PATH_CMD_MOVE_TO,
+        }
+    }
+
+    pub fn clip_box(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        self.clip_box = RectD::new(x1, y1, x2, y2);
+        self.clip_box.normalize();
+    }
+
+    pub fn x1(&self) -> f64 {
+        self.clip_box.x1
+    }
+
+    pub fn y1(&self) -> f64 {
+        self.clip_box.y1
+    }
+
+    pub fn x2(&self) -> f64 {
+        self.clip_box.x2
+    }
+
+    pub fn y2(&self) -> f64 {
+        self.clip_box.y2
+    }
+
+    pub fn auto_close() -> bool {
+        true
+    }
+
+    pub fn auto_unclose() -> bool {
+        false
+    }
+
+    pub fn reset(&mut self) {
+        self.vertex = 0;
+        self.num_vertices = 0;
+    }
+
+    pub fn move_to(&mut self, x: f64, y: f64) {
+        self.vertex = 0;
+        self.num_vertices = 0;
+        self.clip_flags = clipping_flags(x, y, &self.clip_box);
+        if self.clip_flags == 0 {
+            self.x[0] = x;
+            self.y[0] = y;
+            self.num_vertices = 1;
+        }
+        self.x1 = x;
+        self.y1 = y;
+        self.cmd = PATH_CMD_MOVE_TO;
+    }
+
+    pub fn line_to(&mut self, x: f64, y: f64) {
+        self.vertex = 0;
+        self.num_vertices = 0;
+        let flags = clipping_flags(x, y, &self.clip_box);
+
+        if self.clip_flags == flags {
+            if flags == 0 {
+                self.x[0] = x;
+                self.y[0] = y;
+                self.num_vertices = 1;
+            }
+        } else {
+            self.num_vertices = clip_liang_barsky_f64(
+                self.x1,
+                self.y1,
+                x,
+                y,
+                &self.clip_box,
+                &mut self.x,
+                &mut self.y,
+            ) as usize;
+        }
+
+        self.clip_flags = flags;
+        self.x1 = x;
+        self.y1 = y;
+    }
+
+    pub fn vertex(&mut self, x: &mut f64, y: &mut f64) -> u32 {
+        if self.vertex < self.num_vertices {
+            *x = self.x[self.vertex];
+            *y = self.y[self.vertex];
+            self.vertex += 1;
+            let cmd = self.cmd;
+            self.cmd = PATH_CMD_LINE_TO;
+            return cmd;
+        }
+        PATH_CMD_STOP
+    }
+}
+
+impl Default for VpgenClipPolygon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VpgenProcessor for VpgenClipPolygon {
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        self.line_to(x, y);
+    }
+
+    fn vertex(&mut self, x: &mut f64, y: &mut f64) -> u32 {
+        self.vertex(x, y)
+    }
+
+    fn auto_close() -> bool {
+        VpgenClipPolygon::auto_close()
+    }
+
+    fn auto_unclose() -> bool {
+        VpgenClipPolygon::auto_unclose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(vpgen: &mut VpgenClipPolygon) -> Vec<(u32, f64, f64)> {
+        let mut out = Vec::new();
+        let (mut x, mut y) = (0.0, 0.0);
+        loop {
+            let cmd = vpgen.vertex(&mut x, &mut y);
+            if cmd == PATH_CMD_STOP {
+                return out;
+            }
+            out.push((cmd, x, y));
+        }
+    }
+
+    #[test]
+    fn test_clip_box_normalized() {
+        let mut vpgen = VpgenClipPolygon::new();
+        vpgen.clip_box(100.0, 80.0, 10.0, 20.0);
+        assert_eq!(
+            (vpgen.x1(), vpgen.y1(), vpgen.x2(), vpgen.y2()),
+            (10.0, 20.0, 100.0, 80.0)
+        );
+    }
+
+    #[test]
+    fn test_inside_passes_through() {
+        let mut vpgen = VpgenClipPolygon::new();
+        vpgen.clip_box(0.0, 0.0, 100.0, 100.0);
+        vpgen.move_to(10.0, 10.0);
+        assert_eq!(drain(&mut vpgen), vec![(PATH_CMD_MOVE_TO, 10.0, 10.0)]);
+        vpgen.line_to(50.0, 20.0);
+        assert_eq!(drain(&mut vpgen), vec![(PATH_CMD_LINE_TO, 50.0, 20.0)]);
+    }
+
+    #[test]
+    fn test_crossing_segment_clipped() {
+        let mut vpgen = VpgenClipPolygon::new();
+        vpgen.clip_box(0.0, 0.0, 100.0, 100.0);
+        vpgen.move_to(-50.0, 50.0);
+        assert!(drain(&mut vpgen).is_empty());
+        vpgen.line_to(50.0, 50.0);
+        assert_eq!(
+            drain(&mut vpgen),
+            vec![
+                (PATH_CMD_MOVE_TO, 0.0, 50.0),
+                (PATH_CMD_LINE_TO, 50.0, 50.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outside_segment_becomes_corner() {
+        // A segment passing outside a corner turns into that corner.
+        let mut vpgen = VpgenClipPolygon::new();
+        vpgen.clip_box(0.0, 0.0, 100.0, 100.0);
+        vpgen.move_to(-10.0, 5.0);
+        vpgen.line_to(5.0, -10.0);
+        assert_eq!(drain(&mut vpgen), vec![(PATH_CMD_MOVE_TO, 0.0, 0.0)]);
+    }
+}
@@ -0,0 +1,231 @@
+//! Vertex processor generator: polyline clipper.
+//!
+//! Port of `agg_vpgen_clip_polyline.h` + `agg_vpgen_clip_polyline.cpp`.
+//! Clips open polylines to a rectangle, dropping the parts outside and
+//! starting a new sub-path wherever the line re-enters the box.
+
+use crate::basics::{RectD, PATH_CMD_LINE_TO, PATH_CMD_MOVE_TO, PATH_CMD_STOP};
+use crate::clip_liang_barsky::clip_line_segment_f64;
+use crate::conv_adaptor_vpgen::VpgenProcessor;
+
+/// Clips polylines to a rectangular clip box.
+///
+/// Used by `ConvAdaptorVpgen` / `ConvClipPolyline`. Output is never
+/// closed: a closed input comes out as an open polyline that returns to
+/// its start.
+pub struct VpgenClipPolyline {
+    clip_box: RectD,
+    x1: f64,
+    y1: f64,
+    x: [f64; 2],
+    y: [f64; 2],
+    cmd: [u32; 2],
+    num_vertices: usize,
+    vertex: usize,
+    move_to: bool,
+}
+
+impl VpgenClipPolyline {
+    pub fn new() -> Self {
+        Self {
+            clip_box: RectD::new(0.0, 0.0, 1.0, 1.0),
+            x1: 0.0,
+            y1: 0.0,
+            x: [0.0; 2],
+            y: [0.0; 2],
+            cmd: [PATH_CMD_STOP; 2],
+            num_vertices: 0,
+            vertex: 0,
+            move_to: false,
+        }
+    }
+
+    pub fn clip_box(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        self.clip_box = RectD::new(x1, y1, x2, y2);
+        self.clip_box.normalize();
+    }
+
+    pub fn x1(&self) -> f64 {
+        self.clip_box.x1
+    }
+
+    pub fn y1(&self) -> f64 {
+        self.clip_box.y1
+    }
+
+    pub fn x2(&self) -> f64 {
+        self.clip_box.x2
+    }
+
+    pub fn y2(&self) -> f64 {
+        self.clip_box.y2
+    }
+
+    pub fn auto_close() -> bool {
+        false
+    }
+
+    pub fn auto_unclose() -> bool {
+        true
+    }
+
+    pub fn reset(&mut self) {
+        self.vertex = 0;
+        self.num_vertices = 0;
+        self.move_to = false;
+    }
+
+    pub fn move_to(&mut self, x: f64, y: f64) {
+        self.vertex = 0;
+        self.num_vertices = 0;
+        self.x1 = x;
+        self.y1 = y;
+        self.move_to = true;
+    }
+
+    pub fn line_to(&mut self, x: f64, y: f64) {
+        let mut x2 = x;
+        let mut y2 = y;
+        let flags =
+            clip_line_segment_f64(&mut self.x1, &mut self.y1, &mut x2, &mut y2, &self.clip_box);
+
+        self.vertex = 0;
+        self.num_vertices = 0;
+        if (flags & 4) == 0 {
+            // Start a new sub-path where the segment enters the box.
+            if (flags & 1) != 0 || self.move_to {
+                self.x[0] = self.x1;
+                self.y[0] = self.y1;
+                self.cmd[0] = PATH_CMD_MOVE_TO;
+                self.num_vertices = 1;
+            }
+            self.x[self.num_vertices] = x2;
+            self.y[self.num_vertices] = y2;
+            self.cmd[self.num_vertices] = PATH_CMD_LINE_TO;
+            self.num_vertices += 1;
+            self.move_to = (flags & 2) != 0;
+        }
+        self.x1 = x;
+        self.y1 = y;
+    }
+
+    pub fn vertex(&mut self, x: &mut f64, y: &mut f64) -> u32 {
+        if self.vertex < self.num_vertices {
+            *x = self.x[self.vertex];
+            *y = self.y[self.vertex];
+            let cmd = self.cmd[self.vertex];
+            self.vertex += 1;
+            return cmd;
+        }
+        PATH_CMD_STOP
+    }
+}
+
+impl Default for VpgenClipPolyline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VpgenProcessor for VpgenClipPolyline {
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        self.line_to(x, y);
+    }
+
+    fn vertex(&mut self, x: &mut f64, y: &mut f64) -> u32 {
+        self.vertex(x, y)
+    }
+
+    fn auto_close() -> bool {
+        VpgenClipPolyline::auto_close()
+    }
+
+    fn auto_unclose() -> bool {
+        VpgenClipPolyline::auto_unclose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(vpgen: &mut VpgenClipPolyline) -> Vec<(u32, f64, f64)> {
+        let mut out = Vec::new();
+        let (mut x, mut y) = (0.0, 0.0);
+        loop {
+            let cmd = vpgen.vertex(&mut x, &mut y);
+            if cmd == PATH_CMD_STOP {
+                return out;
+            }
+            out.push((cmd, x, y));
+        }
+    }
+
+    #[test]
+    fn test_inside_passes_through() {
+        let mut vpgen = VpgenClipPolyline::new();
+        vpgen.clip_box(0.0, 0.0, 100.0, 100.0);
+        vpgen.move_to(10.0, 10.0);
+        assert!(drain(&mut vpgen).is_empty());
+        vpgen.line_to(50.0, 20.0);
+        assert_eq!(
+            drain(&mut vpgen),
+            vec![
+                (PATH_CMD_MOVE_TO, 10.0, 10.0),
+                (PATH_CMD_LINE_TO, 50.0, 20.0)
+            ]
+        );
+        vpgen.line_to(60.0, 30.0);
+        assert_eq!(drain(&mut vpgen), vec![(PATH_CMD_LINE_TO, 60.0, 30.0)]);
+    }
+
+    #[test]
+    fn test_leaving_and_reentering() {
+        let mut vpgen = VpgenClipPolyline::new();
+        vpgen.clip_box(0.0, 0.0, 100.0, 100.0);
+        vpgen.move_to(50.0, 50.0);
+        vpgen.line_to(150.0, 50.0);
+        assert_eq!(
+            drain(&mut vpgen),
+            vec![
+                (PATH_CMD_MOVE_TO, 50.0, 50.0),
+                (PATH_CMD_LINE_TO, 100.0, 50.0)
+            ]
+        );
+        // Entirely outside: nothing.
+        vpgen.line_to(150.0, 80.0);
+        assert!(drain(&mut vpgen).is_empty());
+        // Back in: a new sub-path from the box edge.
+        vpgen.line_to(50.0, 80.0);
+        assert_eq!(
+            drain(&mut vpgen),
+            vec![
+                (PATH_CMD_MOVE_TO, 100.0, 80.0),
+                (PATH_CMD_LINE_TO, 50.0, 80.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_segment_crossing_box() {
+        let mut vpgen = VpgenClipPolyline::new();
+        vpgen.clip_box(0.0, 0.0, 100.0, 100.0);
+        vpgen.move_to(-50.0, 50.0);
+        vpgen.line_to(150.0, 50.0);
+        assert_eq!(
+            drain(&mut vpgen),
+            vec![
+                (PATH_CMD_MOVE_TO, 0.0, 50.0),
+                (PATH_CMD_LINE_TO, 100.0, 50.0)
+            ]
+        );
+    }
+}
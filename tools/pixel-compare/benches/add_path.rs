@@ -0,0 +1,131 @@
+// Copyright 2026. RasterizerScanlineAa::add_path dispatch benchmark.
+//
+// Feeds the scanline-rasterized geometry of the lion (stroked, as with
+// lion_outline's "Use Scanline Rasterizer") and rasterizers2 demos into the
+// rasterizer twice: once through the monomorphized `add_path` and once as
+// `&mut dyn VertexSource`, which is how `add_path` dispatched before it
+// became generic. Only outline building and cell sorting are timed, so the
+// difference is not drowned out by pixel blending.
+//
+//   cargo bench -p pixel-compare --bench add_path
+
+use agg_rust::basics::VertexSource;
+use agg_rust::bounding_rect::bounding_rect;
+use agg_rust::conv_stroke::ConvStroke;
+use agg_rust::conv_transform::ConvTransform;
+use agg_rust::gsv_text::GsvText;
+use agg_rust::math_stroke::{LineCap, LineJoin};
+use agg_rust::path_storage::PathStorage;
+use agg_rust::rasterizer_scanline_aa::RasterizerScanlineAa;
+use agg_rust::trans_affine::TransAffine;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pixel_compare::render::{parse_lion, Spiral};
+
+/// lion_outline's transform at 512x512 with the default controls.
+fn lion_mtx(path: &mut PathStorage, ids: &[u32]) -> TransAffine {
+    let rect = bounding_rect(path, ids, 0, ids.len()).expect("Lion path has no vertices");
+    let mut mtx = TransAffine::new();
+    mtx.multiply(&TransAffine::new_translation(
+        -(rect.x2 - rect.x1) / 2.0,
+        -(rect.y2 - rect.y1) / 2.0,
+    ));
+    mtx.multiply(&TransAffine::new_rotation(std::f64::consts::PI));
+    mtx.multiply(&TransAffine::new_translation(256.0, 256.0));
+    mtx
+}
+
+/// Lion paths stroked at width 1.
+fn lion(
+    ras: &mut RasterizerScanlineAa,
+    path: &mut PathStorage,
+    ids: &[u32],
+    mtx: TransAffine,
+    dynamic: bool,
+) {
+    for &start in ids {
+        let mut stroke = ConvStroke::new(&mut *path);
+        stroke.set_width(1.0);
+        stroke.set_line_join(LineJoin::Round);
+        let mut transformed = ConvTransform::new(&mut stroke, mtx);
+        ras.reset();
+        if dynamic {
+            ras.add_path(&mut transformed as &mut dyn VertexSource, start);
+        } else {
+            ras.add_path(&mut transformed, start);
+        }
+        black_box(ras.rewind_scanlines());
+    }
+}
+
+/// The rasterizers2 scanline pass at 500x450: the stroked spiral and the
+/// stroked text labels.
+fn rasterizers2(ras: &mut RasterizerScanlineAa, dynamic: bool) {
+    let (w, h) = (500.0, 450.0);
+
+    let mut spiral = Spiral::new(w / 2.0, h - h / 4.0 + 20.0, 5.0, 70.0, 8.0, 0.0);
+    let mut stroke = ConvStroke::new(&mut spiral);
+    stroke.set_width(3.0);
+    stroke.set_line_cap(LineCap::Round);
+    ras.reset();
+    if dynamic {
+        ras.add_path(&mut stroke as &mut dyn VertexSource, 0);
+    } else {
+        ras.add_path(&mut stroke, 0);
+    }
+    black_box(ras.rewind_scanlines());
+
+    let labels = [
+        (50.0, 80.0, "Bresenham lines,\n\nregular accuracy"),
+        (
+            w / 2.0 - 50.0,
+            80.0,
+            "Bresenham lines,\n\nsubpixel accuracy",
+        ),
+        (50.0, h / 2.0 + 50.0, "Anti-aliased lines"),
+        (w / 2.0 - 50.0, h / 2.0 + 50.0, "Scanline rasterizer"),
+        (
+            w - w / 5.0 - 50.0,
+            h / 2.0 + 50.0,
+            "Arbitrary Image Pattern",
+        ),
+    ];
+    for (lx, ly, txt) in labels {
+        let mut t = GsvText::new();
+        t.size(8.0, 0.0);
+        t.text(txt);
+        t.start_point(lx, ly);
+        let mut ts = ConvStroke::new(&mut t);
+        ts.set_width(0.7);
+        ras.reset();
+        if dynamic {
+            ras.add_path(&mut ts as &mut dyn VertexSource, 0);
+        } else {
+            ras.add_path(&mut ts, 0);
+        }
+        black_box(ras.rewind_scanlines());
+    }
+}
+
+fn bench_add_path(c: &mut Criterion) {
+    let (mut path, _colors, path_idx) = parse_lion();
+    let ids: Vec<u32> = path_idx.iter().map(|&i| i as u32).collect();
+    let mtx = lion_mtx(&mut path, &ids);
+    let mut ras = RasterizerScanlineAa::new();
+
+    let mut group = c.benchmark_group("add_path/lion");
+    group.bench_function("generic", |b| {
+        b.iter(|| lion(&mut ras, &mut path, &ids, mtx, false))
+    });
+    group.bench_function("dyn", |b| {
+        b.iter(|| lion(&mut ras, &mut path, &ids, mtx, true))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("add_path/rasterizers2");
+    group.bench_function("generic", |b| b.iter(|| rasterizers2(&mut ras, false)));
+    group.bench_function("dyn", |b| b.iter(|| rasterizers2(&mut ras, true)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_add_path);
+criterion_main!(benches);
@@ -0,0 +1,72 @@
+// Copyright 2026. RGBA32 span blending benchmark.
+//
+// Times `blend_hline`, `blend_solid_hspan` and `blend_color_hspan` over a
+// 1024x1024 buffer for the straight (`PixfmtRgba32`) and premultiplied
+// (`PixfmtBgra32Pre`) formats and for `SrcOver` compositing
+// (`PixfmtRgba32CompOp`). The colors and covers are translucent so that
+// every pixel takes the blend path rather than a copy.
+//
+//   cargo bench -p pixel-compare --bench blend_span
+
+use agg_rust::color::Rgba8;
+use agg_rust::comp_op::PixfmtRgba32CompOp;
+use agg_rust::pixfmt_rgba::{PixelFormat, PixfmtBgra32Pre, PixfmtRgba32};
+use agg_rust::rendering_buffer::RowAccessor;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const W: u32 = 1024;
+const H: u32 = 1024;
+
+/// Blend every row of the buffer with `hline`, `solid_hspan` and
+/// `color_hspan` in turn.
+fn bench_format<PF: PixelFormat<ColorType = Rgba8>>(
+    c: &mut Criterion,
+    name: &str,
+    pf: &mut PF,
+    covers: &[u8],
+    colors: &[Rgba8],
+) {
+    let color = Rgba8::new(200, 100, 50, 160);
+    let mut group = c.benchmark_group(format!("blend_span/{name}"));
+    group.bench_function("hline", |b| {
+        b.iter(|| {
+            for y in 0..H as i32 {
+                pf.blend_hline(0, y, W, &color, 200);
+            }
+        })
+    });
+    group.bench_function("solid_hspan", |b| {
+        b.iter(|| {
+            for y in 0..H as i32 {
+                pf.blend_solid_hspan(0, y, W, &color, covers);
+            }
+        })
+    });
+    group.bench_function("color_hspan", |b| {
+        b.iter(|| {
+            for y in 0..H as i32 {
+                pf.blend_color_hspan(0, y, W, colors, covers, 255);
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_blend_span(c: &mut Criterion) {
+    let mut buf = vec![128u8; (W * H * 4) as usize];
+    let mut rbuf = unsafe { RowAccessor::new_with_buf(buf.as_mut_ptr(), W, H, (W * 4) as i32) };
+    let covers: Vec<u8> = (0..W).map(|i| (i * 7 % 256) as u8).collect();
+    let colors: Vec<Rgba8> = (0..W)
+        .map(|i| Rgba8::new(i % 256, i * 3 % 256, 90, i * 5 % 256))
+        .collect();
+
+    let mut pf = PixfmtRgba32::new(&mut rbuf);
+    bench_format(c, "rgba32", &mut pf, &covers, &colors);
+    let mut pf = PixfmtBgra32Pre::new(&mut rbuf);
+    bench_format(c, "bgra32_pre", &mut pf, &covers, &colors);
+    let mut pf = PixfmtRgba32CompOp::new(&mut rbuf);
+    bench_format(c, "rgba32_src_over", &mut pf, &covers, &colors);
+}
+
+criterion_group!(benches, bench_blend_span);
+criterion_main!(benches);
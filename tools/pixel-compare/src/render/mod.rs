@@ -7,6 +7,8 @@ mod image_filters;
 mod lion_outline;
 mod rasterizers2;
 
+pub use rasterizers2::Spiral;
+
 use crate::PixelBuffer;
 
 /// Render a named demo at the given dimensions with the given parameters.
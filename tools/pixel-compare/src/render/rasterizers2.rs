@@ -30,7 +30,7 @@ use agg_rust::scanline_u::ScanlineU8;
 // Spiral vertex source — matching C++ rasterizers2.cpp spiral class
 // ============================================================================
 
-struct Spiral {
+pub struct Spiral {
     cx: f64,
     cy: f64,
     r1: f64,
@@ -44,7 +44,7 @@ struct Spiral {
 }
 
 impl Spiral {
-    fn new(cx: f64, cy: f64, r1: f64, r2: f64, step: f64, start_angle: f64) -> Self {
+    pub fn new(cx: f64, cy: f64, r1: f64, r2: f64, step: f64, start_angle: f64) -> Self {
         let da = (8.0_f64).to_radians();
         Self {
             cx, cy, r1, r2, da,